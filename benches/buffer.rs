@@ -0,0 +1,124 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+};
+
+/// Benchmark for creating an empty buffer, as done by the terminal for each of its buffers on
+/// every resize.
+pub fn empty(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer/empty");
+    for area in [Rect::new(0, 0, 100, 50), Rect::new(0, 0, 300, 100)] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(area.area()),
+            &area,
+            |b, &area| b.iter(|| Buffer::empty(black_box(area))),
+        );
+    }
+    group.finish();
+}
+
+/// Benchmark for merging a buffer into a larger one, which moves and clones every cell.
+pub fn merge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer/merge");
+    for area in [Rect::new(0, 0, 100, 50), Rect::new(0, 0, 300, 100)] {
+        let top = filled_buffer(Rect {
+            height: area.height / 2,
+            ..area
+        });
+        let bottom = filled_buffer(Rect {
+            y: area.height / 2,
+            height: area.height - area.height / 2,
+            ..area
+        });
+        group.bench_with_input(
+            BenchmarkId::from_parameter(area.area()),
+            &bottom,
+            |b, bottom| {
+                b.iter_batched(
+                    || top.clone(),
+                    |mut top| top.merge(bottom),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Benchmark for diffing two buffers, as done by the terminal on every frame. Most frames of an
+/// application that sits idle are identical to the previous one, or only change a few rows.
+pub fn diff(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer/diff");
+    for area in [
+        Rect::new(0, 0, 100, 50),  // vertically split screen
+        Rect::new(0, 0, 300, 100), // fullscreen on a large monitor
+    ] {
+        let previous = filled_buffer(area);
+        let id = area.area();
+
+        // nothing changed since the last frame
+        let current = previous.clone();
+        group.bench_with_input(BenchmarkId::new("unchanged", id), &current, |b, current| {
+            b.iter(|| black_box(previous.diff(current)))
+        });
+
+        // nothing changed, but the frame was drawn again, so none of its rows were hashed yet
+        group.bench_with_input(BenchmarkId::new("redrawn", id), &area, |b, &area| {
+            b.iter_batched(
+                || filled_buffer(area),
+                |current| black_box(previous.diff(&current).len()),
+                BatchSize::LargeInput,
+            )
+        });
+
+        // a mostly blank frame, e.g. a dialog in the middle of the screen, where the rows that
+        // nothing was drawn to are compared by hash
+        let dialog = |message: &str| {
+            let mut buffer = Buffer::empty(area);
+            for y in area.height / 2 - 2..area.height / 2 + 2 {
+                buffer.set_string(area.width / 4, y, message, Style::new());
+            }
+            buffer
+        };
+        let sparse = dialog("Are you sure?");
+        group.bench_with_input(BenchmarkId::new("sparse", id), &area, |b, _| {
+            b.iter_batched(
+                || dialog("Are you sure?"),
+                |current| black_box(sparse.diff(&current).len()),
+                BatchSize::LargeInput,
+            )
+        });
+
+        // a status line at the bottom changed, e.g. a clock
+        let mut current = previous.clone();
+        current.set_string(0, area.bottom() - 1, "12:34:56", Style::new());
+        group.bench_with_input(BenchmarkId::new("one_row", id), &current, |b, current| {
+            b.iter(|| black_box(previous.diff(current)))
+        });
+
+        // everything changed, e.g. the first frame or a new screen
+        let current = Buffer::empty(area);
+        group.bench_with_input(BenchmarkId::new("all_rows", id), &current, |b, current| {
+            b.iter(|| black_box(previous.diff(current)))
+        });
+    }
+    group.finish();
+}
+
+/// Returns a buffer covering `area` with some text in every row.
+fn filled_buffer(area: Rect) -> Buffer {
+    let mut buffer = Buffer::empty(area);
+    for y in area.top()..area.bottom() {
+        let line = format!("{y:>4} │ The quick brown fox jumps over the lazy dog ");
+        let style = Style::new().fg(Color::Indexed(y as u8));
+        for x in (area.left()..area.right()).step_by(line.chars().count()) {
+            buffer.set_stringn(x, y, &line, (area.right() - x) as usize, style);
+        }
+    }
+    buffer
+}
+
+criterion_group!(benches, empty, merge, diff);
+criterion_main!(benches);
@@ -3,11 +3,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crossterm::{
-    event::{self, Event, KeyCode},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
+use crossterm::event::{self, Event, KeyCode};
 use ratatui::{
     prelude::*,
     widgets::{canvas::*, *},
@@ -73,7 +69,7 @@ impl App {
                 last_tick = Instant::now();
             }
         }
-        restore_terminal()
+        terminal.restore()
     }
 
     fn on_tick(&mut self) {
@@ -185,14 +181,6 @@ impl App {
     }
 }
 
-fn init_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    Terminal::new(CrosstermBackend::new(stdout()))
-}
-
-fn restore_terminal() -> io::Result<()> {
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
-    Ok(())
+fn init_terminal() -> io::Result<TerminalSession<CrosstermBackend<Stdout>>> {
+    TerminalSession::new(CrosstermBackend::new(stdout()))
 }
@@ -7,11 +7,7 @@ use std::{
     time::Duration,
 };
 
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode};
 use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
 
@@ -27,7 +23,7 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
     loop {
         terminal.draw(ui)?;
 
@@ -277,19 +273,13 @@ fn render_indexed_grayscale(frame: &mut Frame, area: Rect) {
     }
 }
 
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn setup_terminal() -> Result<TerminalSession<CrosstermBackend<Stdout>>> {
+    let mut terminal = TerminalSession::new(CrosstermBackend::new(io::stdout()))?;
     terminal.hide_cursor()?;
     Ok(terminal)
 }
 
-fn restore_terminal(mut terminal: Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+fn restore_terminal(mut terminal: TerminalSession<CrosstermBackend<Stdout>>) -> Result<()> {
+    terminal.restore()?;
     Ok(())
 }
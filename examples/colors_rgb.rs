@@ -8,11 +8,7 @@ use std::{
     time::Duration,
 };
 
-use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use palette::{
     convert::{FromColorUnclamped, IntoColorUnclamped},
     Okhsv, Srgb,
@@ -22,31 +18,31 @@ use ratatui::{prelude::*, widgets::*};
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
 fn main() -> Result<()> {
-    install_panic_hook();
+    // the terminal session chains its panic hook, which restores the terminal, before this one
+    better_panic::install();
     App::new()?.run()
 }
 
 struct App {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    terminal: TerminalSession<CrosstermBackend<Stdout>>,
     should_quit: bool,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
         Ok(Self {
-            terminal: Terminal::new(CrosstermBackend::new(stdout()))?,
+            terminal: TerminalSession::new(CrosstermBackend::new(stdout()))?,
             should_quit: false,
         })
     }
 
     pub fn run(mut self) -> Result<()> {
-        init_terminal()?;
         self.terminal.clear()?;
         while !self.should_quit {
             self.draw()?;
             self.handle_events()?;
         }
-        restore_terminal()?;
+        self.terminal.restore()?;
         Ok(())
     }
 
@@ -69,12 +65,6 @@ impl App {
     }
 }
 
-impl Drop for App {
-    fn drop(&mut self) {
-        let _ = restore_terminal();
-    }
-}
-
 struct RgbColors;
 
 impl Widget for RgbColors {
@@ -123,25 +113,3 @@ impl RgbColors {
         }
     }
 }
-
-/// Install a panic hook that restores the terminal before panicking.
-fn install_panic_hook() {
-    better_panic::install();
-    let prev_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |info| {
-        let _ = restore_terminal();
-        prev_hook(info);
-    }));
-}
-
-fn init_terminal() -> Result<()> {
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    Ok(())
-}
-
-fn restore_terminal() -> Result<()> {
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
-    Ok(())
-}
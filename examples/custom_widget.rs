@@ -1,13 +1,6 @@
 use std::{error::Error, io, ops::ControlFlow, time::Duration};
 
-use crossterm::{
-    event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent,
-        MouseEventKind,
-    },
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{prelude::*, widgets::*};
 
 /// A custom widget that renders a button with a label, theme and state.
@@ -121,23 +114,17 @@ impl Button<'_> {
 
 fn main() -> Result<(), Box<dyn Error>> {
     // setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let options = SessionOptions {
+        mouse_capture: true,
+        ..Default::default()
+    };
+    let mut terminal = TerminalSession::with_options(CrosstermBackend::new(io::stdout()), options)?;
 
     // create app and run it
     let res = run_app(&mut terminal);
 
     // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    terminal.restore()?;
 
     if let Err(err) = res {
         println!("{err:?}");
@@ -146,7 +133,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), B::Error> {
     let mut selected_button: usize = 0;
     let button_states = &mut [State::Selected, State::Normal, State::Normal];
     loop {
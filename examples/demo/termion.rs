@@ -1,25 +1,17 @@
 use std::{error::Error, io, sync::mpsc, thread, time::Duration};
 
 use ratatui::prelude::*;
-use termion::{
-    event::Key,
-    input::{MouseTerminal, TermRead},
-    raw::IntoRawMode,
-    screen::IntoAlternateScreen,
-};
+use termion::{event::Key, input::TermRead};
 
 use crate::{app::App, ui};
 
 pub fn run(tick_rate: Duration, enhanced_graphics: bool) -> Result<(), Box<dyn Error>> {
     // setup terminal
-    let stdout = io::stdout()
-        .into_raw_mode()
-        .unwrap()
-        .into_alternate_screen()
-        .unwrap();
-    let stdout = MouseTerminal::from(stdout);
-    let backend = TermionBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let options = SessionOptions {
+        mouse_capture: true,
+        ..Default::default()
+    };
+    let mut terminal = TerminalSession::with_options(TermionBackend::new(io::stdout()), options)?;
 
     // create app and run it
     let app = App::new("Termion demo", enhanced_graphics);
@@ -9,16 +9,14 @@ use termwiz::{input::*, terminal::Terminal as TermwizTerminal};
 use crate::{app::App, ui};
 
 pub fn run(tick_rate: Duration, enhanced_graphics: bool) -> Result<(), Box<dyn Error>> {
-    let backend = TermwizBackend::new()?;
-    let mut terminal = Terminal::new(backend)?;
+    let mut terminal = TerminalSession::new(TermwizBackend::new()?)?;
     terminal.hide_cursor()?;
 
     // create app and run it
     let app = App::new("Termwiz Demo", enhanced_graphics);
     let res = run_app(&mut terminal, app, tick_rate);
 
-    terminal.show_cursor()?;
-    terminal.flush()?;
+    terminal.restore()?;
 
     if let Err(err) = res {
         println!("{err:?}");
@@ -35,7 +35,7 @@ impl App {
             app.draw()?;
             app.handle_events()?;
         }
-        Term::stop()?;
+        app.term.stop()?;
         Ok(())
     }
 
@@ -91,8 +91,8 @@ impl App {
 pub fn install_panic_hook() {
     better_panic::install();
     let hook = std::panic::take_hook();
+    // the terminal session restores the terminal before this hook is called
     std::panic::set_hook(Box::new(move |info| {
-        let _ = Term::stop();
         hook(info);
         std::process::exit(1);
     }));
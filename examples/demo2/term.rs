@@ -1,45 +1,38 @@
 use std::{
-    io::{self, stdout, Stdout},
+    io::{self, Stdout},
     ops::{Deref, DerefMut},
     time::Duration,
 };
 
 use anyhow::{Context, Result};
-use crossterm::{
-    event::{self, Event},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
+use crossterm::event::{self, Event};
 use ratatui::prelude::*;
 
 /// A wrapper around the terminal that handles setting up and tearing down the terminal
 /// and provides a helper method to read events from the terminal.
 #[derive(Debug)]
 pub struct Term {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    terminal: TerminalSession<CrosstermBackend<Stdout>>,
 }
 
 impl Term {
     pub fn start() -> Result<Self> {
         // this size is to match the size of the terminal when running the demo
         // using vhs in a 1280x640 sized window (github social preview size)
-        let options = TerminalOptions {
-            viewport: Viewport::Fixed(Rect::new(0, 0, 81, 18)),
+        let options = SessionOptions {
+            terminal: TerminalOptions {
+                viewport: Viewport::Fixed(Rect::new(0, 0, 81, 18)),
+                ..Default::default()
+            },
+            ..Default::default()
         };
-        let terminal = Terminal::with_options(CrosstermBackend::new(io::stdout()), options)?;
-        enable_raw_mode().context("enable raw mode")?;
-        stdout()
-            .execute(EnterAlternateScreen)
-            .context("enter alternate screen")?;
+        let terminal = TerminalSession::with_options(CrosstermBackend::new(io::stdout()), options)
+            .context("start terminal session")?;
         Ok(Self { terminal })
     }
 
-    pub fn stop() -> Result<()> {
-        disable_raw_mode().context("disable raw mode")?;
-        stdout()
-            .execute(LeaveAlternateScreen)
-            .context("leave alternate screen")?;
-        Ok(())
+    pub fn stop(&mut self) -> Result<()> {
+        self.terminal.restore().context("restore terminal")
     }
 
     pub fn next_event(timeout: Duration) -> io::Result<Option<Event>> {
@@ -63,9 +56,3 @@ impl DerefMut for Term {
         &mut self.terminal
     }
 }
-
-impl Drop for Term {
-    fn drop(&mut self) {
-        let _ = Term::stop();
-    }
-}
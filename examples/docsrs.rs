@@ -1,9 +1,5 @@
 use std::io::{self, stdout};
 
-use crossterm::{
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
 use ratatui::{prelude::*, widgets::*};
 
 /// Example code for libr.rs
@@ -12,9 +8,7 @@ use ratatui::{prelude::*, widgets::*};
 /// rather than copied to the lib.rs file.
 fn main() -> io::Result<()> {
     let arg = std::env::args().nth(1).unwrap_or_default();
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let mut terminal = TerminalSession::new(CrosstermBackend::new(stdout()))?;
 
     let mut should_quit = false;
     while !should_quit {
@@ -27,8 +21,7 @@ fn main() -> io::Result<()> {
         should_quit = handle_events()?;
     }
 
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
+    terminal.restore()?;
     Ok(())
 }
 
@@ -5,11 +5,7 @@ use std::{
 };
 
 use anyhow::Result;
-use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::{
     prelude::*,
     widgets::{block::Title, *},
@@ -61,7 +57,7 @@ impl App {
     }
 
     fn stop(&mut self) -> Result<()> {
-        Term::stop()?;
+        self.term.stop()?;
         Ok(())
     }
 
@@ -163,21 +159,17 @@ impl App {
 }
 
 struct Term {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    terminal: TerminalSession<CrosstermBackend<Stdout>>,
 }
 
 impl Term {
     pub fn start() -> io::Result<Term> {
-        stdout().execute(EnterAlternateScreen)?;
-        enable_raw_mode()?;
-        let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        let terminal = TerminalSession::new(CrosstermBackend::new(stdout()))?;
         Ok(Self { terminal })
     }
 
-    pub fn stop() -> io::Result<()> {
-        disable_raw_mode()?;
-        stdout().execute(LeaveAlternateScreen)?;
-        Ok(())
+    pub fn stop(&mut self) -> io::Result<()> {
+        self.terminal.restore()
     }
 
     fn draw(&mut self, frame: impl FnOnce(&mut Frame)) -> Result<()> {
@@ -4,21 +4,16 @@ use std::{
 };
 
 use anyhow::{Context, Result};
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode};
 use ratatui::{prelude::*, widgets::*};
 
 /// This is a bare minimum example. There are many approaches to running an application loop, so
 /// this is not meant to be prescriptive. It is only meant to demonstrate the basic setup and
 /// teardown of a terminal application.
 ///
-/// A more robust application would probably want to handle errors and ensure that the terminal is
-/// restored to a sane state before exiting. This example does not do that. It also does not handle
-/// events or update the application state. It just draws a greeting and exits when the user
-/// presses 'q'.
+/// The terminal is restored to a sane state before exiting, even if the application loop fails.
+/// This example does not handle events or update the application state. It just draws a greeting
+/// and exits when the user presses 'q'.
 fn main() -> Result<()> {
     let mut terminal = setup_terminal().context("setup failed")?;
     run(&mut terminal).context("app loop failed")?;
@@ -26,23 +21,16 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-/// Setup the terminal. This is where you would enable raw mode, enter the alternate screen, and
-/// hide the cursor. This example does not handle errors. A more robust application would probably
-/// want to handle errors and ensure that the terminal is restored to a sane state before exiting.
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
-    let mut stdout = io::stdout();
-    enable_raw_mode().context("failed to enable raw mode")?;
-    execute!(stdout, EnterAlternateScreen).context("unable to enter alternate screen")?;
-    Terminal::new(CrosstermBackend::new(stdout)).context("creating terminal failed")
+/// Setup the terminal. The terminal session enables raw mode and enters the alternate screen for as
+/// long as it lives.
+fn setup_terminal() -> Result<TerminalSession<CrosstermBackend<Stdout>>> {
+    TerminalSession::new(CrosstermBackend::new(io::stdout())).context("creating terminal failed")
 }
 
 /// Restore the terminal. This is where you disable raw mode, leave the alternate screen, and show
-/// the cursor.
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
-    disable_raw_mode().context("failed to disable raw mode")?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)
-        .context("unable to switch to main screen")?;
-    terminal.show_cursor().context("unable to show cursor")
+/// the cursor. The session also does this when it is dropped, but without reporting errors.
+fn restore_terminal(terminal: &mut TerminalSession<CrosstermBackend<Stdout>>) -> Result<()> {
+    terminal.restore().context("unable to restore terminal")
 }
 
 /// Run the application loop. This is where you would handle events and update the application
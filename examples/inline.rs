@@ -8,7 +8,7 @@ use std::{
 };
 
 use rand::distributions::{Distribution, Uniform};
-use ratatui::{prelude::*, widgets::*};
+use ratatui::{prelude::*, style::ColorDepth, widgets::*};
 
 const NUM_DOWNLOADS: usize = 10;
 
@@ -64,15 +64,16 @@ struct Worker {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    crossterm::terminal::enable_raw_mode()?;
-    let stdout = io::stdout();
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::with_options(
-        backend,
-        TerminalOptions {
+    let options = SessionOptions {
+        terminal: TerminalOptions {
             viewport: Viewport::Inline(8),
+            color_depth: ColorDepth::detect(),
+            ..Default::default()
         },
-    )?;
+        alternate_screen: false,
+        ..Default::default()
+    };
+    let mut terminal = TerminalSession::with_options(CrosstermBackend::new(io::stdout()), options)?;
 
     let (tx, rx) = mpsc::channel();
     input_handling(tx.clone());
@@ -86,7 +87,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     run_app(&mut terminal, workers, downloads, rx)?;
 
-    crossterm::terminal::disable_raw_mode()?;
+    terminal.restore()?;
     terminal.clear()?;
 
     Ok(())
@@ -9,11 +9,7 @@ use std::{
     time::Duration,
 };
 
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode};
 use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
 
@@ -29,7 +25,7 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
     loop {
         terminal.draw(ui)?;
 
@@ -98,19 +94,13 @@ fn ui(frame: &mut Frame) {
     }
 }
 
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn setup_terminal() -> Result<TerminalSession<CrosstermBackend<Stdout>>> {
+    let mut terminal = TerminalSession::new(CrosstermBackend::new(io::stdout()))?;
     terminal.hide_cursor()?;
     Ok(terminal)
 }
 
-fn restore_terminal(mut terminal: Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+fn restore_terminal(mut terminal: TerminalSession<CrosstermBackend<Stdout>>) -> Result<()> {
+    terminal.restore()?;
     Ok(())
 }
@@ -12,13 +12,14 @@
 //! distort the output.
 //!
 //! That's why this example is set up to show both situations, with and without
-//! the chained panic hook, to see the difference.
+//! the chained panic hook, to see the difference. A [`TerminalSession`] installs
+//! such a hook by default, so it is disabled here.
 
 use std::{error::Error, io};
 
 use crossterm::{
     event::{self, Event, KeyCode},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
 use ratatui::{prelude::*, widgets::*};
 
@@ -48,7 +49,7 @@ fn main() -> Result<()> {
     let mut app = App::default();
     let res = run_tui(&mut terminal, &mut app);
 
-    reset_terminal()?;
+    terminal.restore()?;
 
     if let Err(err) = res {
         println!("{err:?}");
@@ -58,19 +59,20 @@ fn main() -> Result<()> {
 }
 
 /// Initializes the terminal.
-fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
-    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
-    enable_raw_mode()?;
-
+fn init_terminal() -> Result<TerminalSession<CrosstermBackend<io::Stdout>>> {
     let backend = CrosstermBackend::new(io::stdout());
+    let options = SessionOptions {
+        panic_hook: false,
+        ..Default::default()
+    };
 
-    let mut terminal = Terminal::new(backend)?;
+    let mut terminal = TerminalSession::with_options(backend, options)?;
     terminal.hide_cursor()?;
 
     Ok(terminal)
 }
 
-/// Resets the terminal.
+/// Resets the terminal from the panic hook, which can't reach the session.
 fn reset_terminal() -> Result<()> {
     disable_raw_mode()?;
     crossterm::execute!(io::stdout(), LeaveAlternateScreen)?;
@@ -79,7 +81,7 @@ fn reset_terminal() -> Result<()> {
 }
 
 /// Runs the TUI loop.
-fn run_tui<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
+fn run_tui<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     loop {
         terminal.draw(|f| ui(f, app))?;
 
@@ -115,8 +117,8 @@ fn ui(f: &mut Frame, app: &App) {
         Line::from("press any other key to quit without panic"),
         Line::from(""),
         Line::from("when you panic without the chained hook,"),
-        Line::from("you will likely have to reset your terminal afterwards"),
-        Line::from("with the `reset` command"),
+        Line::from("the panic report is printed to the alternate screen"),
+        Line::from("and is lost when the terminal is restored"),
         Line::from(""),
         Line::from("with the chained panic hook enabled,"),
         Line::from("you should see the panic report as you would without ratatui"),
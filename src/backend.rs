@@ -9,7 +9,12 @@
 //! - [Termion]: enable the `termion` feature and use [`TermionBackend`]
 //! - [Termwiz]: enable the `termwiz` feature and use [`TermwizBackend`]
 //!
-//! Additionally, a [`TestBackend`] is provided for testing purposes.
+//! The [`AnsiBackend`] writes plain ANSI escape sequences to any [`std::io::Write`] without
+//! depending on a terminal library, and the [`AnsiParser`] replays such escape sequences into a
+//! [`Buffer`](crate::buffer::Buffer). The [`AsciicastBackend`] wraps any other backend and records
+//! what is drawn as an [asciinema](https://asciinema.org) recording, and the [`TeeBackend`] renders
+//! to two backends at once. Additionally, a [`TestBackend`] is provided for testing purposes, along
+//! with [`assert_buffer_snapshot`] to compare rendered buffers to golden files.
 //!
 //! See the [Backend Comparison] section of the [Ratatui Website] for more details on the different
 //! backends.
@@ -17,7 +22,8 @@
 //! Each backend supports a number of features, such as [raw mode](#raw-mode), [alternate
 //! screen](#alternate-screen), and [mouse capture](#mouse-capture). These features are generally
 //! not enabled by default, and must be enabled by the application before they can be used. See the
-//! documentation for each backend for more details.
+//! documentation for each backend for more details. A [`TerminalSession`] enables them through the
+//! [`Backend`] trait and restores the terminal when it is dropped or when the application panics.
 //!
 //! Note: most applications should use the [`Terminal`] struct instead of directly calling methods
 //! on the backend.
@@ -91,6 +97,7 @@
 //!
 //! [`TermionBackend`]: termion/struct.TermionBackend.html
 //! [`Terminal`]: crate::terminal::Terminal
+//! [`TerminalSession`]: crate::terminal::TerminalSession
 //! [`TermionBackend`]: termion/struct.TermionBackend.html
 //! [Crossterm]: https://crates.io/crates/crossterm
 //! [Termion]: https://crates.io/crates/termion
@@ -99,11 +106,11 @@
 //! [Backend Comparison]:
 //!     https://ratatui-org.github.io/ratatui-book/concepts/backends/comparison.html
 //! [Ratatui Website]: https://ratatui-org.github.io/ratatui-book
-use std::io;
+use std::{io, ops::Range, time::Duration};
 
 use strum::{Display, EnumString};
 
-use crate::{buffer::Cell, layout::Size, prelude::Rect};
+use crate::{buffer::Cell, event::Event, layout::Size, prelude::Rect, style::Color};
 
 #[cfg(feature = "termion")]
 mod termion;
@@ -120,8 +127,20 @@ mod termwiz;
 #[cfg(feature = "termwiz")]
 pub use self::termwiz::TermwizBackend;
 
+mod ansi;
+#[cfg(feature = "underline-color")]
+pub(crate) use self::ansi::UnderlineColor;
+pub(crate) use self::ansi::{base64, Bg, Fg, Hyperlink, ModifierDiff, ResetAttributes};
+pub use self::ansi::{AnsiBackend, AnsiParser};
+
+mod asciicast;
+pub use self::asciicast::AsciicastBackend;
+
+mod tee;
+pub use self::tee::TeeBackend;
+
 mod test;
-pub use self::test::TestBackend;
+pub use self::test::{assert_buffer_snapshot, TestBackend, TestReply};
 
 /// Enum representing the different types of clearing operations that can be performed
 /// on the terminal screen.
@@ -139,6 +158,40 @@ pub enum ClearType {
     UntilNewLine,
 }
 
+/// The shape of the cursor and whether it blinks.
+///
+/// Backends set it with the `DECSCUSR` escape sequence, which most terminals support. The default,
+/// [`CursorStyle::DefaultUserShape`], is the shape configured by the user in their terminal.
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorStyle {
+    /// The shape configured in the terminal.
+    #[default]
+    DefaultUserShape,
+    /// A blinking block (`█`).
+    BlinkingBlock,
+    /// A steady block (`█`).
+    SteadyBlock,
+    /// A blinking underline (`_`).
+    BlinkingUnderline,
+    /// A steady underline (`_`).
+    SteadyUnderline,
+    /// A blinking vertical bar (`|`).
+    BlinkingBar,
+    /// A steady vertical bar (`|`).
+    SteadyBar,
+}
+
+/// One of the default colors of the terminal, see [`Backend::query_default_color`].
+#[derive(Debug, Display, EnumString, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DefaultColor {
+    /// The color of text drawn with [`Color::Reset`] as foreground (`OSC 10`).
+    Foreground,
+    /// The color of cells drawn with [`Color::Reset`] as background (`OSC 11`).
+    Background,
+}
+
 /// The window size in characters (columns / rows) as well as pixels.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct WindowSize {
@@ -160,22 +213,224 @@ pub struct WindowSize {
 ///
 /// [`Terminal`]: crate::terminal::Terminal
 pub trait Backend {
+    /// The error returned by the methods of the backend.
+    ///
+    /// Backends that write to a terminal use [`io::Error`]. Other backends can report their
+    /// failures with their own type, which must be convertible from [`io::Error`] as the optional
+    /// methods return an error of kind [`io::ErrorKind::Unsupported`] by default. The [`Terminal`]
+    /// recognizes these errors as long as they are the error itself or one of its
+    /// [sources](std::error::Error::source), so that it can do without the unsupported features.
+    ///
+    /// [`Terminal`]: crate::terminal::Terminal
+    type Error: std::error::Error + From<io::Error> + 'static;
+
     /// Draw the given content to the terminal screen.
     ///
     /// The content is provided as an iterator over `(u16, u16, &Cell)` tuples, where the first two
     /// elements represent the x and y coordinates, and the third element is a reference to the
     /// [`Cell`] to be drawn.
-    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>;
 
     /// Insert `n` line breaks to the terminal screen.
     ///
     /// This method is optional and may not be implemented by all backends.
-    fn append_lines(&mut self, _n: u16) -> io::Result<()> {
+    fn append_lines(&mut self, _n: u16) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Begin a synchronized update of the terminal screen.
+    ///
+    /// Terminals that support synchronized output (DEC private mode 2026) hold back rendering of
+    /// everything written until [`end_synchronized_update`] is called, so that a frame is never
+    /// displayed half-drawn. Terminals without support ignore it.
+    ///
+    /// This method is optional and does nothing by default.
+    ///
+    /// [`end_synchronized_update`]: Backend::end_synchronized_update
+    fn begin_synchronized_update(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// End a synchronized update of the terminal screen started by [`begin_synchronized_update`].
+    ///
+    /// This method is optional and does nothing by default.
+    ///
+    /// [`begin_synchronized_update`]: Backend::begin_synchronized_update
+    fn end_synchronized_update(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
 
+    /// Enables [raw mode](self#raw-mode).
+    ///
+    /// Most applications should use a [`TerminalSession`], which enables raw mode when it is
+    /// created and disables it when it is dropped.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`TerminalSession`]: crate::terminal::TerminalSession
+    fn enable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "raw mode is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Disables raw mode enabled by [`enable_raw_mode`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`enable_raw_mode`]: Backend::enable_raw_mode
+    fn disable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "raw mode is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Switches to the [alternate screen](self#alternate-screen).
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    fn enter_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the alternate screen is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Switches back to the main screen after [`enter_alternate_screen`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`enter_alternate_screen`]: Backend::enter_alternate_screen
+    fn leave_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the alternate screen is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Enables [mouse capture](self#mouse-capture).
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    fn enable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "mouse capture is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Disables mouse capture enabled by [`enable_mouse_capture`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`enable_mouse_capture`]: Backend::enable_mouse_capture
+    fn disable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "mouse capture is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Returns a function that disables raw mode and mouse capture and leaves the alternate
+    /// screen without going through the backend.
+    ///
+    /// The function is called by the panic hook of a [`TerminalSession`], where the backend can't
+    /// be reached, so that the panic message is printed to a usable terminal. It must do nothing
+    /// harmful when the modes are not enabled.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`], in which case the
+    /// terminal is only restored when the session is dropped.
+    ///
+    /// [`TerminalSession`]: crate::terminal::TerminalSession
+    fn panic_restorer(&self) -> Result<PanicRestorer, Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "restoring the terminal from a panic hook is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Sets the title of the terminal window (`OSC 2`).
+    ///
+    /// Control characters are removed from the title. Most terminals keep the title after the
+    /// application exits.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::backend::{Backend, TestBackend};
+    /// # let mut backend = TestBackend::new(80, 25);
+    /// backend.set_title("My application")?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    fn set_title(&mut self, title: &str) -> Result<(), Self::Error> {
+        let _ = title;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "setting the window title is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Copies `content` to the system clipboard (`OSC 52`).
+    ///
+    /// The content is sent to the terminal emulator, so this also works when the application runs
+    /// on a remote host over SSH. Some terminals ignore the sequence or require it to be enabled
+    /// in their settings, and there is no way to know whether it succeeded.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::backend::{Backend, TestBackend};
+    /// # let mut backend = TestBackend::new(80, 25);
+    /// backend.set_clipboard("copied text")?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    fn set_clipboard(&mut self, content: &str) -> Result<(), Self::Error> {
+        let _ = content;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "setting the clipboard is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Rings the terminal bell (`BEL`).
+    ///
+    /// Depending on its settings, the terminal plays a sound, flashes the screen or marks the
+    /// window as urgent.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    fn bell(&mut self) -> Result<(), Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "ringing the bell is not supported with this backend",
+        )
+        .into())
+    }
+
     /// Hide the cursor on the terminal screen.
     ///
     ///
@@ -192,14 +447,14 @@ pub trait Backend {
     /// ```
     ///
     /// [`show_cursor`]: Backend::show_cursor
-    fn hide_cursor(&mut self) -> io::Result<()>;
+    fn hide_cursor(&mut self) -> Result<(), Self::Error>;
 
     /// Show the cursor on the terminal screen.
     ///
     /// See [`hide_cursor`] for an example.
     ///
     /// [`hide_cursor`]: Backend::hide_cursor
-    fn show_cursor(&mut self) -> io::Result<()>;
+    fn show_cursor(&mut self) -> Result<(), Self::Error>;
 
     /// Get the current cursor position on the terminal screen.
     ///
@@ -209,7 +464,7 @@ pub trait Backend {
     /// See [`set_cursor`] for an example.
     ///
     /// [`set_cursor`]: Backend::set_cursor
-    fn get_cursor(&mut self) -> io::Result<(u16, u16)>;
+    fn get_cursor(&mut self) -> Result<(u16, u16), Self::Error>;
 
     /// Set the cursor position on the terminal screen to the given x and y coordinates.
     ///
@@ -226,7 +481,36 @@ pub trait Backend {
     /// ```
     ///
     /// [`get_cursor`]: Backend::get_cursor
-    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()>;
+    fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), Self::Error>;
+
+    /// Set the shape of the cursor and whether it blinks.
+    ///
+    /// The style is kept until it is changed again, including after the application exits, so
+    /// applications should restore [`CursorStyle::DefaultUserShape`] before exiting. The
+    /// [`Terminal`] does this automatically for styles set with [`Frame::set_cursor_style`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::backend::{Backend, CursorStyle, TestBackend};
+    /// # let mut backend = TestBackend::new(80, 25);
+    /// backend.set_cursor_style(CursorStyle::SteadyBar)?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    ///
+    /// [`Terminal`]: crate::terminal::Terminal
+    /// [`Frame::set_cursor_style`]: crate::terminal::Frame::set_cursor_style
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), Self::Error> {
+        let _ = style;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cursor styles are not supported with this backend",
+        )
+        .into())
+    }
 
     /// Clears the whole terminal screen
     ///
@@ -238,7 +522,7 @@ pub trait Backend {
     /// backend.clear()?;
     /// # std::io::Result::Ok(())
     /// ```
-    fn clear(&mut self) -> io::Result<()>;
+    fn clear(&mut self) -> Result<(), Self::Error>;
 
     /// Clears a specific region of the terminal specified by the [`ClearType`] parameter
     ///
@@ -261,7 +545,7 @@ pub trait Backend {
     /// return an error if the `clear_type` is not supported by the backend.
     ///
     /// [`clear`]: Backend::clear
-    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
         match clear_type {
             ClearType::All => self.clear(),
             ClearType::AfterCursor
@@ -270,10 +554,62 @@ pub trait Backend {
             | ClearType::UntilNewLine => Err(io::Error::new(
                 io::ErrorKind::Other,
                 format!("clear_type [{clear_type:?}] not supported with this backend"),
-            )),
+            )
+            .into()),
         }
     }
 
+    /// Scrolls the rows in `region` up by `line_count` lines.
+    ///
+    /// The `line_count` rows at the top of the region are discarded and blank rows are inserted at
+    /// the bottom of the region. Rows outside of the region are not affected. This is done by
+    /// setting the terminal's scroll region (`DECSTBM`), scrolling it and resetting it to the whole
+    /// screen afterwards. The position of the cursor is undefined afterwards.
+    ///
+    /// The [`Terminal`] uses this to avoid redrawing content that has only moved vertically.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use ratatui::{prelude::*, backend::TestBackend};
+    /// # let mut backend = TestBackend::new(80, 25);
+    /// // scroll the rows 2 to 9 up by 3 lines
+    /// backend.scroll_region_up(2..10, 3)?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    ///
+    /// [`Terminal`]: crate::terminal::Terminal
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> Result<(), Self::Error> {
+        let _ = (region, line_count);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "scroll regions are not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Scrolls the rows in `region` down by `line_count` lines.
+    ///
+    /// The `line_count` rows at the bottom of the region are discarded and blank rows are
+    /// inserted at the top of the region. See [`scroll_region_up`] for more details.
+    ///
+    /// [`scroll_region_up`]: Backend::scroll_region_up
+    fn scroll_region_down(
+        &mut self,
+        region: Range<u16>,
+        line_count: u16,
+    ) -> Result<(), Self::Error> {
+        let _ = (region, line_count);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "scroll regions are not supported with this backend",
+        )
+        .into())
+    }
+
     /// Get the size of the terminal screen in columns/rows as a [`Rect`].
     ///
     /// The returned [`Rect`] contains the width and height of the terminal screen.
@@ -286,17 +622,104 @@ pub trait Backend {
     /// assert_eq!(backend.size()?, Rect::new(0, 0, 80, 25));
     /// # std::io::Result::Ok(())
     /// ```
-    fn size(&self) -> io::Result<Rect>;
+    fn size(&self) -> Result<Rect, Self::Error>;
 
     /// Get the size of the terminal screen in columns/rows and pixels as a [`WindowSize`].
     ///
     /// The reason for this not returning only the pixel size, given the redundancy with the
     /// `size()` method, is that the underlying backends most likely get both values with one
     /// syscall, and the user is also most likely to need columns and rows along with pixel size.
-    fn window_size(&mut self) -> io::Result<WindowSize>;
+    fn window_size(&mut self) -> Result<WindowSize, Self::Error>;
+
+    /// Waits for the next input [`Event`] for at most `timeout`, or indefinitely when `timeout` is
+    /// `None`.
+    ///
+    /// Returns `Ok(None)` when no event was received before the timeout. Native events that have no
+    /// equivalent [`Event`] are skipped. Most applications should use [`Terminal::poll_event`] and
+    /// [`Terminal::read_event`] rather than calling this directly.
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`Terminal::poll_event`]: crate::Terminal::poll_event
+    /// [`Terminal::read_event`]: crate::Terminal::read_event
+    fn next_event(&mut self, timeout: Option<Duration>) -> Result<Option<Event>, Self::Error> {
+        let _ = timeout;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "reading events is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Returns the total number of bytes the backend has written to the terminal since it was
+    /// created.
+    ///
+    /// [`Terminal::draw`] uses it to report the number of bytes written by each frame in its
+    /// [`FrameStats`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`Terminal::draw`]: crate::Terminal::draw
+    /// [`FrameStats`]: crate::terminal::FrameStats
+    fn bytes_written(&self) -> Result<u64, Self::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "counting written bytes is not supported with this backend",
+        )
+        .into())
+    }
+
+    /// Asks the terminal for one of its default colors (`OSC 10` / `OSC 11`) and waits at most
+    /// `timeout` for the answer.
+    ///
+    /// Returns the color as a [`Color::Rgb`], or `Ok(None)` when the terminal didn't answer in
+    /// time, which is what terminals that don't support the query do. Input events received while
+    /// waiting for the answer are returned by [`next_event`] afterwards.
+    ///
+    /// This is mostly useful to choose between a light and a dark theme, see
+    /// [`Terminal::query_default_color`].
+    ///
+    /// This method is optional and may not be implemented by all backends. The default
+    /// implementation returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`next_event`]: Backend::next_event
+    /// [`Terminal::query_default_color`]: crate::Terminal::query_default_color
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> Result<Option<Color>, Self::Error> {
+        let _ = (color, timeout);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "querying the default colors is not supported with this backend",
+        )
+        .into())
+    }
 
     /// Flush any buffered content to the terminal screen.
-    fn flush(&mut self) -> io::Result<()>;
+    fn flush(&mut self) -> Result<(), Self::Error>;
+}
+
+/// A function restoring the terminal from a panic hook, see [`Backend::panic_restorer`].
+pub type PanicRestorer = Box<dyn Fn() -> io::Result<()> + Send + Sync>;
+
+/// Returns whether `err`, or one of its sources, is an [`io::Error`] of kind
+/// [`io::ErrorKind::Unsupported`], as returned by the optional [`Backend`] methods by default.
+pub(crate) fn is_unsupported(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if err
+            .downcast_ref::<io::Error>()
+            .is_some_and(|err| err.kind() == io::ErrorKind::Unsupported)
+        {
+            return true;
+        }
+        source = err.source();
+    }
+    false
 }
 
 #[cfg(test)]
@@ -335,4 +758,15 @@ mod tests {
         );
         assert_eq!("".parse::<ClearType>(), Err(ParseError::VariantNotFound));
     }
+
+    #[test]
+    fn cursor_style_tostring_and_from_str() {
+        assert_eq!(CursorStyle::default(), CursorStyle::DefaultUserShape);
+        assert_eq!(CursorStyle::SteadyBar.to_string(), "SteadyBar");
+        assert_eq!(
+            "BlinkingUnderline".parse::<CursorStyle>(),
+            Ok(CursorStyle::BlinkingUnderline)
+        );
+        assert_eq!("".parse::<CursorStyle>(), Err(ParseError::VariantNotFound));
+    }
 }
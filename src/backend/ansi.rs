@@ -0,0 +1,1145 @@
+//! This module provides the [`AnsiBackend`] implementation for the [`Backend`] trait. It writes
+//! plain ANSI / VT100 escape sequences to any [`Write`] implementation and does not depend on any
+//! terminal library.
+//!
+//! [`Backend`]: crate::backend::Backend
+//! [`AnsiBackend`]: crate::backend::AnsiBackend
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, Write},
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::{Backend, ClearType, CursorStyle, DefaultColor, WindowSize},
+    buffer::Cell,
+    event::{Event, KeyCode, KeyModifiers},
+    layout::Size,
+    prelude::Rect,
+    style::{Color, Modifier},
+};
+
+mod parser;
+pub use parser::AnsiParser;
+
+/// A [`Backend`] implementation that writes plain ANSI escape sequences to any writer.
+///
+/// Unlike the other backends, `AnsiBackend` does not depend on [Crossterm], [Termion] or
+/// [Termwiz] and never talks to a tty directly. This makes it suitable for rendering to sockets,
+/// serial lines, files or any other byte stream that is interpreted by a terminal emulator on the
+/// other side.
+///
+/// Since the backend cannot ask the operating system for the size of the terminal, the size is
+/// either given by the caller (see [`AnsiBackend::new`] and [`AnsiBackend::resize`]) or obtained
+/// from a callback (see [`AnsiBackend::with_size_callback`]). Likewise, the cursor position
+/// returned by [`Backend::get_cursor`] is the last position the backend moved the cursor to, not a
+/// position reported by the terminal.
+///
+/// Raw mode, the alternate screen and mouse capture are the responsibility of whatever is on the
+/// other end of the writer.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui::{backend::AnsiBackend, prelude::*, widgets::Paragraph};
+///
+/// let backend = AnsiBackend::new(Vec::new(), 10, 1);
+/// let mut terminal = Terminal::new(backend)?;
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("Hello"), frame.size());
+/// })?;
+/// let bytes = terminal.backend().writer();
+/// assert!(bytes.starts_with(b"\x1b[1;1HHello"));
+/// # std::io::Result::Ok(())
+/// ```
+///
+/// [Crossterm]: https://crates.io/crates/crossterm
+/// [Termion]: https://crates.io/crates/termion
+/// [Termwiz]: https://crates.io/crates/termwiz
+#[derive(Debug)]
+pub struct AnsiBackend<W: Write> {
+    /// The writer the escape sequences are sent to.
+    writer: CountingWriter<W>,
+    /// Where the size of the terminal comes from.
+    size: SizeSource,
+    /// Last known position of the cursor.
+    cursor: (u16, u16),
+}
+
+/// The source of the window size of an [`AnsiBackend`].
+enum SizeSource {
+    /// A size given by the caller.
+    Fixed(WindowSize),
+    /// A callback that is called every time the size is requested.
+    Callback(Box<dyn Fn() -> io::Result<WindowSize>>),
+}
+
+impl fmt::Debug for SizeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeSource::Fixed(size) => f.debug_tuple("Fixed").field(size).finish(),
+            SizeSource::Callback(_) => f.debug_tuple("Callback").finish(),
+        }
+    }
+}
+
+impl<W> AnsiBackend<W>
+where
+    W: Write,
+{
+    /// Creates a new `AnsiBackend` with the given writer and a fixed size in columns and rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::backend::AnsiBackend;
+    /// let backend = AnsiBackend::new(Vec::new(), 80, 24);
+    /// ```
+    pub fn new(writer: W, width: u16, height: u16) -> AnsiBackend<W> {
+        AnsiBackend {
+            writer: CountingWriter::new(writer),
+            size: SizeSource::Fixed(WindowSize {
+                columns_rows: Size { width, height },
+                pixels: Size::default(),
+            }),
+            cursor: (0, 0),
+        }
+    }
+
+    /// Creates a new `AnsiBackend` with the given writer which gets its size from the given
+    /// callback.
+    ///
+    /// The callback is called each time the size of the terminal is requested, which happens at
+    /// least once per [`Terminal::draw`] call.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::{backend::{AnsiBackend, WindowSize}, layout::Size};
+    /// let backend = AnsiBackend::with_size_callback(Vec::new(), || {
+    ///     Ok(WindowSize {
+    ///         columns_rows: Size { width: 80, height: 24 },
+    ///         pixels: Size { width: 640, height: 480 },
+    ///     })
+    /// });
+    /// ```
+    ///
+    /// [`Terminal::draw`]: crate::terminal::Terminal::draw
+    pub fn with_size_callback<F>(writer: W, callback: F) -> AnsiBackend<W>
+    where
+        F: Fn() -> io::Result<WindowSize> + 'static,
+    {
+        AnsiBackend {
+            writer: CountingWriter::new(writer),
+            size: SizeSource::Callback(Box::new(callback)),
+            cursor: (0, 0),
+        }
+    }
+
+    /// Sets a fixed size in columns and rows, replacing any previous size or size callback.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.size = SizeSource::Fixed(WindowSize {
+            columns_rows: Size { width, height },
+            pixels: Size::default(),
+        });
+    }
+
+    /// Returns a reference to the writer used by the backend.
+    pub fn writer(&self) -> &W {
+        self.writer.get_ref()
+    }
+
+    /// Returns a mutable reference to the writer used by the backend.
+    pub fn writer_mut(&mut self) -> &mut W {
+        self.writer.get_mut()
+    }
+}
+
+impl<W> Write for AnsiBackend<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W> Backend for AnsiBackend<W>
+where
+    W: Write,
+{
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let mut fg = Color::Reset;
+        let mut bg = Color::Reset;
+        #[cfg(feature = "underline-color")]
+        let mut underline_color = Color::Reset;
+        let mut modifier = Modifier::empty();
+        let mut hyperlink = None;
+        let mut last_pos: Option<(u16, u16)> = None;
+        for (x, y, cell) in content {
+            // Move the cursor if the previous location was not (x - 1, y)
+            if !matches!(last_pos, Some(p) if x == p.0 + 1 && y == p.1) {
+                write!(self.writer, "{}", MoveTo(x, y))?;
+            }
+            last_pos = Some((x, y));
+            if cell.modifier != modifier {
+                let diff = ModifierDiff {
+                    from: modifier,
+                    to: cell.modifier,
+                };
+                write!(self.writer, "{diff}")?;
+                modifier = cell.modifier;
+            }
+            if cell.fg != fg {
+                write!(self.writer, "{}", Fg(cell.fg))?;
+                fg = cell.fg;
+            }
+            if cell.bg != bg {
+                write!(self.writer, "{}", Bg(cell.bg))?;
+                bg = cell.bg;
+            }
+            #[cfg(feature = "underline-color")]
+            if cell.underline_color != underline_color {
+                write!(self.writer, "{}", UnderlineColor(cell.underline_color))?;
+                underline_color = cell.underline_color;
+            }
+
+            if cell.hyperlink() != hyperlink {
+                write!(self.writer, "{}", Hyperlink(cell.hyperlink()))?;
+                hyperlink = cell.hyperlink();
+            }
+
+            self.writer.write_all(cell.symbol().as_bytes())?;
+            self.cursor = (x.saturating_add(cell.symbol_width() as u16), y);
+        }
+
+        if hyperlink.is_some() {
+            write!(self.writer, "{}", Hyperlink(None))?;
+        }
+
+        #[cfg(feature = "underline-color")]
+        write!(
+            self.writer,
+            "{}{}{}{}",
+            Fg(Color::Reset),
+            Bg(Color::Reset),
+            UnderlineColor(Color::Reset),
+            ResetAttributes,
+        )?;
+        #[cfg(not(feature = "underline-color"))]
+        write!(
+            self.writer,
+            "{}{}{}",
+            Fg(Color::Reset),
+            Bg(Color::Reset),
+            ResetAttributes,
+        )?;
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?25l")?;
+        self.writer.flush()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?25h")?;
+        self.writer.flush()
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) -> io::Result<()> {
+        write!(self.writer, "{}", SetCursorStyle(style))?;
+        self.writer.flush()
+    }
+
+    fn set_title(&mut self, title: &str) -> io::Result<()> {
+        write!(self.writer, "{}", SetTitle(title))?;
+        self.writer.flush()
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> io::Result<()> {
+        write!(self.writer, "{}", SetClipboard(content))?;
+        self.writer.flush()
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x07")?;
+        self.writer.flush()
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        Ok(self.cursor)
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        write!(self.writer, "{}", MoveTo(x, y))?;
+        self.cursor = (x, y);
+        self.writer.flush()
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.clear_region(ClearType::All)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        let sequence: &[u8] = match clear_type {
+            ClearType::All => b"\x1b[2J",
+            ClearType::AfterCursor => b"\x1b[J",
+            ClearType::BeforeCursor => b"\x1b[1J",
+            ClearType::CurrentLine => b"\x1b[2K",
+            ClearType::UntilNewLine => b"\x1b[K",
+        };
+        self.writer.write_all(sequence)?;
+        self.writer.flush()
+    }
+
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        write!(
+            self.writer,
+            "\x1b[{};{}r\x1b[{line_count}S\x1b[r",
+            region.start + 1,
+            region.end
+        )?;
+        // resetting the scroll region moves the cursor to the top left corner
+        self.cursor = (0, 0);
+        Ok(())
+    }
+
+    fn scroll_region_down(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        write!(
+            self.writer,
+            "\x1b[{};{}r\x1b[{line_count}T\x1b[r",
+            region.start + 1,
+            region.end
+        )?;
+        self.cursor = (0, 0);
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        for _ in 0..n {
+            self.writer.write_all(b"\n")?;
+        }
+        let (x, y) = self.cursor;
+        let max_y = self.size()?.height.saturating_sub(1);
+        self.cursor = (x, y.saturating_add(n).min(max_y));
+        self.writer.flush()
+    }
+
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?2026h")
+    }
+
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\x1b[?2026l")
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        let size = match &self.size {
+            SizeSource::Fixed(size) => size.columns_rows,
+            SizeSource::Callback(callback) => callback()?.columns_rows,
+        };
+        Ok(Rect::new(0, 0, size.width, size.height))
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        match &self.size {
+            SizeSource::Fixed(size) => Ok(*size),
+            SizeSource::Callback(callback) => callback(),
+        }
+    }
+
+    fn bytes_written(&self) -> io::Result<u64> {
+        Ok(self.writer.count())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// A writer that counts the bytes written to the inner writer, used by the backends to implement
+/// [`Backend::bytes_written`].
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub(crate) struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W> CountingWriter<W> {
+    pub(crate) fn new(inner: W) -> CountingWriter<W> {
+        CountingWriter { inner, count: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub(crate) fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Moves the cursor to the given zero based column and row (`CUP`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct MoveTo(pub u16, pub u16);
+
+/// Sets the shape of the cursor and whether it blinks (`DECSCUSR`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct SetCursorStyle(pub CursorStyle);
+
+/// Sets the foreground color (`SGR 30-37, 38, 39, 90-97`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Fg(pub Color);
+
+/// Sets the background color (`SGR 40-47, 48, 49, 100-107`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Bg(pub Color);
+
+/// Sets the underline color (`SGR 58, 59`).
+#[cfg(feature = "underline-color")]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct UnderlineColor(pub Color);
+
+/// Resets all colors and attributes (`SGR 0`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct ResetAttributes;
+
+/// Starts a hyperlink to the given target, or ends the current hyperlink when `None` (`OSC 8`).
+///
+/// Control characters are removed from the target so that it can't end the sequence early.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Hyperlink<'a>(pub Option<&'a str>);
+
+/// Sets the title of the terminal window (`OSC 2`).
+///
+/// Control characters are removed from the title so that it can't end the sequence early.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct SetTitle<'a>(pub &'a str);
+
+/// Copies the given text to the system clipboard (`OSC 52`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct SetClipboard<'a>(pub &'a str);
+
+/// Asks the terminal for one of its default colors (`OSC 10` / `OSC 11`).
+///
+/// The reply is read with [`read_color_reply`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct QueryDefaultColor(pub DefaultColor);
+
+/// The `ModifierDiff` struct is used to calculate the difference between two `Modifier`
+/// values. This is useful when updating the terminal display, as it allows for more
+/// efficient updates by only sending the necessary changes.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct ModifierDiff {
+    pub from: Modifier,
+    pub to: Modifier,
+}
+
+impl fmt::Display for MoveTo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b[{};{}H", self.1 + 1, self.0 + 1)
+    }
+}
+
+impl fmt::Display for SetCursorStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self.0 {
+            CursorStyle::DefaultUserShape => 0,
+            CursorStyle::BlinkingBlock => 1,
+            CursorStyle::SteadyBlock => 2,
+            CursorStyle::BlinkingUnderline => 3,
+            CursorStyle::SteadyUnderline => 4,
+            CursorStyle::BlinkingBar => 5,
+            CursorStyle::SteadyBar => 6,
+        };
+        write!(f, "\x1b[{code} q")
+    }
+}
+
+impl fmt::Display for Fg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Color::Reset => write!(f, "\x1b[39m"),
+            Color::Indexed(i) => write!(f, "\x1b[38;5;{i}m"),
+            Color::Rgb(r, g, b) => write!(f, "\x1b[38;2;{r};{g};{b}m"),
+            color => write!(f, "\x1b[{}m", ansi_fg_code(color)),
+        }
+    }
+}
+
+impl fmt::Display for Bg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Color::Reset => write!(f, "\x1b[49m"),
+            Color::Indexed(i) => write!(f, "\x1b[48;5;{i}m"),
+            Color::Rgb(r, g, b) => write!(f, "\x1b[48;2;{r};{g};{b}m"),
+            color => write!(f, "\x1b[{}m", ansi_fg_code(color) + 10),
+        }
+    }
+}
+
+#[cfg(feature = "underline-color")]
+impl fmt::Display for UnderlineColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Color::Reset => write!(f, "\x1b[59m"),
+            Color::Indexed(i) => write!(f, "\x1b[58;5;{i}m"),
+            Color::Rgb(r, g, b) => write!(f, "\x1b[58;2;{r};{g};{b}m"),
+            // There is no short form for the underline color, so named colors are sent as their
+            // index in the 256 color palette.
+            color => {
+                let code = ansi_fg_code(color);
+                let index = if code >= 90 { code - 90 + 8 } else { code - 30 };
+                write!(f, "\x1b[58;5;{index}m")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Hyperlink<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\x1b]8;;")?;
+        for c in self.0.unwrap_or_default().chars() {
+            if !c.is_control() {
+                fmt::Write::write_char(f, c)?;
+            }
+        }
+        f.write_str("\x1b\\")
+    }
+}
+
+impl fmt::Display for SetTitle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\x1b]2;")?;
+        for c in self.0.chars() {
+            if !c.is_control() {
+                fmt::Write::write_char(f, c)?;
+            }
+        }
+        f.write_str("\x1b\\")
+    }
+}
+
+impl fmt::Display for SetClipboard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b]52;c;{}\x1b\\", base64(self.0.as_bytes()))
+    }
+}
+
+impl fmt::Display for QueryDefaultColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self.0 {
+            DefaultColor::Foreground => 10,
+            DefaultColor::Background => 11,
+        };
+        write!(f, "\x1b]{code};?\x1b\\")
+    }
+}
+
+impl fmt::Display for ResetAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b[0m")
+    }
+}
+
+/// Encodes `bytes` in base64, with padding.
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// Reads the reply to a [`QueryDefaultColor`] from the input events returned by `next_event`,
+/// which is given the time left before `timeout` expires.
+///
+/// The input parsers of the backends don't know about the reply and report it as key presses:
+/// `Alt+]` for the `ESC ]` starting it, the characters of the reply, then `Ctrl+G` for a `BEL` or
+/// `Alt+\` for an `ESC \` ending it. The events received before the reply are added to `skipped`,
+/// so that the backend can return them later.
+pub(crate) fn read_color_reply<E>(
+    mut next_event: impl FnMut(Duration) -> Result<Option<Event>, E>,
+    timeout: Duration,
+    skipped: &mut VecDeque<Event>,
+) -> Result<Option<Color>, E> {
+    let deadline = Instant::now() + timeout;
+    let mut reply: Option<String> = None;
+    while let Some(event) = next_event(deadline.saturating_duration_since(Instant::now()))? {
+        let Event::Key(key) = event else {
+            skipped.push_back(event);
+            continue;
+        };
+        match (&mut reply, key.code) {
+            (None, KeyCode::Char(']')) if key.modifiers == KeyModifiers::ALT => {
+                reply = Some(String::new());
+            }
+            (Some(reply), KeyCode::Char(c))
+                if (key.modifiers - KeyModifiers::SHIFT).is_empty() && !c.is_control() =>
+            {
+                reply.push(c);
+            }
+            (Some(reply), _) => return Ok(parse_color_reply(reply)),
+            (None, _) => skipped.push_back(event),
+        }
+    }
+    Ok(None)
+}
+
+/// Parses the reply to a [`QueryDefaultColor`] without its `ESC ]` prefix and terminator, e.g.
+/// `11;rgb:ffff/8000/0000`.
+///
+/// Terminals send each component with 1 to 4 hexadecimal digits, which are scaled to 8 bits.
+fn parse_color_reply(reply: &str) -> Option<Color> {
+    let (_, color) = reply.split_once(";rgb:")?;
+    let mut components = color.split('/').map(|component| {
+        let max = 16u32.checked_pow(component.len() as u32)?.checked_sub(1)?;
+        let value = u32::from_str_radix(component, 16).ok()?;
+        (max > 0 && component.len() <= 4).then(|| (value * 255 + max / 2) / max)
+    });
+    let (Some(Some(r)), Some(Some(g)), Some(Some(b)), None) = (
+        components.next(),
+        components.next(),
+        components.next(),
+        components.next(),
+    ) else {
+        return None;
+    };
+    Some(Color::Rgb(r as u8, g as u8, b as u8))
+}
+
+/// Returns the SGR parameter of an underline style returned by [`Modifier::underline`].
+pub(crate) fn underline_code(underline: Modifier) -> &'static str {
+    match underline {
+        Modifier::DOUBLE_UNDERLINED => "4:2",
+        Modifier::CURLY_UNDERLINED => "4:3",
+        Modifier::DOTTED_UNDERLINED => "4:4",
+        Modifier::DASHED_UNDERLINED => "4:5",
+        _ => "4",
+    }
+}
+
+/// Returns the `SGR` foreground code of a named color. The background code is 10 more.
+///
+/// Must not be called with [`Color::Reset`], [`Color::Indexed`] or [`Color::Rgb`].
+fn ansi_fg_code(color: Color) -> u8 {
+    match color {
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+        Color::Reset | Color::Indexed(_) | Color::Rgb(..) => {
+            unreachable!("{color:?} has no named SGR code")
+        }
+    }
+}
+
+impl fmt::Display for ModifierDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let removed = self.from - self.to;
+        let underline = self.to.underline();
+        let underline_changed = self.from.underline() != underline;
+        if removed.contains(Modifier::REVERSED) {
+            write!(f, "\x1b[27m")?;
+        }
+        if removed.contains(Modifier::BOLD) {
+            // Normal intensity turns off both bold and dim, so dim has to be re-enabled if needed.
+            write!(f, "\x1b[22m")?;
+            if self.to.contains(Modifier::DIM) {
+                write!(f, "\x1b[2m")?;
+            }
+        }
+        if removed.contains(Modifier::ITALIC) {
+            write!(f, "\x1b[23m")?;
+        }
+        if underline_changed && underline.is_empty() {
+            write!(f, "\x1b[24m")?;
+        }
+        if removed.contains(Modifier::DIM) {
+            write!(f, "\x1b[22m")?;
+            if self.to.contains(Modifier::BOLD) {
+                write!(f, "\x1b[1m")?;
+            }
+        }
+        if removed.contains(Modifier::CROSSED_OUT) {
+            write!(f, "\x1b[29m")?;
+        }
+        if removed.contains(Modifier::SLOW_BLINK) || removed.contains(Modifier::RAPID_BLINK) {
+            write!(f, "\x1b[25m")?;
+        }
+        if removed.contains(Modifier::HIDDEN) {
+            write!(f, "\x1b[28m")?;
+        }
+        if removed.contains(Modifier::OVERLINED) {
+            write!(f, "\x1b[55m")?;
+        }
+
+        let added = self.to - self.from;
+        if added.contains(Modifier::REVERSED) {
+            write!(f, "\x1b[7m")?;
+        }
+        if added.contains(Modifier::BOLD) {
+            write!(f, "\x1b[1m")?;
+        }
+        if added.contains(Modifier::ITALIC) {
+            write!(f, "\x1b[3m")?;
+        }
+        if underline_changed && !underline.is_empty() {
+            // setting an underline style replaces the previous one
+            write!(f, "\x1b[{}m", underline_code(underline))?;
+        }
+        if added.contains(Modifier::DIM) {
+            write!(f, "\x1b[2m")?;
+        }
+        if added.contains(Modifier::CROSSED_OUT) {
+            write!(f, "\x1b[9m")?;
+        }
+        if added.contains(Modifier::SLOW_BLINK) {
+            write!(f, "\x1b[5m")?;
+        }
+        if added.contains(Modifier::RAPID_BLINK) {
+            write!(f, "\x1b[6m")?;
+        }
+        if added.contains(Modifier::HIDDEN) {
+            write!(f, "\x1b[8m")?;
+        }
+        if added.contains(Modifier::OVERLINED) {
+            write!(f, "\x1b[53m")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::KeyEvent;
+
+    fn cell(symbol: &str) -> Cell {
+        let mut cell = Cell::default();
+        cell.set_symbol(symbol);
+        cell
+    }
+
+    /// The sequence written at the end of each `draw` call.
+    fn reset() -> String {
+        #[cfg(feature = "underline-color")]
+        return "\x1b[39m\x1b[49m\x1b[59m\x1b[0m".to_string();
+        #[cfg(not(feature = "underline-color"))]
+        return "\x1b[39m\x1b[49m\x1b[0m".to_string();
+    }
+
+    #[test]
+    fn new() {
+        let backend = AnsiBackend::new(Vec::new(), 10, 2);
+        assert_eq!(backend.size().unwrap(), Rect::new(0, 0, 10, 2));
+        assert!(backend.writer().is_empty());
+    }
+
+    #[test]
+    fn resize() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        backend.resize(5, 5);
+        assert_eq!(backend.size().unwrap(), Rect::new(0, 0, 5, 5));
+    }
+
+    #[test]
+    fn size_callback() {
+        let mut backend = AnsiBackend::with_size_callback(Vec::new(), || {
+            Ok(WindowSize {
+                columns_rows: Size {
+                    width: 80,
+                    height: 24,
+                },
+                pixels: Size {
+                    width: 640,
+                    height: 480,
+                },
+            })
+        });
+        assert_eq!(backend.size().unwrap(), Rect::new(0, 0, 80, 24));
+        assert_eq!(
+            backend.window_size().unwrap().pixels,
+            Size {
+                width: 640,
+                height: 480
+            }
+        );
+    }
+
+    #[test]
+    fn size_callback_error() {
+        let backend = AnsiBackend::with_size_callback(Vec::new(), || {
+            Err(io::Error::new(io::ErrorKind::Other, "no size"))
+        });
+        assert!(backend.size().is_err());
+    }
+
+    #[test]
+    fn draw_moves_cursor_only_when_needed() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        let (a, b, c) = (cell("a"), cell("b"), cell("c"));
+        backend
+            .draw([(0, 0, &a), (1, 0, &b), (5, 1, &c)].into_iter())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(backend.writer.inner).unwrap(),
+            format!("\x1b[1;1Hab\x1b[2;6Hc{}", reset())
+        );
+    }
+
+    #[test]
+    fn draw_colors() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 1);
+        let mut red = cell("r");
+        red.set_fg(Color::Red).set_bg(Color::LightBlue);
+        let mut indexed = cell("i");
+        indexed
+            .set_fg(Color::Indexed(42))
+            .set_bg(Color::Indexed(42));
+        let mut rgb = cell("c");
+        rgb.set_fg(Color::Rgb(1, 2, 3)).set_bg(Color::Rgb(4, 5, 6));
+        backend
+            .draw([(0, 0, &red), (1, 0, &indexed), (2, 0, &rgb)].into_iter())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(backend.writer.inner).unwrap(),
+            format!(
+                "\x1b[1;1H\x1b[31m\x1b[104mr\x1b[38;5;42m\x1b[48;5;42mi\
+                 \x1b[38;2;1;2;3m\x1b[48;2;4;5;6mc{}",
+                reset()
+            )
+        );
+    }
+
+    #[cfg(feature = "underline-color")]
+    #[test]
+    fn draw_underline_color() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 1);
+        let mut a = cell("a");
+        a.underline_color = Color::LightRed;
+        let mut b = cell("b");
+        b.underline_color = Color::Rgb(1, 2, 3);
+        backend.draw([(0, 0, &a), (1, 0, &b)].into_iter()).unwrap();
+        assert_eq!(
+            String::from_utf8(backend.writer.inner).unwrap(),
+            format!("\x1b[1;1H\x1b[58;5;9ma\x1b[58;2;1;2;3mb{}", reset())
+        );
+    }
+
+    #[test]
+    fn draw_hyperlinks() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 1);
+        let mut a = cell("a");
+        a.set_hyperlink(Some("https://a\x1b"));
+        let mut b = cell("b");
+        b.set_hyperlink(Some("https://a\x1b"));
+        let c = cell("c");
+        let mut d = cell("d");
+        d.set_hyperlink(Some("https://d"));
+        backend
+            .draw([(0, 0, &a), (1, 0, &b), (2, 0, &c), (3, 0, &d)].into_iter())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(backend.writer.inner).unwrap(),
+            format!(
+                "\x1b[1;1H\x1b]8;;https://a\x1b\\ab\x1b]8;;\x1b\\c\x1b]8;;https://d\x1b\\d\x1b]8;;\x1b\\{}",
+                reset()
+            )
+        );
+    }
+
+    #[test]
+    fn draw_modifiers() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 1);
+        let mut bold = cell("a");
+        bold.modifier = Modifier::BOLD | Modifier::DIM;
+        let mut dim = cell("b");
+        dim.modifier = Modifier::DIM | Modifier::ITALIC;
+        let plain = cell("c");
+        backend
+            .draw([(0, 0, &bold), (1, 0, &dim), (2, 0, &plain)].into_iter())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(backend.writer.inner).unwrap(),
+            format!(
+                "\x1b[1;1H\x1b[1m\x1b[2ma\x1b[22m\x1b[2m\x1b[3mb\x1b[23m\x1b[22mc{}",
+                reset()
+            )
+        );
+    }
+
+    #[test]
+    fn modifier_diff() {
+        let diff = ModifierDiff {
+            from: Modifier::empty(),
+            to: Modifier::all(),
+        };
+        assert_eq!(
+            diff.to_string(),
+            "\x1b[7m\x1b[1m\x1b[3m\x1b[4:3m\x1b[2m\x1b[9m\x1b[5m\x1b[6m\x1b[8m\x1b[53m"
+        );
+        let diff = ModifierDiff {
+            from: Modifier::all(),
+            to: Modifier::empty(),
+        };
+        assert_eq!(
+            diff.to_string(),
+            "\x1b[27m\x1b[22m\x1b[23m\x1b[24m\x1b[22m\x1b[29m\x1b[25m\x1b[28m\x1b[55m"
+        );
+    }
+
+    #[test]
+    fn modifier_diff_underlines() {
+        let diff = |from, to| ModifierDiff { from, to }.to_string();
+        assert_eq!(
+            diff(Modifier::empty(), Modifier::DOUBLE_UNDERLINED),
+            "\x1b[4:2m"
+        );
+        assert_eq!(
+            diff(Modifier::UNDERLINED, Modifier::DOTTED_UNDERLINED),
+            "\x1b[4:4m"
+        );
+        assert_eq!(
+            diff(
+                Modifier::UNDERLINED | Modifier::CURLY_UNDERLINED,
+                Modifier::UNDERLINED
+            ),
+            "\x1b[4m"
+        );
+        assert_eq!(
+            diff(
+                Modifier::UNDERLINED | Modifier::DASHED_UNDERLINED,
+                Modifier::DASHED_UNDERLINED
+            ),
+            ""
+        );
+        assert_eq!(
+            diff(Modifier::DASHED_UNDERLINED, Modifier::empty()),
+            "\x1b[24m"
+        );
+    }
+
+    #[test]
+    fn cursor() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        backend.hide_cursor().unwrap();
+        backend.set_cursor(3, 1).unwrap();
+        backend.show_cursor().unwrap();
+        assert_eq!(backend.get_cursor().unwrap(), (3, 1));
+        assert_eq!(backend.writer(), b"\x1b[?25l\x1b[2;4H\x1b[?25h");
+    }
+
+    #[test]
+    fn bytes_written() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        assert_eq!(backend.bytes_written().unwrap(), 0);
+        backend.set_cursor(3, 1).unwrap();
+        backend.writer_mut().clear();
+        backend.hide_cursor().unwrap();
+        assert_eq!(backend.bytes_written().unwrap(), 12);
+    }
+
+    #[test]
+    fn cursor_style() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        backend.set_cursor_style(CursorStyle::SteadyBar).unwrap();
+        backend
+            .set_cursor_style(CursorStyle::DefaultUserShape)
+            .unwrap();
+        assert_eq!(backend.writer(), b"\x1b[6 q\x1b[0 q");
+    }
+
+    #[test]
+    fn title_clipboard_and_bell() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        backend.set_title("a\x07b").unwrap();
+        backend.set_clipboard("foo").unwrap();
+        backend.bell().unwrap();
+        assert_eq!(backend.writer(), b"\x1b]2;ab\x1b\\\x1b]52;c;Zm9v\x1b\\\x07");
+    }
+
+    #[test]
+    fn base64_encodes_with_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn query_default_color() {
+        assert_eq!(
+            QueryDefaultColor(DefaultColor::Foreground).to_string(),
+            "\x1b]10;?\x1b\\"
+        );
+        assert_eq!(
+            QueryDefaultColor(DefaultColor::Background).to_string(),
+            "\x1b]11;?\x1b\\"
+        );
+    }
+
+    #[test]
+    fn parse_color_replies() {
+        let parse = parse_color_reply;
+        assert_eq!(
+            parse("11;rgb:ffff/8000/0000"),
+            Some(Color::Rgb(255, 128, 0))
+        );
+        assert_eq!(parse("10;rgb:ff/80/00"), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(parse("10;rgb:f/8/0"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse("10;rgb:1e1e/1e1e/2e2e"), Some(Color::Rgb(30, 30, 46)));
+        assert_eq!(parse("10;rgb:ffff/ffff"), None);
+        assert_eq!(parse("10;rgb:ffff/ffff/ffff/ffff"), None);
+        assert_eq!(parse("10;rgb:fffff/ffff/ffff"), None);
+        assert_eq!(parse("10;rgb:/ffff/ffff"), None);
+        assert_eq!(parse("10;rgb:gggg/ffff/ffff"), None);
+        assert_eq!(parse("10;?"), None);
+    }
+
+    #[test]
+    fn read_color_reply_from_key_events() {
+        let alt = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT));
+        let ctrl = |c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+        let char = |c| Event::Key(KeyCode::Char(c).into());
+        let typed = Event::Key(KeyCode::Enter.into());
+        let mut events: VecDeque<Event> = [typed.clone(), alt(']')]
+            .into_iter()
+            .chain("11;rgb:0000/8080/ffff".chars().map(char))
+            .chain([ctrl('g'), Event::FocusLost])
+            .collect();
+        let mut skipped = VecDeque::new();
+        let color = read_color_reply(
+            |_| Ok::<_, io::Error>(events.pop_front()),
+            Duration::ZERO,
+            &mut skipped,
+        );
+        assert_eq!(color.unwrap(), Some(Color::Rgb(0, 128, 255)));
+        assert_eq!(skipped, [typed]);
+        assert_eq!(events, [Event::FocusLost]);
+
+        // the reply can also end with `ST`
+        let mut events: VecDeque<Event> = [alt(']')]
+            .into_iter()
+            .chain("10;rgb:ff/ff/ff".chars().map(char))
+            .chain([alt('\\')])
+            .collect();
+        let color = read_color_reply(
+            |_| Ok::<_, io::Error>(events.pop_front()),
+            Duration::ZERO,
+            &mut skipped,
+        );
+        assert_eq!(color.unwrap(), Some(Color::Rgb(255, 255, 255)));
+    }
+
+    #[test]
+    fn read_color_reply_times_out() {
+        let typed = Event::Key(KeyCode::Char('a').into());
+        let mut events = VecDeque::from([typed.clone()]);
+        let mut skipped = VecDeque::new();
+        let color = read_color_reply(
+            |_| Ok::<_, io::Error>(events.pop_front()),
+            Duration::ZERO,
+            &mut skipped,
+        );
+        assert_eq!(color.unwrap(), None);
+        assert_eq!(skipped, [typed]);
+    }
+
+    #[test]
+    fn get_cursor_after_draw() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        let wide = cell("称");
+        backend.draw([(2, 1, &wide)].into_iter()).unwrap();
+        assert_eq!(backend.get_cursor().unwrap(), (4, 1));
+    }
+
+    #[test]
+    fn clear_region() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 2);
+        backend.clear().unwrap();
+        backend.clear_region(ClearType::AfterCursor).unwrap();
+        backend.clear_region(ClearType::BeforeCursor).unwrap();
+        backend.clear_region(ClearType::CurrentLine).unwrap();
+        backend.clear_region(ClearType::UntilNewLine).unwrap();
+        assert_eq!(backend.writer(), b"\x1b[2J\x1b[J\x1b[1J\x1b[2K\x1b[K");
+    }
+
+    #[test]
+    fn scroll_region() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 10);
+        backend.set_cursor(3, 3).unwrap();
+        backend.writer_mut().clear();
+        backend.scroll_region_up(2..5, 1).unwrap();
+        backend.scroll_region_down(0..10, 3).unwrap();
+        assert_eq!(
+            backend.writer(),
+            b"\x1b[3;5r\x1b[1S\x1b[r\x1b[1;10r\x1b[3T\x1b[r"
+        );
+        assert_eq!(backend.get_cursor().unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn append_lines() {
+        let mut backend = AnsiBackend::new(Vec::new(), 10, 5);
+        backend.set_cursor(0, 2).unwrap();
+        backend.writer_mut().clear();
+        backend.append_lines(4).unwrap();
+        assert_eq!(backend.writer(), b"\n\n\n\n");
+        assert_eq!(backend.get_cursor().unwrap(), (0, 4));
+    }
+}
@@ -0,0 +1,1114 @@
+use std::io;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+/// A parser for ANSI / VT100 escape sequences that replays a stream of bytes into a [`Buffer`].
+///
+/// `AnsiParser` emulates the parts of a terminal that are needed to reconstruct what a backend
+/// draws: printable text (including wide and combining characters), cursor movement, `SGR` colors
+/// and modifiers, `OSC 8` hyperlinks, erasing, scrolling (including scroll regions) and cursor
+/// visibility. Sequences that it does not understand are consumed and ignored.
+///
+/// This makes it possible to test the exact bytes written by a [`Backend`] like
+/// [`CrosstermBackend`] or [`TermionBackend`] rather than the cells handed to
+/// [`TestBackend::draw`], and to import captured terminal sessions as [`Buffer`]s.
+///
+/// The parser assumes the terminal is in raw mode, so a line feed moves the cursor down without
+/// returning it to the first column. Erased cells are reset to [`Cell::default`] rather than being
+/// filled with the current background color.
+///
+/// Named colors are usually sent as indexes into the 256 color palette (e.g. `ESC[38;5;1m` for
+/// [`Color::Red`]). The parser maps the indexes 0 to 15 back to the named colors so that the
+/// reconstructed buffer can be compared to the buffer that was drawn.
+///
+/// `AnsiParser` implements [`Write`](io::Write), so it can be used directly as the writer of a
+/// backend.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui::{backend::AnsiParser, prelude::*};
+///
+/// let mut parser = AnsiParser::new(Rect::new(0, 0, 5, 2));
+/// parser.process(b"\x1b[2;2H\x1b[31mab\x1b[0m");
+///
+/// let mut expected = Buffer::with_lines(vec!["     ", " ab  "]);
+/// expected.set_style(Rect::new(1, 1, 2, 1), Style::new().fg(Color::Red));
+/// assert_eq!(parser.buffer(), &expected);
+/// assert_eq!(parser.cursor(), (3, 1));
+/// ```
+///
+/// [`Backend`]: crate::backend::Backend
+/// [`CrosstermBackend`]: crate::backend::CrosstermBackend
+/// [`TermionBackend`]: crate::backend::TermionBackend
+/// [`TestBackend::draw`]: crate::backend::TestBackend
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AnsiParser {
+    /// The reconstructed screen.
+    buffer: Buffer,
+    /// The cursor position relative to the top left corner of the screen.
+    cursor: (u16, u16),
+    /// The cursor position stored by `DECSC` / `SCOSC`.
+    saved_cursor: (u16, u16),
+    /// Whether the cursor is visible (`DECTCEM`).
+    cursor_visible: bool,
+    /// The top (inclusive) and bottom (exclusive) rows of the scroll region (`DECSTBM`).
+    scroll_region: (u16, u16),
+    /// Whether the last character was printed in the last column, so that the next printable
+    /// character wraps to the next line.
+    pending_wrap: bool,
+    /// The colors and modifiers applied to printed characters.
+    pen: Cell,
+    /// The position of the last printed cell, which combining characters are appended to.
+    last_printed: Option<(u16, u16)>,
+    /// The state of the escape sequence state machine.
+    state: State,
+    /// The parameter and intermediate bytes of the current control sequence.
+    sequence: Vec<u8>,
+    /// The bytes of an incomplete UTF-8 encoded character.
+    utf8: Vec<u8>,
+}
+
+/// The states of the escape sequence state machine.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+enum State {
+    /// Printable text and C0 control characters.
+    #[default]
+    Ground,
+    /// After `ESC`.
+    Escape,
+    /// After `ESC` followed by an intermediate byte such as `(`, waiting for the final byte.
+    EscapeIntermediate,
+    /// Inside a control sequence (`ESC [`).
+    Csi,
+    /// Inside an operating system command (`ESC ]`), terminated by `BEL` or `ST`.
+    Osc,
+    /// After an `ESC` inside an operating system command, which is usually the start of `ST`.
+    OscEscape,
+    /// Inside a device control, privacy message or application program command string,
+    /// terminated by `ST`.
+    String,
+    /// After an `ESC` inside an `OSC` or other string, which is usually the start of `ST`.
+    StringEscape,
+}
+
+impl AnsiParser {
+    /// Creates a new parser with an empty screen of the given size.
+    ///
+    /// Only the width and height of `area` are used, the screen always starts at `(0, 0)`.
+    pub fn new(area: Rect) -> AnsiParser {
+        AnsiParser {
+            buffer: Buffer::empty(Rect::new(0, 0, area.width, area.height)),
+            cursor: (0, 0),
+            saved_cursor: (0, 0),
+            cursor_visible: true,
+            scroll_region: (0, area.height),
+            pending_wrap: false,
+            pen: Cell::default(),
+            last_printed: None,
+            state: State::Ground,
+            sequence: Vec::new(),
+            utf8: Vec::new(),
+        }
+    }
+
+    /// Returns the reconstructed screen.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Consumes the parser and returns the reconstructed screen.
+    pub fn into_buffer(self) -> Buffer {
+        self.buffer
+    }
+
+    /// Returns the current cursor position as `(x, y)`.
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    /// Returns whether the cursor is visible.
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Processes the given bytes, updating the screen and the cursor.
+    ///
+    /// Escape sequences and UTF-8 encoded characters may be split across several calls.
+    pub fn process(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.advance(byte);
+        }
+    }
+
+    fn advance(&mut self, byte: u8) {
+        match self.state {
+            State::Ground => self.ground(byte),
+            State::Escape => self.escape(byte),
+            State::EscapeIntermediate => {
+                // Designating character sets and similar sequences are not supported.
+                if (0x30..=0x7e).contains(&byte) {
+                    self.state = State::Ground;
+                }
+            }
+            State::Csi => match byte {
+                0x20..=0x3f => self.sequence.push(byte),
+                0x40..=0x7e => {
+                    self.state = State::Ground;
+                    self.csi_dispatch(byte);
+                }
+                0x1b => self.state = State::Escape,
+                // Control characters are executed in the middle of a control sequence.
+                _ => self.execute(byte),
+            },
+            State::Osc => match byte {
+                0x07 => {
+                    self.state = State::Ground;
+                    self.osc_dispatch();
+                }
+                0x1b => self.state = State::OscEscape,
+                _ => self.sequence.push(byte),
+            },
+            State::OscEscape => {
+                self.state = State::Ground;
+                if byte == b'\\' {
+                    self.osc_dispatch();
+                } else {
+                    self.escape(byte);
+                }
+            }
+            State::String => {
+                if byte == 0x1b {
+                    self.state = State::StringEscape;
+                }
+            }
+            State::StringEscape => {
+                // `ESC \` is the string terminator, any other byte starts a new escape sequence.
+                self.state = State::Ground;
+                if byte != b'\\' {
+                    self.escape(byte);
+                }
+            }
+        }
+    }
+
+    fn ground(&mut self, byte: u8) {
+        if !self.utf8.is_empty() || byte >= 0x80 {
+            self.utf8.push(byte);
+            match std::str::from_utf8(&self.utf8) {
+                Ok(s) => {
+                    let c = s.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER);
+                    self.utf8.clear();
+                    self.print(c);
+                }
+                Err(err) if err.error_len().is_some() => {
+                    self.utf8.clear();
+                    self.print(char::REPLACEMENT_CHARACTER);
+                }
+                // incomplete character, wait for the next byte
+                Err(_) => {}
+            }
+            return;
+        }
+        match byte {
+            0x1b => self.state = State::Escape,
+            0x20..=0x7e => self.print(byte as char),
+            _ => self.execute(byte),
+        }
+    }
+
+    fn escape(&mut self, byte: u8) {
+        self.state = State::Ground;
+        match byte {
+            b'[' => {
+                self.sequence.clear();
+                self.state = State::Csi;
+            }
+            b']' => {
+                self.sequence.clear();
+                self.state = State::Osc;
+            }
+            b'P' | b'X' | b'^' | b'_' => self.state = State::String,
+            b' '..=b'/' => self.state = State::EscapeIntermediate,
+            b'7' => self.saved_cursor = self.cursor,
+            b'8' => self.move_to(self.saved_cursor.0, self.saved_cursor.1),
+            b'D' => self.line_feed(),
+            b'E' => {
+                self.line_feed();
+                self.cursor.0 = 0;
+            }
+            b'M' => self.reverse_index(),
+            b'c' => *self = AnsiParser::new(self.buffer.area),
+            _ => {}
+        }
+    }
+
+    /// Executes a C0 control character.
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            // backspace
+            0x08 => self.move_to(self.cursor.0.saturating_sub(1), self.cursor.1),
+            // horizontal tab
+            0x09 => {
+                let next_tab_stop = (self.cursor.0 / 8 + 1) * 8;
+                self.move_to(next_tab_stop, self.cursor.1);
+            }
+            // line feed, vertical tab and form feed
+            0x0a..=0x0c => self.line_feed(),
+            // carriage return
+            0x0d => self.move_to(0, self.cursor.1),
+            _ => {}
+        }
+    }
+
+    fn print(&mut self, c: char) {
+        let width = c.width().unwrap_or(0) as u16;
+        if width == 0 {
+            self.append_to_last_printed(c);
+            return;
+        }
+        if self.last_printed_ends_with_zwj() {
+            self.append_to_last_printed(c);
+            return;
+        }
+        if self.buffer.area.is_empty() {
+            return;
+        }
+        if self.pending_wrap || self.cursor.0 + width > self.buffer.area.width {
+            self.line_feed();
+            self.cursor.0 = 0;
+        }
+        let (x, y) = self.cursor;
+        let cell = self.buffer.get_mut(x, y);
+        *cell = self.pen.clone();
+        cell.set_char(c);
+        // Reset the cells hidden by a multi-width character, like `Buffer::set_stringn` does.
+        for hidden in x + 1..(x + width).min(self.buffer.area.width) {
+            self.buffer.get_mut(hidden, y).reset();
+        }
+        self.last_printed = Some((x, y));
+        if x + width >= self.buffer.area.width {
+            self.cursor.0 = self.buffer.area.width - 1;
+            self.pending_wrap = true;
+        } else {
+            self.cursor.0 = x + width;
+        }
+    }
+
+    fn append_to_last_printed(&mut self, c: char) {
+        if let Some((x, y)) = self.last_printed {
+            let cell = self.buffer.get_mut(x, y);
+            let mut symbol = cell.symbol().to_string();
+            symbol.push(c);
+            cell.set_symbol(&symbol);
+        }
+    }
+
+    fn last_printed_ends_with_zwj(&self) -> bool {
+        self.last_printed
+            .is_some_and(|(x, y)| self.buffer.get(x, y).symbol().ends_with('\u{200d}'))
+    }
+
+    fn move_to(&mut self, x: u16, y: u16) {
+        let area = self.buffer.area;
+        self.cursor = (
+            x.min(area.width.saturating_sub(1)),
+            y.min(area.height.saturating_sub(1)),
+        );
+        self.pending_wrap = false;
+        self.last_printed = None;
+    }
+
+    fn line_feed(&mut self) {
+        let (x, y) = self.cursor;
+        if y + 1 == self.scroll_region.1 {
+            self.scroll_up(1);
+            self.move_to(x, y);
+        } else {
+            self.move_to(x, y + 1);
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        let (x, y) = self.cursor;
+        if y == self.scroll_region.0 {
+            self.scroll_down(1);
+            self.move_to(x, y);
+        } else {
+            self.move_to(x, y.saturating_sub(1));
+        }
+    }
+
+    /// Moves the lines of the scroll region up by `n`, discarding the lines at the top and adding
+    /// blank lines at the bottom.
+    fn scroll_up(&mut self, n: u16) {
+        self.delete_lines(self.scroll_region.0, n);
+    }
+
+    /// Moves the lines of the scroll region down by `n`, discarding the lines at the bottom and
+    /// adding blank lines at the top.
+    fn scroll_down(&mut self, n: u16) {
+        self.insert_lines(self.scroll_region.0, n);
+    }
+
+    /// Returns the cells of the lines from row `top` to the bottom of the scroll region, or the
+    /// bottom of the screen if `top` is below the scroll region.
+    fn lines_from(&mut self, top: u16) -> &mut [Cell] {
+        let (region_top, region_bottom) = self.scroll_region;
+        let bottom = if (region_top..region_bottom).contains(&top) {
+            region_bottom
+        } else {
+            self.buffer.area.height
+        };
+        let width = self.buffer.area.width as usize;
+        &mut self.buffer.content_mut()[top as usize * width..bottom as usize * width]
+    }
+
+    /// Deletes `n` lines starting at row `top`, moving the lines below up.
+    fn delete_lines(&mut self, top: u16, n: u16) {
+        let width = self.buffer.area.width as usize;
+        let lines = self.lines_from(top);
+        let n = (n as usize * width).min(lines.len());
+        lines.rotate_left(n);
+        let len = lines.len();
+        lines[len - n..].fill(Cell::default());
+    }
+
+    /// Inserts `n` blank lines at row `top`, moving the lines below down.
+    fn insert_lines(&mut self, top: u16, n: u16) {
+        let width = self.buffer.area.width as usize;
+        let lines = self.lines_from(top);
+        let n = (n as usize * width).min(lines.len());
+        lines.rotate_right(n);
+        lines[..n].fill(Cell::default());
+    }
+
+    /// Resets the cells of the given row in the range `from..to`.
+    fn erase(&mut self, y: u16, from: u16, to: u16) {
+        if y >= self.buffer.area.height {
+            return;
+        }
+        let to = to.min(self.buffer.area.width);
+        for x in from..to {
+            self.buffer.get_mut(x, y).reset();
+        }
+    }
+
+    fn csi_dispatch(&mut self, action: u8) {
+        let sequence = std::mem::take(&mut self.sequence);
+        let private = sequence
+            .first()
+            .copied()
+            .filter(|b| (b'<'..=b'?').contains(b));
+        let intermediates: Vec<u8> = sequence
+            .iter()
+            .copied()
+            .filter(|b| (0x20..=0x2f).contains(b))
+            .collect();
+        let params = Params::parse(&sequence[private.is_some() as usize..]);
+        let (x, y) = self.cursor;
+        let (width, height) = (self.buffer.area.width, self.buffer.area.height);
+
+        match (private, intermediates.as_slice(), action) {
+            (None, [], b'A') => self.move_to(x, y.saturating_sub(params.count(0))),
+            (None, [], b'B' | b'e') => self.move_to(x, y.saturating_add(params.count(0))),
+            (None, [], b'C' | b'a') => self.move_to(x.saturating_add(params.count(0)), y),
+            (None, [], b'D') => self.move_to(x.saturating_sub(params.count(0)), y),
+            (None, [], b'E') => self.move_to(0, y.saturating_add(params.count(0))),
+            (None, [], b'F') => self.move_to(0, y.saturating_sub(params.count(0))),
+            (None, [], b'G' | b'`') => self.move_to(params.count(0) - 1, y),
+            (None, [], b'd') => self.move_to(x, params.count(0) - 1),
+            (None, [], b'H' | b'f') => self.move_to(params.count(1) - 1, params.count(0) - 1),
+            (None, [], b'J') => match params.get(0, 0) {
+                0 => {
+                    self.erase(y, x, width);
+                    for row in y + 1..height {
+                        self.erase(row, 0, width);
+                    }
+                }
+                1 => {
+                    for row in 0..y {
+                        self.erase(row, 0, width);
+                    }
+                    self.erase(y, 0, x + 1);
+                }
+                2 | 3 => self.buffer.reset(),
+                _ => {}
+            },
+            (None, [], b'K') => match params.get(0, 0) {
+                0 => self.erase(y, x, width),
+                1 => self.erase(y, 0, x + 1),
+                2 => self.erase(y, 0, width),
+                _ => {}
+            },
+            (None, [], b'X') => self.erase(y, x, x.saturating_add(params.count(0))),
+            // There is no cell to insert or delete in an empty area.
+            (None, [], b'@' | b'P') if width == 0 || height == 0 => {}
+            (None, [], b'@') => {
+                let n = params.count(0).min(width - x);
+                let start = self.buffer.index_of(x, y);
+                let line = &mut self.buffer.content_mut()[start..start + (width - x) as usize];
+                line.rotate_right(n as usize);
+                line[..n as usize].fill(Cell::default());
+            }
+            (None, [], b'P') => {
+                let n = params.count(0).min(width - x);
+                let start = self.buffer.index_of(x, y);
+                let line = &mut self.buffer.content_mut()[start..start + (width - x) as usize];
+                line.rotate_left(n as usize);
+                let len = line.len();
+                line[len - n as usize..].fill(Cell::default());
+            }
+            (None, [], b'L') => {
+                self.insert_lines(y, params.count(0));
+                self.move_to(0, y);
+            }
+            (None, [], b'M') => {
+                self.delete_lines(y, params.count(0));
+                self.move_to(0, y);
+            }
+            (None, [], b'r') => {
+                let top = params.count(0) - 1;
+                let bottom = match params.get(1, 0) {
+                    0 => height,
+                    bottom => bottom.min(height),
+                };
+                if top + 1 < bottom {
+                    self.scroll_region = (top, bottom);
+                    self.move_to(0, 0);
+                }
+            }
+            (None, [], b'S') => self.scroll_up(params.count(0)),
+            (None, [], b'T') => self.scroll_down(params.count(0)),
+            (None, [], b's') => self.saved_cursor = self.cursor,
+            (None, [], b'u') => self.move_to(self.saved_cursor.0, self.saved_cursor.1),
+            (None, [], b'm') => self.sgr(&params),
+            (Some(b'?'), [], b'h' | b'l') if params.iter().any(|p| p == 25) => {
+                self.cursor_visible = action == b'h';
+            }
+            _ => {}
+        }
+    }
+
+    fn osc_dispatch(&mut self) {
+        let sequence = std::mem::take(&mut self.sequence);
+        let sequence = String::from_utf8_lossy(&sequence);
+        let mut parts = sequence.splitn(3, ';');
+        // `OSC 8 ; params ; target` starts a hyperlink, an empty target ends it
+        if let (Some("8"), Some(_), Some(target)) = (parts.next(), parts.next(), parts.next()) {
+            let target = (!target.is_empty()).then_some(target);
+            self.pen.set_hyperlink(target);
+        }
+    }
+
+    /// Applies a Select Graphic Rendition sequence to the pen.
+    fn sgr(&mut self, params: &Params) {
+        if params.is_empty() {
+            self.reset_pen();
+            return;
+        }
+        let mut i = 0;
+        while i < params.len() {
+            let param = params.get(i, 0);
+            let sub_params = params.sub_params(i);
+            match param {
+                0 => self.reset_pen(),
+                1 => self.pen.modifier.insert(Modifier::BOLD),
+                2 => self.pen.modifier.insert(Modifier::DIM),
+                3 => self.pen.modifier.insert(Modifier::ITALIC),
+                4 => {
+                    self.remove_underlines();
+                    let underline = match sub_params.first() {
+                        None | Some(1) => Modifier::UNDERLINED,
+                        Some(2) => Modifier::DOUBLE_UNDERLINED,
+                        Some(3) => Modifier::CURLY_UNDERLINED,
+                        Some(4) => Modifier::DOTTED_UNDERLINED,
+                        Some(5) => Modifier::DASHED_UNDERLINED,
+                        Some(_) => Modifier::empty(),
+                    };
+                    self.pen.modifier.insert(underline);
+                }
+                5 => self.pen.modifier.insert(Modifier::SLOW_BLINK),
+                6 => self.pen.modifier.insert(Modifier::RAPID_BLINK),
+                7 => self.pen.modifier.insert(Modifier::REVERSED),
+                8 => self.pen.modifier.insert(Modifier::HIDDEN),
+                9 => self.pen.modifier.insert(Modifier::CROSSED_OUT),
+                22 => self.pen.modifier.remove(Modifier::BOLD | Modifier::DIM),
+                23 => self.pen.modifier.remove(Modifier::ITALIC),
+                21 => {
+                    self.remove_underlines();
+                    self.pen.modifier.insert(Modifier::DOUBLE_UNDERLINED);
+                }
+                24 => self.remove_underlines(),
+                25 => self
+                    .pen
+                    .modifier
+                    .remove(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+                27 => self.pen.modifier.remove(Modifier::REVERSED),
+                28 => self.pen.modifier.remove(Modifier::HIDDEN),
+                29 => self.pen.modifier.remove(Modifier::CROSSED_OUT),
+                53 => self.pen.modifier.insert(Modifier::OVERLINED),
+                55 => self.pen.modifier.remove(Modifier::OVERLINED),
+                30..=37 => self.pen.fg = indexed_color((param - 30) as u8),
+                39 => self.pen.fg = Color::Reset,
+                40..=47 => self.pen.bg = indexed_color((param - 40) as u8),
+                49 => self.pen.bg = Color::Reset,
+                90..=97 => self.pen.fg = indexed_color((param - 90 + 8) as u8),
+                100..=107 => self.pen.bg = indexed_color((param - 100 + 8) as u8),
+                38 | 48 | 58 => {
+                    let color = if sub_params.is_empty() {
+                        let (color, consumed) = extended_color(params, i + 1);
+                        i += consumed;
+                        color
+                    } else {
+                        extended_color_from_sub_params(sub_params)
+                    };
+                    if let Some(color) = color {
+                        match param {
+                            38 => self.pen.fg = color,
+                            48 => self.pen.bg = color,
+                            #[cfg(feature = "underline-color")]
+                            _ => self.pen.underline_color = color,
+                            #[cfg(not(feature = "underline-color"))]
+                            _ => {}
+                        }
+                    }
+                }
+                #[cfg(feature = "underline-color")]
+                59 => self.pen.underline_color = Color::Reset,
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    fn remove_underlines(&mut self) {
+        for underline in Modifier::UNDERLINES {
+            self.pen.modifier.remove(underline);
+        }
+    }
+
+    fn reset_pen(&mut self) {
+        // Hyperlinks are not attributes, they are only ended by an empty `OSC 8`.
+        let hyperlink = self.pen.hyperlink.take();
+        self.pen = Cell::default();
+        self.pen.hyperlink = hyperlink;
+    }
+}
+
+impl io::Write for AnsiParser {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.process(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the color for the given index in the 256 color palette, using the named colors for
+/// the first 16 indexes.
+fn indexed_color(index: u8) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::Gray,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::LightYellow,
+        12 => Color::LightBlue,
+        13 => Color::LightMagenta,
+        14 => Color::LightCyan,
+        15 => Color::White,
+        _ => Color::Indexed(index),
+    }
+}
+
+/// Parses the semicolon separated form of an extended color (`38;5;n` or `38;2;r;g;b`) starting
+/// at the parameter after `38`, `48` or `58`. Returns the color and the number of parameters used.
+fn extended_color(params: &Params, start: usize) -> (Option<Color>, usize) {
+    match params.get(start, 0) {
+        5 => (
+            Some(indexed_color(params.get(start + 1, 0) as u8)),
+            2.min(params.len() - start),
+        ),
+        2 => (
+            Some(Color::Rgb(
+                params.get(start + 1, 0) as u8,
+                params.get(start + 2, 0) as u8,
+                params.get(start + 3, 0) as u8,
+            )),
+            4.min(params.len() - start),
+        ),
+        _ => (None, 0),
+    }
+}
+
+/// Parses the colon separated form of an extended color (`38:5:n`, `38:2:r:g:b` or
+/// `38:2:colorspace:r:g:b`).
+fn extended_color_from_sub_params(sub_params: &[u16]) -> Option<Color> {
+    match sub_params {
+        [5, index, ..] => Some(indexed_color(*index as u8)),
+        [2, _, r, g, b, ..] | [2, r, g, b] => Some(Color::Rgb(*r as u8, *g as u8, *b as u8)),
+        _ => None,
+    }
+}
+
+/// The numeric parameters of a control sequence.
+///
+/// Parameters are separated by `;`. Each parameter may have sub parameters separated by `:`.
+/// Missing parameters are represented by `None`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+struct Params(Vec<(Option<u16>, Vec<u16>)>);
+
+impl Params {
+    fn parse(bytes: &[u8]) -> Params {
+        if bytes.is_empty() {
+            return Params::default();
+        }
+        let params = bytes
+            .split(|&b| b == b';')
+            .map(|param| {
+                let mut parts = param.split(|&b| b == b':').map(|part| {
+                    std::str::from_utf8(part)
+                        .ok()
+                        .and_then(|s| s.parse::<u16>().ok())
+                });
+                let value = parts.next().flatten();
+                let sub_params = parts.map(Option::unwrap_or_default).collect();
+                (value, sub_params)
+            })
+            .collect();
+        Params(params)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.0.iter().filter_map(|(value, _)| *value)
+    }
+
+    /// Returns the parameter at `index`, or `default` if it is missing.
+    fn get(&self, index: usize, default: u16) -> u16 {
+        self.0
+            .get(index)
+            .and_then(|(value, _)| *value)
+            .unwrap_or(default)
+    }
+
+    /// Returns the parameter at `index` as a count, where both a missing parameter and `0` mean 1.
+    fn count(&self, index: usize) -> u16 {
+        self.get(index, 1).max(1)
+    }
+
+    fn sub_params(&self, index: usize) -> &[u16] {
+        self.0
+            .get(index)
+            .map(|(_, sub_params)| sub_params.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_width::UnicodeWidthStr;
+
+    use super::*;
+    use crate::{
+        assert_buffer_eq,
+        backend::{AnsiBackend, Backend},
+        style::{Style, Stylize},
+        text::Span,
+    };
+
+    /// Returns the cells of the buffer as strings, ignoring the cells hidden by wide characters.
+    fn lines(buffer: &Buffer) -> Vec<String> {
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|cells| {
+                let mut line = String::new();
+                let mut skip = 0;
+                for cell in cells {
+                    if skip == 0 {
+                        line.push_str(cell.symbol());
+                    }
+                    skip = std::cmp::max(skip, cell.symbol().width()).saturating_sub(1);
+                }
+                line
+            })
+            .collect()
+    }
+
+    fn parse(width: u16, height: u16, bytes: &[u8]) -> AnsiParser {
+        let mut parser = AnsiParser::new(Rect::new(0, 0, width, height));
+        parser.process(bytes);
+        parser
+    }
+
+    #[test]
+    fn prints_text() {
+        let parser = parse(5, 2, b"abc");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["abc  ", "     "]));
+        assert_eq!(parser.cursor(), (3, 0));
+    }
+
+    #[test]
+    fn empty_area() {
+        for (width, height) in [(0, 0), (3, 0), (0, 2)] {
+            let parser = parse(
+                width,
+                height,
+                b"ab\x1b[2@\x1b[2P\x1b[2X\x1b[K\x1b[L\x1b[M\x1b[J",
+            );
+            assert_eq!(parser.buffer().area, Rect::new(0, 0, width, height));
+        }
+    }
+
+    #[test]
+    fn wraps_at_end_of_line() {
+        let parser = parse(3, 2, b"abcd");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["abc", "d  "]));
+        assert_eq!(parser.cursor(), (1, 1));
+    }
+
+    #[test]
+    fn pending_wrap_is_cancelled_by_cursor_movement() {
+        let parser = parse(3, 2, b"abc\x1b[1;1Hd");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["dbc", "   "]));
+    }
+
+    #[test]
+    fn scrolls_at_bottom() {
+        let parser = parse(3, 2, b"aaa\r\nbbb\r\nccc");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["bbb", "ccc"]));
+    }
+
+    #[test]
+    fn line_feed_does_not_return_carriage() {
+        let parser = parse(3, 2, b"a\nb");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["a  ", " b "]));
+    }
+
+    #[test]
+    fn control_characters() {
+        let parser = parse(10, 1, b"ab\x08c\td");
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["ac      d "]));
+    }
+
+    #[test]
+    fn cursor_movement() {
+        let parser = parse(
+            5,
+            5,
+            b"\x1b[3;3Ha\x1b[Ab\x1b[2Dc\x1b[2Bd\x1b[Ce\x1b[1Gf\x1b[1dg",
+        );
+        assert_eq!(
+            lines(parser.buffer()),
+            vec![" g   ", "  cb ", "  a  ", "f  de", "     "]
+        );
+    }
+
+    #[test]
+    fn cursor_movement_is_clamped() {
+        let parser = parse(5, 5, b"\x1b[99;99H");
+        assert_eq!(parser.cursor(), (4, 4));
+        let parser = parse(5, 5, b"\x1b[2;2H\x1b[99A\x1b[99D");
+        assert_eq!(parser.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn save_and_restore_cursor() {
+        let parser = parse(5, 5, b"\x1b[2;3H\x1b7\x1b[5;5H\x1b8");
+        assert_eq!(parser.cursor(), (2, 1));
+        let parser = parse(5, 5, b"\x1b[2;3H\x1b[s\x1b[5;5H\x1b[u");
+        assert_eq!(parser.cursor(), (2, 1));
+    }
+
+    #[test]
+    fn erase_display() {
+        let screen = b"aaaaa\r\nbbbbb\r\nccccc\x1b[2;3H";
+        let mut parser = parse(5, 3, screen);
+        parser.process(b"\x1b[J");
+        assert_eq!(lines(parser.buffer()), vec!["aaaaa", "bb   ", "     "]);
+
+        let mut parser = parse(5, 3, screen);
+        parser.process(b"\x1b[1J");
+        assert_eq!(lines(parser.buffer()), vec!["     ", "   bb", "ccccc"]);
+
+        let mut parser = parse(5, 3, screen);
+        parser.process(b"\x1b[2J");
+        assert_eq!(lines(parser.buffer()), vec!["     ", "     ", "     "]);
+    }
+
+    #[test]
+    fn erase_line() {
+        let screen = b"aaaaa\x1b[1;3H";
+        let mut parser = parse(5, 1, screen);
+        parser.process(b"\x1b[K");
+        assert_eq!(lines(parser.buffer()), vec!["aa   "]);
+
+        let mut parser = parse(5, 1, screen);
+        parser.process(b"\x1b[1K");
+        assert_eq!(lines(parser.buffer()), vec!["   aa"]);
+
+        let mut parser = parse(5, 1, screen);
+        parser.process(b"\x1b[2K");
+        assert_eq!(lines(parser.buffer()), vec!["     "]);
+
+        let mut parser = parse(5, 1, screen);
+        parser.process(b"\x1b[2X");
+        assert_eq!(lines(parser.buffer()), vec!["aa  a"]);
+    }
+
+    #[test]
+    fn insert_and_delete_characters() {
+        let mut parser = parse(5, 1, b"abcde\x1b[1;2H");
+        parser.process(b"\x1b[2@");
+        assert_eq!(lines(parser.buffer()), vec!["a  bc"]);
+        parser.process(b"\x1b[3P");
+        assert_eq!(lines(parser.buffer()), vec!["ac   "]);
+    }
+
+    #[test]
+    fn insert_and_delete_lines() {
+        let mut parser = parse(1, 4, b"a\r\nb\r\nc\r\nd\x1b[2;1H");
+        parser.process(b"\x1b[L");
+        assert_eq!(lines(parser.buffer()), vec!["a", " ", "b", "c"]);
+        parser.process(b"\x1b[2M");
+        assert_eq!(lines(parser.buffer()), vec!["a", "c", " ", " "]);
+    }
+
+    #[test]
+    fn scroll_up_and_down() {
+        let mut parser = parse(1, 3, b"a\r\nb\r\nc");
+        parser.process(b"\x1b[S");
+        assert_eq!(lines(parser.buffer()), vec!["b", "c", " "]);
+        parser.process(b"\x1b[2T");
+        assert_eq!(lines(parser.buffer()), vec![" ", " ", "b"]);
+        parser.process(b"\x1b[1;1H\x1bM");
+        assert_eq!(lines(parser.buffer()), vec![" ", " ", " "]);
+    }
+
+    #[test]
+    fn scroll_region() {
+        let mut parser = parse(1, 5, b"a\r\nb\r\nc\r\nd\r\ne");
+        parser.process(b"\x1b[2;4r");
+        assert_eq!(parser.cursor(), (0, 0));
+        parser.process(b"\x1b[S");
+        assert_eq!(lines(parser.buffer()), vec!["a", "c", "d", " ", "e"]);
+        parser.process(b"\x1b[2T");
+        assert_eq!(lines(parser.buffer()), vec!["a", " ", " ", "c", "e"]);
+        // line feed at the bottom margin scrolls the region
+        parser.process(b"\x1b[4;1H\nx");
+        assert_eq!(lines(parser.buffer()), vec!["a", " ", "c", "x", "e"]);
+        // lines are only inserted within the region
+        parser.process(b"\x1b[3;1H\x1b[L");
+        assert_eq!(lines(parser.buffer()), vec!["a", " ", " ", "c", "e"]);
+        // resetting the region scrolls the whole screen again
+        parser.process(b"\x1b[r\x1b[S");
+        assert_eq!(lines(parser.buffer()), vec![" ", " ", "c", "e", " "]);
+    }
+
+    #[test]
+    fn cursor_visibility() {
+        let mut parser = parse(1, 1, b"");
+        assert!(parser.cursor_visible());
+        parser.process(b"\x1b[?25l");
+        assert!(!parser.cursor_visible());
+        parser.process(b"\x1b[?25h");
+        assert!(parser.cursor_visible());
+    }
+
+    #[test]
+    fn wide_characters() {
+        let parser = parse(5, 1, "a称号".as_bytes());
+        assert_buffer_eq!(*parser.buffer(), Buffer::with_lines(vec!["a称号"]));
+        assert_eq!(parser.cursor(), (4, 0));
+    }
+
+    #[test]
+    fn wide_character_wraps_when_it_does_not_fit() {
+        let parser = parse(3, 2, "ab称".as_bytes());
+        assert_eq!(lines(parser.buffer()), vec!["ab ", "称 "]);
+    }
+
+    #[test]
+    fn combining_characters_are_appended() {
+        let parser = parse(3, 1, "e\u{301}x".as_bytes());
+        assert_eq!(parser.buffer().get(0, 0).symbol(), "e\u{301}");
+        assert_eq!(parser.buffer().get(1, 0).symbol(), "x");
+    }
+
+    #[test]
+    fn split_utf8_and_escape_sequences() {
+        let bytes = "\x1b[31m称".as_bytes();
+        let mut parser = AnsiParser::new(Rect::new(0, 0, 2, 1));
+        for byte in bytes {
+            parser.process(&[*byte]);
+        }
+        let mut expected = Buffer::with_lines(vec!["称"]);
+        expected.set_style(Rect::new(0, 0, 1, 1), Style::new().fg(Color::Red));
+        assert_buffer_eq!(*parser.buffer(), expected);
+    }
+
+    #[test]
+    fn invalid_utf8_prints_replacement_character() {
+        let parser = parse(2, 1, b"\xffa");
+        assert_eq!(lines(parser.buffer()), vec!["\u{fffd}a"]);
+    }
+
+    #[test]
+    fn sgr_modifiers() {
+        let mut parser = parse(9, 1, b"\x1b[1;2;3;4;5;6;7;8;9;53ma");
+        let extended_underlines = Modifier::DOUBLE_UNDERLINED
+            | Modifier::CURLY_UNDERLINED
+            | Modifier::DOTTED_UNDERLINED
+            | Modifier::DASHED_UNDERLINED;
+        assert_eq!(
+            parser.buffer().get(0, 0).modifier,
+            Modifier::all() - extended_underlines
+        );
+        parser.process(b"\x1b[22;23;24;25;27;28;29;55mb");
+        assert_eq!(parser.buffer().get(1, 0).modifier, Modifier::empty());
+        parser.process(b"\x1b[1mc\x1b[0md\x1b[1me\x1b[mf");
+        assert_eq!(parser.buffer().get(2, 0).modifier, Modifier::BOLD);
+        assert_eq!(parser.buffer().get(3, 0).modifier, Modifier::empty());
+        assert_eq!(parser.buffer().get(4, 0).modifier, Modifier::BOLD);
+        assert_eq!(parser.buffer().get(5, 0).modifier, Modifier::empty());
+    }
+
+    #[test]
+    fn sgr_underline_styles() {
+        let parser = parse(
+            6,
+            1,
+            b"\x1b[4ma\x1b[4:3mb\x1b[21mc\x1b[4:4md\x1b[4:5me\x1b[4:0mf",
+        );
+        let modifiers: Vec<Modifier> = (0..6).map(|x| parser.buffer().get(x, 0).modifier).collect();
+        assert_eq!(
+            modifiers,
+            [
+                Modifier::UNDERLINED,
+                Modifier::CURLY_UNDERLINED,
+                Modifier::DOUBLE_UNDERLINED,
+                Modifier::DOTTED_UNDERLINED,
+                Modifier::DASHED_UNDERLINED,
+                Modifier::empty(),
+            ]
+        );
+    }
+
+    #[test]
+    fn sgr_colors() {
+        let parser = parse(
+            6,
+            1,
+            b"\x1b[31;42ma\x1b[91;102mb\x1b[38;5;1;48;5;200mc\x1b[38;2;1;2;3md\
+              \x1b[38:2::4:5:6;48:5:9me\x1b[39;49mf",
+        );
+        let buffer = parser.buffer();
+        assert_eq!(
+            (buffer.get(0, 0).fg, buffer.get(0, 0).bg),
+            (Color::Red, Color::Green)
+        );
+        assert_eq!(
+            (buffer.get(1, 0).fg, buffer.get(1, 0).bg),
+            (Color::LightRed, Color::LightGreen)
+        );
+        assert_eq!(
+            (buffer.get(2, 0).fg, buffer.get(2, 0).bg),
+            (Color::Red, Color::Indexed(200))
+        );
+        assert_eq!(buffer.get(3, 0).fg, Color::Rgb(1, 2, 3));
+        assert_eq!(
+            (buffer.get(4, 0).fg, buffer.get(4, 0).bg),
+            (Color::Rgb(4, 5, 6), Color::LightRed)
+        );
+        assert_eq!(
+            (buffer.get(5, 0).fg, buffer.get(5, 0).bg),
+            (Color::Reset, Color::Reset)
+        );
+    }
+
+    #[cfg(feature = "underline-color")]
+    #[test]
+    fn sgr_underline_color() {
+        let parser = parse(2, 1, b"\x1b[4;58;2;1;2;3ma\x1b[59mb");
+        assert_eq!(
+            parser.buffer().get(0, 0).underline_color,
+            Color::Rgb(1, 2, 3)
+        );
+        assert_eq!(parser.buffer().get(1, 0).underline_color, Color::Reset);
+    }
+
+    #[test]
+    fn ignores_unsupported_sequences() {
+        let parser = parse(
+            5,
+            1,
+            b"\x1b]0;title\x07a\x1b]2;title\x1b\\b\x1bPdata\x1b\\c\x1b(Bd\x1b[?1049he",
+        );
+        assert_eq!(lines(parser.buffer()), vec!["abcde"]);
+    }
+
+    #[test]
+    fn hyperlinks() {
+        let parser = parse(
+            6,
+            1,
+            b"a\x1b]8;;https://a\x1b\\bc\x1b[0md\x1b]8;id=1;https://b\x07e\x1b]8;;\x1b\\f",
+        );
+        let hyperlinks: Vec<_> = parser
+            .buffer()
+            .content()
+            .iter()
+            .map(Cell::hyperlink)
+            .collect();
+        assert_eq!(
+            hyperlinks,
+            vec![
+                None,
+                Some("https://a"),
+                Some("https://a"),
+                Some("https://a"),
+                Some("https://b"),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_ansi_backend_output() {
+        let mut expected = Buffer::empty(Rect::new(0, 0, 8, 2));
+        expected.set_string(0, 0, "Hello", Style::new().red().on_blue().bold());
+        expected.set_string(2, 1, "称号", Style::new().italic().underlined());
+        expected.set_string(6, 1, "!", Style::new().fg(Color::Rgb(1, 2, 3)));
+        expected.set_span(0, 1, &Span::raw("ab").hyperlink("https://ratatui.rs"), 2);
+
+        let mut backend = AnsiBackend::new(AnsiParser::new(expected.area), 8, 2);
+        backend
+            .draw(Buffer::empty(expected.area).diff(&expected).into_iter())
+            .unwrap();
+        assert_buffer_eq!(*backend.writer().buffer(), expected);
+    }
+}
@@ -0,0 +1,410 @@
+//! This module provides the [`AsciicastBackend`] implementation for the [`Backend`] trait. It
+//! forwards everything to another backend and records the output in the [asciicast v2] format
+//! used by [asciinema].
+//!
+//! [`Backend`]: crate::backend::Backend
+//! [`AsciicastBackend`]: crate::backend::AsciicastBackend
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+//! [asciinema]: https://asciinema.org
+use std::{
+    fmt::Write as _,
+    io::Write,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    backend::{
+        AnsiBackend, Backend, ClearType, CursorStyle, DefaultColor, PanicRestorer, WindowSize,
+    },
+    buffer::Cell,
+    event::Event,
+    prelude::Rect,
+    style::Color,
+};
+
+/// A [`Backend`] that forwards all calls to an inner backend and records what is drawn as an
+/// [asciicast v2] file.
+///
+/// The header of the recording is written when the backend is created. Every time the backend is
+/// flushed (once per [`Terminal::draw`]), the escape sequences produced since the previous flush
+/// are written as a single output event, preceded by a resize event if the size of the inner
+/// backend changed. The escape sequences are produced by an [`AnsiBackend`], independently of the
+/// inner backend, so the recording looks the same whatever backend is used.
+///
+/// Event timestamps are the time elapsed since the backend was created. Use
+/// [`AsciicastBackend::frame_interval`] to space frames by a fixed duration instead, which makes
+/// recordings reproducible.
+///
+/// The resulting file can be played with `asciinema play` or embedded in web pages with the
+/// asciinema player.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::{fs::File, io::stdout};
+///
+/// use ratatui::{backend::AsciicastBackend, prelude::*, widgets::Paragraph};
+///
+/// let file = File::create("demo.cast")?;
+/// let backend = AsciicastBackend::new(CrosstermBackend::new(stdout()), file)?;
+/// let mut terminal = Terminal::new(backend)?;
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("Hello World!"), frame.size());
+/// })?;
+/// # std::io::Result::Ok(())
+/// ```
+///
+/// [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+/// [`Terminal::draw`]: crate::terminal::Terminal::draw
+#[derive(Debug)]
+pub struct AsciicastBackend<B, W>
+where
+    B: Backend,
+    W: Write,
+{
+    /// The backend all calls are forwarded to.
+    inner: B,
+    /// The writer the recording is written to.
+    writer: W,
+    /// Produces the escape sequences recorded since the last flush.
+    recorder: AnsiBackend<Vec<u8>>,
+    /// The time the recording started.
+    start: Instant,
+    /// Fixed time between two frames, if any.
+    frame_interval: Option<Duration>,
+    /// Number of frames recorded so far.
+    frames: u32,
+    /// The size of the terminal in the recording.
+    size: Rect,
+}
+
+impl<B, W> AsciicastBackend<B, W>
+where
+    B: Backend,
+    W: Write,
+{
+    /// Creates a new `AsciicastBackend` forwarding to `inner` and recording to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size of the inner backend cannot be queried or if the header of
+    /// the recording cannot be written.
+    pub fn new(inner: B, mut writer: W) -> Result<AsciicastBackend<B, W>, B::Error> {
+        let size = inner.size()?;
+        writeln!(
+            writer,
+            r#"{{"version": 2, "width": {}, "height": {}}}"#,
+            size.width, size.height
+        )?;
+        writer.flush()?;
+        Ok(AsciicastBackend {
+            inner,
+            writer,
+            recorder: AnsiBackend::new(Vec::new(), size.width, size.height),
+            start: Instant::now(),
+            frame_interval: None,
+            frames: 0,
+            size,
+        })
+    }
+
+    /// Spaces the recorded frames by `interval` instead of the actual time between them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use ratatui::backend::{AsciicastBackend, TestBackend};
+    /// let backend = AsciicastBackend::new(TestBackend::new(80, 24), Vec::new())?
+    ///     .frame_interval(Duration::from_millis(100));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn frame_interval(mut self, interval: Duration) -> AsciicastBackend<B, W> {
+        self.frame_interval = Some(interval);
+        self
+    }
+
+    /// Returns a reference to the inner backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner backend.
+    ///
+    /// Output produced by calling the inner backend directly is not recorded.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the writer the recording is written to.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer the recording is written to.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Writes the output recorded since the last flush as a single event.
+    fn record_frame(&mut self) -> Result<(), B::Error> {
+        let size = self.inner.size()?;
+        let resized = size != self.size;
+        if !resized && self.recorder.writer().is_empty() {
+            return Ok(());
+        }
+        let time = match self.frame_interval {
+            Some(interval) => interval * self.frames,
+            None => self.start.elapsed(),
+        }
+        .as_secs_f64();
+        self.frames += 1;
+        if resized {
+            self.size = size;
+            self.recorder.resize(size.width, size.height);
+            let size = format!("{}x{}", size.width, size.height);
+            writeln!(self.writer, "[{time:.6}, \"r\", {}]", json_string(&size))?;
+        }
+        let output = std::mem::take(self.recorder.writer_mut());
+        if !output.is_empty() {
+            let output = String::from_utf8_lossy(&output);
+            writeln!(self.writer, "[{time:.6}, \"o\", {}]", json_string(&output))?;
+        }
+        Ok(self.writer.flush()?)
+    }
+}
+
+impl<B, W> Backend for AsciicastBackend<B, W>
+where
+    B: Backend,
+    W: Write,
+{
+    type Error = B::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let content: Vec<_> = content.collect();
+        self.inner.draw(content.iter().copied())?;
+        Ok(self.recorder.draw(content.into_iter())?)
+    }
+
+    fn append_lines(&mut self, n: u16) -> Result<(), Self::Error> {
+        self.inner.append_lines(n)?;
+        Ok(self.recorder.append_lines(n)?)
+    }
+
+    fn begin_synchronized_update(&mut self) -> Result<(), Self::Error> {
+        self.inner.begin_synchronized_update()?;
+        Ok(self.recorder.begin_synchronized_update()?)
+    }
+
+    fn end_synchronized_update(&mut self) -> Result<(), Self::Error> {
+        self.inner.end_synchronized_update()?;
+        Ok(self.recorder.end_synchronized_update()?)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), Self::Error> {
+        self.inner.hide_cursor()?;
+        Ok(self.recorder.hide_cursor()?)
+    }
+
+    fn show_cursor(&mut self) -> Result<(), Self::Error> {
+        self.inner.show_cursor()?;
+        Ok(self.recorder.show_cursor()?)
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), Self::Error> {
+        self.inner.set_cursor_style(style)?;
+        Ok(self.recorder.set_cursor_style(style)?)
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        self.inner.enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        self.inner.disable_raw_mode()
+    }
+
+    fn enter_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        self.inner.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        self.inner.leave_alternate_screen()
+    }
+
+    fn enable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        self.inner.enable_mouse_capture()
+    }
+
+    fn disable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        self.inner.disable_mouse_capture()
+    }
+
+    fn panic_restorer(&self) -> Result<PanicRestorer, Self::Error> {
+        self.inner.panic_restorer()
+    }
+
+    fn set_title(&mut self, title: &str) -> Result<(), Self::Error> {
+        self.inner.set_title(title)
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> Result<(), Self::Error> {
+        self.inner.set_clipboard(content)
+    }
+
+    fn bell(&mut self) -> Result<(), Self::Error> {
+        self.inner.bell()
+    }
+
+    fn get_cursor(&mut self) -> Result<(u16, u16), Self::Error> {
+        self.inner.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), Self::Error> {
+        self.inner.set_cursor(x, y)?;
+        Ok(self.recorder.set_cursor(x, y)?)
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.inner.clear()?;
+        Ok(self.recorder.clear()?)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
+        self.inner.clear_region(clear_type)?;
+        Ok(self.recorder.clear_region(clear_type)?)
+    }
+
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> Result<(), Self::Error> {
+        self.inner.scroll_region_up(region.clone(), line_count)?;
+        Ok(self.recorder.scroll_region_up(region, line_count)?)
+    }
+
+    fn scroll_region_down(
+        &mut self,
+        region: Range<u16>,
+        line_count: u16,
+    ) -> Result<(), Self::Error> {
+        self.inner.scroll_region_down(region.clone(), line_count)?;
+        Ok(self.recorder.scroll_region_down(region, line_count)?)
+    }
+
+    fn size(&self) -> Result<Rect, Self::Error> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> Result<WindowSize, Self::Error> {
+        self.inner.window_size()
+    }
+
+    fn next_event(&mut self, timeout: Option<Duration>) -> Result<Option<Event>, Self::Error> {
+        self.inner.next_event(timeout)
+    }
+
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> Result<Option<Color>, Self::Error> {
+        self.inner.query_default_color(color, timeout)
+    }
+
+    fn bytes_written(&self) -> Result<u64, Self::Error> {
+        self.inner.bytes_written()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()?;
+        self.record_frame()
+    }
+}
+
+/// Returns `value` as a quoted JSON string.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::TestBackend, buffer::Buffer, widgets::Paragraph, Terminal};
+
+    fn lines(backend: &AsciicastBackend<TestBackend, Vec<u8>>) -> Vec<String> {
+        String::from_utf8(backend.writer().clone())
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!(json_string("abc"), r#""abc""#);
+        assert_eq!(json_string("\"\\\n"), r#""\"\\\n""#);
+        assert_eq!(json_string("\x1b[1m"), r#""\u001b[1m""#);
+        assert_eq!(json_string("█"), r#""█""#);
+    }
+
+    #[test]
+    fn new_writes_header() {
+        let backend = AsciicastBackend::new(TestBackend::new(10, 2), Vec::new()).unwrap();
+        assert_eq!(
+            lines(&backend),
+            [r#"{"version": 2, "width": 10, "height": 2}"#]
+        );
+    }
+
+    #[test]
+    fn records_one_event_per_draw() {
+        let backend = AsciicastBackend::new(TestBackend::new(5, 1), Vec::new())
+            .unwrap()
+            .frame_interval(Duration::from_millis(500));
+        let mut terminal = Terminal::new(backend).unwrap();
+        for text in ["one", "two"] {
+            terminal
+                .draw(|f| f.render_widget(Paragraph::new(text), f.size()))
+                .unwrap();
+        }
+
+        let backend = terminal.backend();
+        backend
+            .inner()
+            .assert_buffer(&Buffer::with_lines(vec!["two  "]));
+        let lines = lines(backend);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with(r#"[0.000000, "o", "\u001b[1;1Hone\u001b[39m"#));
+        assert!(lines[2].starts_with(r#"[0.500000, "o", "\u001b[1;1Htwo\u001b[39m"#));
+    }
+
+    #[test]
+    fn records_resize() {
+        let mut backend = AsciicastBackend::new(TestBackend::new(5, 1), Vec::new())
+            .unwrap()
+            .frame_interval(Duration::from_secs(1));
+        backend.inner_mut().resize(6, 2);
+        backend.flush().unwrap();
+        assert_eq!(lines(&backend)[1], r#"[0.000000, "r", "6x2"]"#);
+        assert_eq!(lines(&backend).len(), 2);
+    }
+}
@@ -2,23 +2,46 @@
 //! the [Crossterm] crate to interact with the terminal.
 //!
 //! [Crossterm]: https://crates.io/crates/crossterm
-use std::io::{self, Write};
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    ops::Range,
+    time::{Duration, Instant},
+};
 
 #[cfg(feature = "underline-color")]
 use crossterm::style::SetUnderlineColor;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, MoveTo, SetCursorStyle, Show},
+    event::{
+        self as cevent, DisableMouseCapture, EnableMouseCapture, Event as CEvent,
+        KeyCode as CKeyCode, KeyEvent as CKeyEvent, KeyEventKind as CKeyEventKind,
+        KeyModifiers as CKeyModifiers, MouseButton as CMouseButton, MouseEvent as CMouseEvent,
+        MouseEventKind as CMouseEventKind,
+    },
     execute, queue,
     style::{
         Attribute as CAttribute, Color as CColor, Print, SetAttribute, SetBackgroundColor,
         SetForegroundColor,
     },
-    terminal::{self, Clear},
+    terminal::{
+        self, BeginSynchronizedUpdate, Clear, EndSynchronizedUpdate, EnterAlternateScreen,
+        LeaveAlternateScreen, ScrollDown, ScrollUp,
+    },
 };
 
 use crate::{
-    backend::{Backend, ClearType, WindowSize},
+    backend::{
+        ansi::{
+            read_color_reply, CountingWriter, Hyperlink, QueryDefaultColor, SetClipboard, SetTitle,
+        },
+        Backend, ClearType, CursorStyle, DefaultColor, PanicRestorer, WindowSize,
+    },
     buffer::Cell,
+    event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
     layout::Size,
     prelude::Rect,
     style::{Color, Modifier},
@@ -80,7 +103,10 @@ use crate::{
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct CrosstermBackend<W: Write> {
     /// The writer used to send commands to the terminal.
-    writer: W,
+    writer: CountingWriter<W>,
+    /// Events received while waiting for the reply to a query, returned by the next calls to
+    /// `next_event`.
+    pending_events: VecDeque<Event>,
 }
 
 impl<W> CrosstermBackend<W>
@@ -97,7 +123,10 @@ where
     /// let backend = CrosstermBackend::new(stdout());
     /// ```
     pub fn new(writer: W) -> CrosstermBackend<W> {
-        CrosstermBackend { writer }
+        CrosstermBackend {
+            writer: CountingWriter::new(writer),
+            pending_events: VecDeque::new(),
+        }
     }
 }
 
@@ -120,6 +149,8 @@ impl<W> Backend for CrosstermBackend<W>
 where
     W: Write,
 {
+    type Error = io::Error;
+
     fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
@@ -129,6 +160,7 @@ where
         #[cfg(feature = "underline-color")]
         let mut underline_color = Color::Reset;
         let mut modifier = Modifier::empty();
+        let mut hyperlink = None;
         let mut last_pos: Option<(u16, u16)> = None;
         for (x, y, cell) in content {
             // Move the cursor if the previous location was not (x - 1, y)
@@ -160,10 +192,18 @@ where
                 queue!(self.writer, SetUnderlineColor(color))?;
                 underline_color = cell.underline_color;
             }
+            if cell.hyperlink() != hyperlink {
+                queue!(self.writer, Print(Hyperlink(cell.hyperlink())))?;
+                hyperlink = cell.hyperlink();
+            }
 
             queue!(self.writer, Print(cell.symbol()))?;
         }
 
+        if hyperlink.is_some() {
+            queue!(self.writer, Print(Hyperlink(None)))?;
+        }
+
         #[cfg(feature = "underline-color")]
         return queue!(
             self.writer,
@@ -189,6 +229,22 @@ where
         execute!(self.writer, Show)
     }
 
+    fn set_cursor_style(&mut self, style: CursorStyle) -> io::Result<()> {
+        execute!(self.writer, SetCursorStyle::from(style))
+    }
+
+    fn set_title(&mut self, title: &str) -> io::Result<()> {
+        execute!(self.writer, Print(SetTitle(title)))
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> io::Result<()> {
+        execute!(self.writer, Print(SetClipboard(content)))
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        execute!(self.writer, Print("\x07"))
+    }
+
     fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
         crossterm::cursor::position()
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
@@ -215,6 +271,19 @@ where
         )
     }
 
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        // crossterm has no command to set the scroll region (DECSTBM), so it is written directly
+        write!(self.writer, "\x1b[{};{}r", region.start + 1, region.end)?;
+        queue!(self.writer, ScrollUp(line_count))?;
+        write!(self.writer, "\x1b[r")
+    }
+
+    fn scroll_region_down(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        write!(self.writer, "\x1b[{};{}r", region.start + 1, region.end)?;
+        queue!(self.writer, ScrollDown(line_count))?;
+        write!(self.writer, "\x1b[r")
+    }
+
     fn append_lines(&mut self, n: u16) -> io::Result<()> {
         for _ in 0..n {
             queue!(self.writer, Print("\n"))?;
@@ -222,6 +291,52 @@ where
         self.writer.flush()
     }
 
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        queue!(self.writer, BeginSynchronizedUpdate)
+    }
+
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        queue!(self.writer, EndSynchronizedUpdate)
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        execute!(self.writer, EnterAlternateScreen)
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        execute!(self.writer, LeaveAlternateScreen)
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        execute!(self.writer, EnableMouseCapture)
+    }
+
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        execute!(self.writer, DisableMouseCapture)
+    }
+
+    /// The returned function writes to stdout, as the writer of the backend can't be reached from
+    /// a panic hook.
+    fn panic_restorer(&self) -> io::Result<PanicRestorer> {
+        Ok(Box::new(|| {
+            terminal::disable_raw_mode()?;
+            execute!(
+                io::stdout(),
+                DisableMouseCapture,
+                LeaveAlternateScreen,
+                Show
+            )
+        }))
+    }
+
     fn size(&self) -> io::Result<Rect> {
         let (width, height) = terminal::size()?;
         Ok(Rect::new(0, 0, width, height))
@@ -243,11 +358,50 @@ where
         })
     }
 
+    fn next_event(&mut self, timeout: Option<Duration>) -> io::Result<Option<Event>> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
+        read_event(timeout)
+    }
+
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> io::Result<Option<Color>> {
+        execute!(self.writer, Print(QueryDefaultColor(color)))?;
+        read_color_reply(
+            |timeout| read_event(Some(timeout)),
+            timeout,
+            &mut self.pending_events,
+        )
+    }
+
+    fn bytes_written(&self) -> io::Result<u64> {
+        Ok(self.writer.count())
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.writer.flush()
     }
 }
 
+/// Reads the next event from crossterm, waiting at most `timeout` when given.
+fn read_event(timeout: Option<Duration>) -> io::Result<Option<Event>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        if let Some(deadline) = deadline {
+            if !cevent::poll(deadline.saturating_duration_since(Instant::now()))? {
+                return Ok(None);
+            }
+        }
+        if let Ok(event) = Event::try_from(cevent::read()?) {
+            return Ok(Some(event));
+        }
+    }
+}
+
 impl From<Color> for CColor {
     fn from(color: Color) -> Self {
         match color {
@@ -274,6 +428,132 @@ impl From<Color> for CColor {
     }
 }
 
+impl From<CursorStyle> for SetCursorStyle {
+    fn from(style: CursorStyle) -> Self {
+        match style {
+            CursorStyle::DefaultUserShape => SetCursorStyle::DefaultUserShape,
+            CursorStyle::BlinkingBlock => SetCursorStyle::BlinkingBlock,
+            CursorStyle::SteadyBlock => SetCursorStyle::SteadyBlock,
+            CursorStyle::BlinkingUnderline => SetCursorStyle::BlinkingUnderScore,
+            CursorStyle::SteadyUnderline => SetCursorStyle::SteadyUnderScore,
+            CursorStyle::BlinkingBar => SetCursorStyle::BlinkingBar,
+            CursorStyle::SteadyBar => SetCursorStyle::SteadyBar,
+        }
+    }
+}
+
+impl TryFrom<CEvent> for Event {
+    type Error = CEvent;
+
+    /// Converts a crossterm event, failing for media and modifier key events.
+    fn try_from(event: CEvent) -> Result<Self, Self::Error> {
+        Ok(match event {
+            CEvent::FocusGained => Event::FocusGained,
+            CEvent::FocusLost => Event::FocusLost,
+            CEvent::Key(key) => Event::Key(key.try_into().map_err(CEvent::Key)?),
+            CEvent::Mouse(mouse) => Event::Mouse(mouse.into()),
+            CEvent::Paste(text) => Event::Paste(text),
+            CEvent::Resize(columns, rows) => Event::Resize(columns, rows),
+        })
+    }
+}
+
+impl TryFrom<CKeyEvent> for KeyEvent {
+    type Error = CKeyEvent;
+
+    fn try_from(event: CKeyEvent) -> Result<Self, Self::Error> {
+        let code = match event.code {
+            CKeyCode::Backspace => KeyCode::Backspace,
+            CKeyCode::Enter => KeyCode::Enter,
+            CKeyCode::Left => KeyCode::Left,
+            CKeyCode::Right => KeyCode::Right,
+            CKeyCode::Up => KeyCode::Up,
+            CKeyCode::Down => KeyCode::Down,
+            CKeyCode::Home => KeyCode::Home,
+            CKeyCode::End => KeyCode::End,
+            CKeyCode::PageUp => KeyCode::PageUp,
+            CKeyCode::PageDown => KeyCode::PageDown,
+            CKeyCode::Tab => KeyCode::Tab,
+            CKeyCode::BackTab => KeyCode::BackTab,
+            CKeyCode::Delete => KeyCode::Delete,
+            CKeyCode::Insert => KeyCode::Insert,
+            CKeyCode::F(n) => KeyCode::F(n),
+            CKeyCode::Char(c) => KeyCode::Char(c),
+            CKeyCode::Null => KeyCode::Null,
+            CKeyCode::Esc => KeyCode::Esc,
+            CKeyCode::CapsLock => KeyCode::CapsLock,
+            CKeyCode::ScrollLock => KeyCode::ScrollLock,
+            CKeyCode::NumLock => KeyCode::NumLock,
+            CKeyCode::PrintScreen => KeyCode::PrintScreen,
+            CKeyCode::Pause => KeyCode::Pause,
+            CKeyCode::Menu => KeyCode::Menu,
+            CKeyCode::KeypadBegin => KeyCode::KeypadBegin,
+            CKeyCode::Media(_) | CKeyCode::Modifier(_) => return Err(event),
+        };
+        let kind = match event.kind {
+            CKeyEventKind::Press => KeyEventKind::Press,
+            CKeyEventKind::Repeat => KeyEventKind::Repeat,
+            CKeyEventKind::Release => KeyEventKind::Release,
+        };
+        Ok(KeyEvent {
+            code,
+            modifiers: event.modifiers.into(),
+            kind,
+        })
+    }
+}
+
+impl From<CKeyModifiers> for KeyModifiers {
+    fn from(modifiers: CKeyModifiers) -> Self {
+        let mut result = KeyModifiers::empty();
+        result.set(
+            KeyModifiers::SHIFT,
+            modifiers.contains(CKeyModifiers::SHIFT),
+        );
+        result.set(
+            KeyModifiers::CONTROL,
+            modifiers.contains(CKeyModifiers::CONTROL),
+        );
+        result.set(KeyModifiers::ALT, modifiers.contains(CKeyModifiers::ALT));
+        result.set(
+            KeyModifiers::SUPER,
+            modifiers.contains(CKeyModifiers::SUPER),
+        );
+        result
+    }
+}
+
+impl From<CMouseEvent> for MouseEvent {
+    fn from(event: CMouseEvent) -> Self {
+        let kind = match event.kind {
+            CMouseEventKind::Down(button) => MouseEventKind::Down(button.into()),
+            CMouseEventKind::Up(button) => MouseEventKind::Up(button.into()),
+            CMouseEventKind::Drag(button) => MouseEventKind::Drag(button.into()),
+            CMouseEventKind::Moved => MouseEventKind::Moved,
+            CMouseEventKind::ScrollDown => MouseEventKind::ScrollDown,
+            CMouseEventKind::ScrollUp => MouseEventKind::ScrollUp,
+            CMouseEventKind::ScrollLeft => MouseEventKind::ScrollLeft,
+            CMouseEventKind::ScrollRight => MouseEventKind::ScrollRight,
+        };
+        MouseEvent {
+            kind,
+            column: event.column,
+            row: event.row,
+            modifiers: event.modifiers.into(),
+        }
+    }
+}
+
+impl From<CMouseButton> for MouseButton {
+    fn from(button: CMouseButton) -> Self {
+        match button {
+            CMouseButton::Left => MouseButton::Left,
+            CMouseButton::Right => MouseButton::Right,
+            CMouseButton::Middle => MouseButton::Middle,
+        }
+    }
+}
+
 /// The `ModifierDiff` struct is used to calculate the difference between two `Modifier`
 /// values. This is useful when updating the terminal display, as it allows for more
 /// efficient updates by only sending the necessary changes.
@@ -290,6 +570,8 @@ impl ModifierDiff {
     {
         //use crossterm::Attribute;
         let removed = self.from - self.to;
+        let underline = self.to.underline();
+        let underline_changed = self.from.underline() != underline;
         if removed.contains(Modifier::REVERSED) {
             queue!(w, SetAttribute(CAttribute::NoReverse))?;
         }
@@ -302,7 +584,7 @@ impl ModifierDiff {
         if removed.contains(Modifier::ITALIC) {
             queue!(w, SetAttribute(CAttribute::NoItalic))?;
         }
-        if removed.contains(Modifier::UNDERLINED) {
+        if underline_changed && underline.is_empty() {
             queue!(w, SetAttribute(CAttribute::NoUnderline))?;
         }
         if removed.contains(Modifier::DIM) {
@@ -314,6 +596,9 @@ impl ModifierDiff {
         if removed.contains(Modifier::SLOW_BLINK) || removed.contains(Modifier::RAPID_BLINK) {
             queue!(w, SetAttribute(CAttribute::NoBlink))?;
         }
+        if removed.contains(Modifier::OVERLINED) {
+            queue!(w, SetAttribute(CAttribute::NotOverLined))?;
+        }
 
         let added = self.to - self.from;
         if added.contains(Modifier::REVERSED) {
@@ -325,8 +610,15 @@ impl ModifierDiff {
         if added.contains(Modifier::ITALIC) {
             queue!(w, SetAttribute(CAttribute::Italic))?;
         }
-        if added.contains(Modifier::UNDERLINED) {
-            queue!(w, SetAttribute(CAttribute::Underlined))?;
+        if underline_changed && !underline.is_empty() {
+            let attribute = match underline {
+                Modifier::DOUBLE_UNDERLINED => CAttribute::DoubleUnderlined,
+                Modifier::CURLY_UNDERLINED => CAttribute::Undercurled,
+                Modifier::DOTTED_UNDERLINED => CAttribute::Underdotted,
+                Modifier::DASHED_UNDERLINED => CAttribute::Underdashed,
+                _ => CAttribute::Underlined,
+            };
+            queue!(w, SetAttribute(attribute))?;
         }
         if added.contains(Modifier::DIM) {
             queue!(w, SetAttribute(CAttribute::Dim))?;
@@ -340,7 +632,62 @@ impl ModifierDiff {
         if added.contains(Modifier::RAPID_BLINK) {
             queue!(w, SetAttribute(CAttribute::RapidBlink))?;
         }
+        if added.contains(Modifier::OVERLINED) {
+            queue!(w, SetAttribute(CAttribute::OverLined))?;
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::MediaKeyCode;
+
+    use super::*;
+
+    #[test]
+    fn convert_key_event() {
+        let event = CEvent::Key(CKeyEvent::new(CKeyCode::Char('c'), CKeyModifiers::CONTROL));
+        assert_eq!(
+            Event::try_from(event),
+            Ok(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            )))
+        );
+    }
+
+    #[test]
+    fn convert_unsupported_key_event() {
+        let event = CEvent::Key(CKeyEvent::from(CKeyCode::Media(MediaKeyCode::Play)));
+        assert_eq!(Event::try_from(event.clone()), Err(event));
+    }
+
+    #[test]
+    fn convert_mouse_event() {
+        let event = CEvent::Mouse(CMouseEvent {
+            kind: CMouseEventKind::Drag(CMouseButton::Right),
+            column: 3,
+            row: 4,
+            modifiers: CKeyModifiers::SHIFT | CKeyModifiers::ALT,
+        });
+        assert_eq!(
+            Event::try_from(event),
+            Ok(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Right),
+                column: 3,
+                row: 4,
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::ALT,
+            }))
+        );
+    }
+
+    #[test]
+    fn convert_resize_event() {
+        assert_eq!(
+            Event::try_from(CEvent::Resize(80, 24)),
+            Ok(Event::Resize(80, 24))
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! This module provides the [`TeeBackend`] implementation for the [`Backend`] trait. It forwards
+//! the output to two backends at once.
+//!
+//! [`Backend`]: crate::backend::Backend
+//! [`TeeBackend`]: crate::backend::TeeBackend
+use std::time::Duration;
+
+use crate::{
+    backend::{Backend, ClearType, CursorStyle, DefaultColor, PanicRestorer, WindowSize},
+    buffer::Cell,
+    event::Event,
+    prelude::Rect,
+    style::Color,
+};
+
+/// A [`Backend`] that renders to a primary and a secondary backend at once.
+///
+/// The primary backend is the one the application interacts with: its size, window size, cursor
+/// position and input events are the ones reported by the `TeeBackend`. Everything that is drawn
+/// is forwarded to both backends, except for the cells outside the area of the secondary backend,
+/// which are not drawn on it. This allows watching a live session on a real terminal while
+/// capturing it in a [`TestBackend`](crate::backend::TestBackend) of a different size for automated
+/// checks, or with an [`AsciicastBackend`](crate::backend::AsciicastBackend).
+///
+/// Scrolling regions are not forwarded (see [`Backend::scroll_region_up`]), so the
+/// [`Terminal`](crate::Terminal) redraws the rows that moved instead. This keeps both backends in
+/// sync even when only one of them supports scrolling regions. Likewise, raw mode, the alternate
+/// screen, mouse capture, the window title, the clipboard and the bell are only handled by the
+/// primary backend.
+///
+/// The `TeeBackend` returns the errors of the primary backend, so the errors of the secondary
+/// backend must be convertible into them.
+///
+/// To render to more than two backends, use another `TeeBackend` as the secondary backend.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::io::stdout;
+///
+/// use ratatui::{
+///     backend::{TeeBackend, TestBackend},
+///     prelude::*,
+///     widgets::Paragraph,
+/// };
+///
+/// let backend = TeeBackend::new(CrosstermBackend::new(stdout()), TestBackend::new(80, 24));
+/// let mut terminal = Terminal::new(backend)?;
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("Hello World!"), frame.size());
+/// })?;
+/// let captured = terminal.backend().secondary().buffer();
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct TeeBackend<P, S>
+where
+    P: Backend,
+    S: Backend,
+{
+    /// The backend the application interacts with.
+    primary: P,
+    /// The backend mirroring the primary backend.
+    secondary: S,
+}
+
+impl<P, S> TeeBackend<P, S>
+where
+    P: Backend,
+    S: Backend,
+{
+    /// Creates a new `TeeBackend` rendering to both `primary` and `secondary`.
+    pub fn new(primary: P, secondary: S) -> TeeBackend<P, S> {
+        TeeBackend { primary, secondary }
+    }
+
+    /// Returns a reference to the primary backend.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a mutable reference to the primary backend.
+    pub fn primary_mut(&mut self) -> &mut P {
+        &mut self.primary
+    }
+
+    /// Returns a reference to the secondary backend.
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    /// Returns a mutable reference to the secondary backend.
+    pub fn secondary_mut(&mut self) -> &mut S {
+        &mut self.secondary
+    }
+
+    /// Consumes the `TeeBackend`, returning the primary and secondary backends.
+    pub fn into_inner(self) -> (P, S) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<P, S> Backend for TeeBackend<P, S>
+where
+    P: Backend,
+    S: Backend,
+    P::Error: From<S::Error>,
+{
+    type Error = P::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), Self::Error>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let content: Vec<_> = content.collect();
+        self.primary.draw(content.iter().copied())?;
+        let area = self.secondary.size()?;
+        self.secondary.draw(
+            content
+                .into_iter()
+                .filter(|&(x, y, _)| x < area.width && y < area.height),
+        )?;
+        Ok(())
+    }
+
+    fn append_lines(&mut self, n: u16) -> Result<(), Self::Error> {
+        self.primary.append_lines(n)?;
+        Ok(self.secondary.append_lines(n)?)
+    }
+
+    fn begin_synchronized_update(&mut self) -> Result<(), Self::Error> {
+        self.primary.begin_synchronized_update()?;
+        Ok(self.secondary.begin_synchronized_update()?)
+    }
+
+    fn end_synchronized_update(&mut self) -> Result<(), Self::Error> {
+        self.primary.end_synchronized_update()?;
+        Ok(self.secondary.end_synchronized_update()?)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), Self::Error> {
+        self.primary.hide_cursor()?;
+        Ok(self.secondary.hide_cursor()?)
+    }
+
+    fn show_cursor(&mut self) -> Result<(), Self::Error> {
+        self.primary.show_cursor()?;
+        Ok(self.secondary.show_cursor()?)
+    }
+
+    fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), Self::Error> {
+        self.primary.set_cursor_style(style)?;
+        Ok(self.secondary.set_cursor_style(style)?)
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        self.primary.enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> Result<(), Self::Error> {
+        self.primary.disable_raw_mode()
+    }
+
+    fn enter_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        self.primary.enter_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&mut self) -> Result<(), Self::Error> {
+        self.primary.leave_alternate_screen()
+    }
+
+    fn enable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        self.primary.enable_mouse_capture()
+    }
+
+    fn disable_mouse_capture(&mut self) -> Result<(), Self::Error> {
+        self.primary.disable_mouse_capture()
+    }
+
+    fn panic_restorer(&self) -> Result<PanicRestorer, Self::Error> {
+        self.primary.panic_restorer()
+    }
+
+    fn set_title(&mut self, title: &str) -> Result<(), Self::Error> {
+        self.primary.set_title(title)
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> Result<(), Self::Error> {
+        self.primary.set_clipboard(content)
+    }
+
+    fn bell(&mut self) -> Result<(), Self::Error> {
+        self.primary.bell()
+    }
+
+    fn get_cursor(&mut self) -> Result<(u16, u16), Self::Error> {
+        self.primary.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), Self::Error> {
+        self.primary.set_cursor(x, y)?;
+        Ok(self.secondary.set_cursor(x, y)?)
+    }
+
+    fn clear(&mut self) -> Result<(), Self::Error> {
+        self.primary.clear()?;
+        Ok(self.secondary.clear()?)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> Result<(), Self::Error> {
+        self.primary.clear_region(clear_type)?;
+        Ok(self.secondary.clear_region(clear_type)?)
+    }
+
+    fn size(&self) -> Result<Rect, Self::Error> {
+        self.primary.size()
+    }
+
+    fn window_size(&mut self) -> Result<WindowSize, Self::Error> {
+        self.primary.window_size()
+    }
+
+    fn next_event(&mut self, timeout: Option<Duration>) -> Result<Option<Event>, Self::Error> {
+        self.primary.next_event(timeout)
+    }
+
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> Result<Option<Color>, Self::Error> {
+        self.primary.query_default_color(color, timeout)
+    }
+
+    fn bytes_written(&self) -> Result<u64, Self::Error> {
+        self.primary.bytes_written()
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.primary.flush()?;
+        Ok(self.secondary.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::TestBackend, buffer::Buffer, widgets::Paragraph, Terminal};
+
+    #[test]
+    fn size_is_primary_size() {
+        let backend = TeeBackend::new(TestBackend::new(10, 2), TestBackend::new(4, 1));
+        assert_eq!(backend.size().unwrap(), Rect::new(0, 0, 10, 2));
+    }
+
+    #[test]
+    fn draw_to_smaller_secondary() {
+        let backend = TeeBackend::new(TestBackend::new(6, 2), TestBackend::new(4, 1));
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("abcdef\nghijkl"), f.size()))
+            .unwrap();
+        let (primary, secondary) = terminal.backend().clone().into_inner();
+        primary.assert_buffer(&Buffer::with_lines(vec!["abcdef", "ghijkl"]));
+        secondary.assert_buffer(&Buffer::with_lines(vec!["abcd"]));
+    }
+
+    #[test]
+    fn draw_to_larger_secondary() {
+        let backend = TeeBackend::new(TestBackend::new(3, 1), TestBackend::new(5, 2));
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new("abc"), f.size()))
+            .unwrap();
+        terminal
+            .backend()
+            .secondary()
+            .assert_buffer(&Buffer::with_lines(vec!["abc  ", "     "]));
+    }
+
+    #[test]
+    fn cursor() {
+        let mut backend = TeeBackend::new(TestBackend::new(10, 2), TestBackend::new(4, 1));
+        backend.set_cursor(2, 1).unwrap();
+        assert_eq!(backend.get_cursor().unwrap(), (2, 1));
+        assert_eq!(backend.secondary_mut().get_cursor().unwrap(), (2, 1));
+    }
+
+    #[test]
+    fn clear() {
+        let mut backend = TeeBackend::new(TestBackend::new(3, 1), TestBackend::new(2, 1));
+        let cell = Cell::default().set_symbol("x").clone();
+        backend.draw([(0, 0, &cell)].into_iter()).unwrap();
+        backend.clear().unwrap();
+        backend
+            .primary()
+            .assert_buffer(&Buffer::with_lines(vec!["   "]));
+        backend
+            .secondary()
+            .assert_buffer(&Buffer::with_lines(vec!["  "]));
+    }
+
+    #[test]
+    fn scroll_regions_are_not_forwarded() {
+        let mut backend = TeeBackend::new(TestBackend::new(3, 3), TestBackend::new(3, 3));
+        let error = backend.scroll_region_up(0..3, 1).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+    }
+}
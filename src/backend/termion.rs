@@ -5,13 +5,33 @@
 //! [`TermionBackend`]: crate::backend::TermionBackend
 //! [Termion]: https://docs.rs/termion
 use std::{
-    fmt,
+    collections::VecDeque,
     io::{self, Write},
+    ops::Range,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex, OnceLock, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use termion::{
+    event::{Event as TEvent, Key, MouseButton as TMouseButton, MouseEvent as TMouseEvent},
+    input::TermRead,
+    raw::{IntoRawMode, RawTerminal},
 };
 
 use crate::{
-    backend::{Backend, ClearType, WindowSize},
+    backend::{
+        ansi::{
+            read_color_reply, Bg, CountingWriter, Fg, Hyperlink, ModifierDiff, QueryDefaultColor,
+            SetClipboard, SetCursorStyle, SetTitle,
+        },
+        Backend, ClearType, CursorStyle, DefaultColor, PanicRestorer, WindowSize,
+    },
     buffer::Cell,
+    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     prelude::Rect,
     style::{Color, Modifier},
 };
@@ -33,6 +53,11 @@ use crate::{
 /// screen mode. This backend automatically disable raw mode and switches back to the primary
 /// screen when the writer is dropped.
 ///
+/// Events are read from stdin by a thread that is started by the first call to
+/// [`Backend::next_event`] or [`Backend::query_default_color`]. From then on, ratatui owns stdin
+/// and the application must not read it itself, for example with [`TermRead`]. Until then, the
+/// backend only reads stdin to get the reply of [`Backend::get_cursor`].
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -63,7 +88,10 @@ pub struct TermionBackend<W>
 where
     W: Write,
 {
-    writer: W,
+    writer: CountingWriter<W>,
+    /// Events received while waiting for the reply to a query, returned by the next calls to
+    /// `next_event`.
+    pending_events: VecDeque<Event>,
 }
 
 impl<W> TermionBackend<W>
@@ -80,7 +108,10 @@ where
     /// let backend = TermionBackend::new(stdout());
     /// ```
     pub fn new(writer: W) -> TermionBackend<W> {
-        TermionBackend { writer }
+        TermionBackend {
+            writer: CountingWriter::new(writer),
+            pending_events: VecDeque::new(),
+        }
     }
 }
 
@@ -101,6 +132,8 @@ impl<W> Backend for TermionBackend<W>
 where
     W: Write,
 {
+    type Error = io::Error;
+
     fn clear(&mut self) -> io::Result<()> {
         self.clear_region(ClearType::All)
     }
@@ -116,6 +149,26 @@ where
         self.writer.flush()
     }
 
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        write!(
+            self.writer,
+            "\x1b[{};{}r{}\x1b[r",
+            region.start + 1,
+            region.end,
+            termion::scroll::Up(line_count)
+        )
+    }
+
+    fn scroll_region_down(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        write!(
+            self.writer,
+            "\x1b[{};{}r{}\x1b[r",
+            region.start + 1,
+            region.end,
+            termion::scroll::Down(line_count)
+        )
+    }
+
     fn append_lines(&mut self, n: u16) -> io::Result<()> {
         for _ in 0..n {
             writeln!(self.writer)?;
@@ -123,6 +176,72 @@ where
         self.writer.flush()
     }
 
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        write!(self.writer, "\x1b[?2026h")
+    }
+
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        write!(self.writer, "\x1b[?2026l")
+    }
+
+    /// Raw mode is a property of the terminal rather than of the writer, so it is enabled even if
+    /// the writer is not a [`RawTerminal`].
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        let mut raw_mode = raw_mode().lock().unwrap_or_else(PoisonError::into_inner);
+        if raw_mode.is_none() {
+            *raw_mode = Some(io::sink().into_raw_mode()?);
+        }
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        // dropping the raw terminal restores the previous mode
+        raw_mode()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        Ok(())
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}", termion::screen::ToAlternateScreen)?;
+        self.writer.flush()
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        write!(self.writer, "{}", termion::screen::ToMainScreen)?;
+        self.writer.flush()
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        write!(self.writer, "{ENABLE_MOUSE_CAPTURE}")?;
+        self.writer.flush()
+    }
+
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        write!(self.writer, "{DISABLE_MOUSE_CAPTURE}")?;
+        self.writer.flush()
+    }
+
+    /// The returned function writes to stdout, as the writer of the backend can't be reached from
+    /// a panic hook.
+    fn panic_restorer(&self) -> io::Result<PanicRestorer> {
+        Ok(Box::new(|| {
+            // the panic may have happened while the lock was held
+            if let Ok(mut raw_mode) = raw_mode().try_lock() {
+                raw_mode.take();
+            }
+            let mut stdout = io::stdout();
+            write!(
+                stdout,
+                "{DISABLE_MOUSE_CAPTURE}{}{}",
+                termion::screen::ToMainScreen,
+                termion::cursor::Show
+            )?;
+            stdout.flush()
+        }))
+    }
+
     fn hide_cursor(&mut self) -> io::Result<()> {
         write!(self.writer, "{}", termion::cursor::Hide)?;
         self.writer.flush()
@@ -133,8 +252,55 @@ where
         self.writer.flush()
     }
 
+    fn set_cursor_style(&mut self, style: CursorStyle) -> io::Result<()> {
+        write!(self.writer, "{}", SetCursorStyle(style))?;
+        self.writer.flush()
+    }
+
+    fn set_title(&mut self, title: &str) -> io::Result<()> {
+        write!(self.writer, "{}", SetTitle(title))?;
+        self.writer.flush()
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> io::Result<()> {
+        write!(self.writer, "{}", SetClipboard(content))?;
+        self.writer.flush()
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        write!(self.writer, "\x07")?;
+        self.writer.flush()
+    }
+
+    /// Queries the cursor position with `ESC [ 6 n`.
+    ///
+    /// The reply is read from stdin directly, unless the thread that reads events for
+    /// [`Backend::next_event`] was started, in which case it is read from that thread so that the
+    /// two don't race for it. Events received before the reply are then returned by the next calls
+    /// to `next_event`.
     fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
-        termion::cursor::DetectCursorPos::cursor_pos(&mut self.writer).map(|(x, y)| (x - 1, y - 1))
+        if STDIN_EVENTS.get().is_none() {
+            return termion::cursor::DetectCursorPos::cursor_pos(&mut self.writer)
+                .map(|(x, y)| (x - 1, y - 1));
+        }
+        write!(self.writer, "\x1b[6n")?;
+        self.writer.flush()?;
+        let deadline =
+            Instant::now() + Duration::from_millis(termion::raw::CONTROL_SEQUENCE_TIMEOUT);
+        while let Some(event) = read_termion_event(Some(deadline))? {
+            if let TEvent::Unsupported(reply) = &event {
+                if let Some(position) = parse_cursor_position(reply) {
+                    return Ok(position);
+                }
+            }
+            if let Ok(event) = Event::try_from(event) {
+                self.pending_events.push_back(event);
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "cursor position detection timed out",
+        ))
     }
 
     fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
@@ -152,6 +318,7 @@ where
         let mut fg = Color::Reset;
         let mut bg = Color::Reset;
         let mut modifier = Modifier::empty();
+        let mut hyperlink = None;
         let mut last_pos: Option<(u16, u16)> = None;
         for (x, y, cell) in content {
             // Move the cursor if the previous location was not (x - 1, y)
@@ -179,8 +346,15 @@ where
                 write!(string, "{}", Bg(cell.bg)).unwrap();
                 bg = cell.bg;
             }
+            if cell.hyperlink() != hyperlink {
+                write!(string, "{}", Hyperlink(cell.hyperlink())).unwrap();
+                hyperlink = cell.hyperlink();
+            }
             string.push_str(cell.symbol());
         }
+        if hyperlink.is_some() {
+            write!(string, "{}", Hyperlink(None)).unwrap();
+        }
         write!(
             self.writer,
             "{string}{}{}{}",
@@ -202,139 +376,243 @@ where
         })
     }
 
+    /// Reads events from stdin.
+    ///
+    /// Termion has no way to wait for input with a timeout, so the first call spawns a thread that
+    /// reads stdin for the rest of the program. From then on, ratatui owns stdin and the
+    /// application must not read it itself. Termion does not report resize, focus or paste events.
+    fn next_event(&mut self, timeout: Option<Duration>) -> io::Result<Option<Event>> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
+        read_event(timeout)
+    }
+
+    /// Queries the default color and reads the reply from stdin.
+    ///
+    /// Like [`Backend::next_event`], this starts the thread that reads stdin for the rest of the
+    /// program, so ratatui owns stdin from the first call on.
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> io::Result<Option<Color>> {
+        write!(self.writer, "{}", QueryDefaultColor(color))?;
+        self.writer.flush()?;
+        read_color_reply(
+            |timeout| read_event(Some(timeout)),
+            timeout,
+            &mut self.pending_events,
+        )
+    }
+
+    fn bytes_written(&self) -> io::Result<u64> {
+        Ok(self.writer.count())
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.writer.flush()
     }
 }
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
-struct Fg(Color);
-
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
-struct Bg(Color);
-
-/// The `ModifierDiff` struct is used to calculate the difference between two `Modifier`
-/// values. This is useful when updating the terminal display, as it allows for more
-/// efficient updates by only sending the necessary changes.
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
-struct ModifierDiff {
-    from: Modifier,
-    to: Modifier,
+
+/// Enables the mouse reporting modes that termion parses (`MouseTerminal` uses the same ones).
+const ENABLE_MOUSE_CAPTURE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+
+/// Disables the modes enabled by [`ENABLE_MOUSE_CAPTURE`].
+const DISABLE_MOUSE_CAPTURE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+/// Returns the raw terminal keeping the terminal in raw mode while it is set.
+fn raw_mode() -> &'static Mutex<Option<RawTerminal<io::Sink>>> {
+    static RAW_MODE: Mutex<Option<RawTerminal<io::Sink>>> = Mutex::new(None);
+    &RAW_MODE
 }
 
-impl fmt::Display for Fg {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use termion::color::Color as TermionColor;
-        match self.0 {
-            Color::Reset => termion::color::Reset.write_fg(f),
-            Color::Black => termion::color::Black.write_fg(f),
-            Color::Red => termion::color::Red.write_fg(f),
-            Color::Green => termion::color::Green.write_fg(f),
-            Color::Yellow => termion::color::Yellow.write_fg(f),
-            Color::Blue => termion::color::Blue.write_fg(f),
-            Color::Magenta => termion::color::Magenta.write_fg(f),
-            Color::Cyan => termion::color::Cyan.write_fg(f),
-            Color::Gray => termion::color::White.write_fg(f),
-            Color::DarkGray => termion::color::LightBlack.write_fg(f),
-            Color::LightRed => termion::color::LightRed.write_fg(f),
-            Color::LightGreen => termion::color::LightGreen.write_fg(f),
-            Color::LightBlue => termion::color::LightBlue.write_fg(f),
-            Color::LightYellow => termion::color::LightYellow.write_fg(f),
-            Color::LightMagenta => termion::color::LightMagenta.write_fg(f),
-            Color::LightCyan => termion::color::LightCyan.write_fg(f),
-            Color::White => termion::color::LightWhite.write_fg(f),
-            Color::Indexed(i) => termion::color::AnsiValue(i).write_fg(f),
-            Color::Rgb(r, g, b) => termion::color::Rgb(r, g, b).write_fg(f),
+/// Reads the next event from stdin, waiting at most `timeout` when given.
+fn read_event(timeout: Option<Duration>) -> io::Result<Option<Event>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    while let Some(event) = read_termion_event(deadline)? {
+        if let Ok(event) = Event::try_from(event) {
+            return Ok(Some(event));
         }
     }
+    Ok(None)
 }
-impl fmt::Display for Bg {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use termion::color::Color as TermionColor;
-        match self.0 {
-            Color::Reset => termion::color::Reset.write_bg(f),
-            Color::Black => termion::color::Black.write_bg(f),
-            Color::Red => termion::color::Red.write_bg(f),
-            Color::Green => termion::color::Green.write_bg(f),
-            Color::Yellow => termion::color::Yellow.write_bg(f),
-            Color::Blue => termion::color::Blue.write_bg(f),
-            Color::Magenta => termion::color::Magenta.write_bg(f),
-            Color::Cyan => termion::color::Cyan.write_bg(f),
-            Color::Gray => termion::color::White.write_bg(f),
-            Color::DarkGray => termion::color::LightBlack.write_bg(f),
-            Color::LightRed => termion::color::LightRed.write_bg(f),
-            Color::LightGreen => termion::color::LightGreen.write_bg(f),
-            Color::LightBlue => termion::color::LightBlue.write_bg(f),
-            Color::LightYellow => termion::color::LightYellow.write_bg(f),
-            Color::LightMagenta => termion::color::LightMagenta.write_bg(f),
-            Color::LightCyan => termion::color::LightCyan.write_bg(f),
-            Color::White => termion::color::LightWhite.write_bg(f),
-            Color::Indexed(i) => termion::color::AnsiValue(i).write_bg(f),
-            Color::Rgb(r, g, b) => termion::color::Rgb(r, g, b).write_bg(f),
+
+/// Reads the next termion event from stdin, including unsupported ones, waiting until `deadline`
+/// when given.
+fn read_termion_event(deadline: Option<Instant>) -> io::Result<Option<TEvent>> {
+    let events = stdin_events()
+        .lock()
+        .map_err(|_| io::Error::new(io::ErrorKind::Other, "stdin reader poisoned"))?;
+    let disconnected = || io::Error::new(io::ErrorKind::UnexpectedEof, "stdin was closed");
+    let event = match deadline {
+        Some(deadline) => {
+            match events.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+            }
         }
-    }
+        None => events.recv().map_err(|_| disconnected())?,
+    };
+    event.map(Some)
 }
 
-impl fmt::Display for ModifierDiff {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let remove = self.from - self.to;
-        if remove.contains(Modifier::REVERSED) {
-            write!(f, "{}", termion::style::NoInvert)?;
-        }
-        if remove.contains(Modifier::BOLD) {
-            // XXX: the termion NoBold flag actually enables double-underline on ECMA-48 compliant
-            // terminals, and NoFaint additionally disables bold... so we use this trick to get
-            // the right semantics.
-            write!(f, "{}", termion::style::NoFaint)?;
-
-            if self.to.contains(Modifier::DIM) {
-                write!(f, "{}", termion::style::Faint)?;
+/// Parses the reply to a cursor position query, `ESC [ row ; column R`, into a zero based
+/// `(x, y)` position.
+///
+/// Termion does not know about the reply and reports it as an unsupported event.
+fn parse_cursor_position(reply: &[u8]) -> Option<(u16, u16)> {
+    let reply = reply.strip_prefix(b"\x1b[")?.strip_suffix(b"R")?;
+    let (row, column) = std::str::from_utf8(reply).ok()?.split_once(';')?;
+    let x = column.parse::<u16>().ok()?.checked_sub(1)?;
+    let y = row.parse::<u16>().ok()?.checked_sub(1)?;
+    Some((x, y))
+}
+
+/// The receiving end of the thread reading events from stdin, set once the thread is spawned.
+static STDIN_EVENTS: OnceLock<Mutex<Receiver<io::Result<TEvent>>>> = OnceLock::new();
+
+/// Returns the receiving end of the thread reading events from stdin, spawning it if needed.
+fn stdin_events() -> &'static Mutex<Receiver<io::Result<TEvent>>> {
+    STDIN_EVENTS.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for event in io::stdin().events() {
+                if sender.send(event).is_err() {
+                    break;
+                }
             }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+impl TryFrom<TEvent> for Event {
+    type Error = TEvent;
+
+    /// Converts a termion event, failing for unsupported events.
+    fn try_from(event: TEvent) -> Result<Self, Self::Error> {
+        match event {
+            TEvent::Key(key) => KeyEvent::try_from(key).map(Event::Key).map_err(TEvent::Key),
+            TEvent::Mouse(mouse) => Ok(Event::Mouse(mouse.into())),
+            TEvent::Unsupported(_) => Err(event),
         }
-        if remove.contains(Modifier::ITALIC) {
-            write!(f, "{}", termion::style::NoItalic)?;
-        }
-        if remove.contains(Modifier::UNDERLINED) {
-            write!(f, "{}", termion::style::NoUnderline)?;
-        }
-        if remove.contains(Modifier::DIM) {
-            write!(f, "{}", termion::style::NoFaint)?;
+    }
+}
+
+impl TryFrom<Key> for KeyEvent {
+    type Error = Key;
+
+    fn try_from(key: Key) -> Result<Self, Self::Error> {
+        let (code, modifiers) = match key {
+            Key::Backspace => (KeyCode::Backspace, KeyModifiers::empty()),
+            Key::Left => (KeyCode::Left, KeyModifiers::empty()),
+            Key::Right => (KeyCode::Right, KeyModifiers::empty()),
+            Key::Up => (KeyCode::Up, KeyModifiers::empty()),
+            Key::Down => (KeyCode::Down, KeyModifiers::empty()),
+            Key::Home => (KeyCode::Home, KeyModifiers::empty()),
+            Key::End => (KeyCode::End, KeyModifiers::empty()),
+            Key::PageUp => (KeyCode::PageUp, KeyModifiers::empty()),
+            Key::PageDown => (KeyCode::PageDown, KeyModifiers::empty()),
+            Key::BackTab => (KeyCode::BackTab, KeyModifiers::SHIFT),
+            Key::Delete => (KeyCode::Delete, KeyModifiers::empty()),
+            Key::Insert => (KeyCode::Insert, KeyModifiers::empty()),
+            Key::F(n) => (KeyCode::F(n), KeyModifiers::empty()),
+            Key::Char('\n') => (KeyCode::Enter, KeyModifiers::empty()),
+            Key::Char('\t') => (KeyCode::Tab, KeyModifiers::empty()),
+            Key::Char(c) if c.is_uppercase() => (KeyCode::Char(c), KeyModifiers::SHIFT),
+            Key::Char(c) => (KeyCode::Char(c), KeyModifiers::empty()),
+            Key::Alt(c) => (KeyCode::Char(c), KeyModifiers::ALT),
+            Key::Ctrl(c) => (KeyCode::Char(c), KeyModifiers::CONTROL),
+            Key::Null => (KeyCode::Null, KeyModifiers::empty()),
+            Key::Esc => (KeyCode::Esc, KeyModifiers::empty()),
+            _ => return Err(key),
+        };
+        Ok(KeyEvent::new(code, modifiers))
+    }
+}
 
-            // XXX: the NoFaint flag additionally disables bold as well, so we need to re-enable it
-            // here if we want it.
-            if self.to.contains(Modifier::BOLD) {
-                write!(f, "{}", termion::style::Bold)?;
+/// Converts a termion mouse event.
+///
+/// Termion does not report which button was released or held down while dragging, so these are
+/// reported as [`MouseButton::Left`].
+impl From<TMouseEvent> for MouseEvent {
+    fn from(event: TMouseEvent) -> Self {
+        let (kind, x, y) = match event {
+            TMouseEvent::Press(button, x, y) => {
+                let kind = match button {
+                    TMouseButton::Left => MouseEventKind::Down(MouseButton::Left),
+                    TMouseButton::Right => MouseEventKind::Down(MouseButton::Right),
+                    TMouseButton::Middle => MouseEventKind::Down(MouseButton::Middle),
+                    TMouseButton::WheelUp => MouseEventKind::ScrollUp,
+                    TMouseButton::WheelDown => MouseEventKind::ScrollDown,
+                };
+                (kind, x, y)
             }
+            TMouseEvent::Release(x, y) => (MouseEventKind::Up(MouseButton::Left), x, y),
+            TMouseEvent::Hold(x, y) => (MouseEventKind::Drag(MouseButton::Left), x, y),
+        };
+        // termion positions start at 1
+        MouseEvent {
+            kind,
+            column: x.saturating_sub(1),
+            row: y.saturating_sub(1),
+            modifiers: KeyModifiers::empty(),
         }
-        if remove.contains(Modifier::CROSSED_OUT) {
-            write!(f, "{}", termion::style::NoCrossedOut)?;
-        }
-        if remove.contains(Modifier::SLOW_BLINK) || remove.contains(Modifier::RAPID_BLINK) {
-            write!(f, "{}", termion::style::NoBlink)?;
-        }
+    }
+}
 
-        let add = self.to - self.from;
-        if add.contains(Modifier::REVERSED) {
-            write!(f, "{}", termion::style::Invert)?;
-        }
-        if add.contains(Modifier::BOLD) {
-            write!(f, "{}", termion::style::Bold)?;
-        }
-        if add.contains(Modifier::ITALIC) {
-            write!(f, "{}", termion::style::Italic)?;
-        }
-        if add.contains(Modifier::UNDERLINED) {
-            write!(f, "{}", termion::style::Underline)?;
-        }
-        if add.contains(Modifier::DIM) {
-            write!(f, "{}", termion::style::Faint)?;
-        }
-        if add.contains(Modifier::CROSSED_OUT) {
-            write!(f, "{}", termion::style::CrossedOut)?;
-        }
-        if add.contains(Modifier::SLOW_BLINK) || add.contains(Modifier::RAPID_BLINK) {
-            write!(f, "{}", termion::style::Blink)?;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    #[test]
+    fn convert_key_event() {
+        let key = |key| Event::try_from(TEvent::Key(key));
+        assert_eq!(
+            key(Key::Ctrl('c')),
+            Ok(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            )))
+        );
+        assert_eq!(key(Key::Char('\n')), Ok(Event::Key(KeyCode::Enter.into())));
+        assert_eq!(
+            key(Key::Char('A')),
+            Ok(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::SHIFT
+            )))
+        );
+    }
+
+    #[test]
+    fn convert_mouse_event() {
+        assert_eq!(
+            Event::try_from(TEvent::Mouse(TMouseEvent::Press(TMouseButton::Right, 1, 2))),
+            Ok(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Right),
+                column: 0,
+                row: 1,
+                modifiers: KeyModifiers::empty(),
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_cursor_position_reply() {
+        assert_eq!(parse_cursor_position(b"\x1b[1;1R"), Some((0, 0)));
+        assert_eq!(parse_cursor_position(b"\x1b[12;34R"), Some((33, 11)));
+        assert_eq!(parse_cursor_position(b"\x1b[0;1R"), None);
+        assert_eq!(parse_cursor_position(b"\x1b[12R"), None);
+        assert_eq!(parse_cursor_position(b"\x1b[1;2~"), None);
+        assert_eq!(parse_cursor_position(b"\x1b[?"), None);
+    }
+
+    #[test]
+    fn convert_unsupported_event() {
+        let event = TEvent::Unsupported(vec![0x1b, b'[', b'?']);
+        assert_eq!(Event::try_from(event.clone()), Err(event));
     }
 }
@@ -5,19 +5,35 @@
 //! [`TermwizBackend`]: crate::backend::TermionBackend
 //! [Termwiz]: https://crates.io/crates/termwiz
 
-use std::{error::Error, io};
+use std::{
+    collections::VecDeque,
+    error::Error,
+    io::{self, Write},
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use termwiz::{
     caps::Capabilities,
     cell::{AttributeChange, Blink, Intensity, Underline},
     color::{AnsiColor, ColorAttribute, SrgbaTuple},
-    surface::{Change, CursorVisibility, Position},
+    hyperlink::Hyperlink,
+    input::{
+        InputEvent, KeyCode as TKeyCode, KeyEvent as TKeyEvent, Modifiers, MouseButtons,
+        MouseEvent as TMouseEvent,
+    },
+    surface::{Change, CursorShape, CursorVisibility, Position},
     terminal::{buffered::BufferedTerminal, ScreenSize, SystemTerminal, Terminal},
 };
 
 use crate::{
-    backend::{Backend, WindowSize},
+    backend::{
+        ansi::{read_color_reply, QueryDefaultColor, SetClipboard, SetTitle},
+        Backend, CursorStyle, DefaultColor, PanicRestorer, WindowSize,
+    },
     buffer::Cell,
+    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::Size,
     prelude::Rect,
     style::{Color, Modifier},
@@ -62,6 +78,11 @@ use crate::{
 /// [examples]: https://github.com/ratatui-org/ratatui/tree/main/examples#readme
 pub struct TermwizBackend {
     buffered_terminal: BufferedTerminal<SystemTerminal>,
+    /// The mouse buttons held down, used to report drags and button releases
+    mouse_buttons: MouseButtons,
+    /// Events received while waiting for the reply to a query, returned by the next calls to
+    /// `next_event`
+    pending_events: VecDeque<Event>,
 }
 
 impl TermwizBackend {
@@ -89,13 +110,15 @@ impl TermwizBackend {
             BufferedTerminal::new(SystemTerminal::new(Capabilities::new_from_env()?)?)?;
         buffered_terminal.terminal().set_raw_mode()?;
         buffered_terminal.terminal().enter_alternate_screen()?;
-        Ok(TermwizBackend { buffered_terminal })
+        Ok(TermwizBackend::with_buffered_terminal(buffered_terminal))
     }
 
     /// Creates a new Termwiz backend instance with the given buffered terminal.
     pub fn with_buffered_terminal(instance: BufferedTerminal<SystemTerminal>) -> TermwizBackend {
         TermwizBackend {
             buffered_terminal: instance,
+            mouse_buttons: MouseButtons::NONE,
+            pending_events: VecDeque::new(),
         }
     }
 
@@ -108,9 +131,66 @@ impl TermwizBackend {
     pub fn buffered_terminal_mut(&mut self) -> &mut BufferedTerminal<SystemTerminal> {
         &mut self.buffered_terminal
     }
+
+    /// Restores the scroll region to the whole screen after scrolling a part of it.
+    fn reset_scroll_region(&mut self) {
+        let (_, rows) = self.buffered_terminal.dimensions();
+        self.buffered_terminal.add_change(Change::ScrollRegionUp {
+            first_row: 0,
+            region_size: rows,
+            scroll_count: 0,
+        });
+    }
+
+    /// Reads the next event from the terminal, waiting at most `timeout` when given.
+    fn read_event(&mut self, timeout: Option<Duration>) -> io::Result<Option<Event>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let wait = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            let event = match self
+                .buffered_terminal
+                .terminal()
+                .poll_input(wait)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            {
+                Some(InputEvent::Mouse(mouse)) => Event::Mouse(self.mouse_event(mouse)),
+                Some(event) => match Event::try_from(event) {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                },
+                None if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                    return Ok(None)
+                }
+                None => continue,
+            };
+            return Ok(Some(event));
+        }
+    }
+
+    /// Converts a mouse event, using the buttons held down before it to report drags and button
+    /// releases, which termwiz does not distinguish from presses and moves.
+    fn mouse_event(&mut self, event: TMouseEvent) -> MouseEvent {
+        let wheel = MouseButtons::VERT_WHEEL | MouseButtons::HORZ_WHEEL;
+        let previous = if event.mouse_buttons.intersects(wheel) {
+            self.mouse_buttons
+        } else {
+            std::mem::replace(&mut self.mouse_buttons, event.mouse_buttons)
+        };
+        let mut mouse = MouseEvent::from(event);
+        mouse.kind = match (mouse.kind, pressed_button(previous)) {
+            (MouseEventKind::Down(button), Some(held)) if button == held => {
+                MouseEventKind::Drag(button)
+            }
+            (MouseEventKind::Moved, Some(held)) => MouseEventKind::Up(held),
+            (kind, _) => kind,
+        };
+        mouse
+    }
 }
 
 impl Backend for TermwizBackend {
+    type Error = io::Error;
+
     fn draw<'a, I>(&mut self, content: I) -> Result<(), io::Error>
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
@@ -143,12 +223,16 @@ impl Backend for TermwizBackend {
 
             self.buffered_terminal
                 .add_change(Change::Attribute(AttributeChange::Underline(
-                    if cell.modifier.contains(Modifier::UNDERLINED) {
-                        Underline::Single
-                    } else {
-                        Underline::None
+                    match cell.modifier.underline() {
+                        Modifier::UNDERLINED => Underline::Single,
+                        Modifier::DOUBLE_UNDERLINED => Underline::Double,
+                        Modifier::CURLY_UNDERLINED => Underline::Curly,
+                        Modifier::DOTTED_UNDERLINED => Underline::Dotted,
+                        Modifier::DASHED_UNDERLINED => Underline::Dashed,
+                        _ => Underline::None,
                     },
                 )));
+            // termwiz has no overline attribute, so `Modifier::OVERLINED` is ignored
 
             self.buffered_terminal
                 .add_change(Change::Attribute(AttributeChange::Reverse(
@@ -176,6 +260,12 @@ impl Backend for TermwizBackend {
                     },
                 )));
 
+            self.buffered_terminal
+                .add_change(Change::Attribute(AttributeChange::Hyperlink(
+                    cell.hyperlink()
+                        .map(|target| Arc::new(Hyperlink::new(target))),
+                )));
+
             self.buffered_terminal.add_change(cell.symbol());
         }
         Ok(())
@@ -193,6 +283,104 @@ impl Backend for TermwizBackend {
         Ok(())
     }
 
+    fn set_cursor_style(&mut self, style: CursorStyle) -> io::Result<()> {
+        self.buffered_terminal
+            .add_change(Change::CursorShape(style.into()));
+        Ok(())
+    }
+
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        // The sequence is written to the underlying terminal directly, as the buffered terminal
+        // would otherwise render it as text into its surface.
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text("\x1b[?2026h".to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        // Render the pending changes of the frame before ending the update
+        self.flush()?;
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text("\x1b[?2026l".to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .set_raw_mode()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .set_cooked_mode()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .enter_alternate_screen()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .exit_alternate_screen()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text(ENABLE_MOUSE_CAPTURE.to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text(DISABLE_MOUSE_CAPTURE.to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    /// The returned function writes to stdout, as the terminal of the backend can't be reached
+    /// from a panic hook. It can't disable raw mode, which termwiz restores when the backend is
+    /// dropped while unwinding.
+    fn panic_restorer(&self) -> io::Result<PanicRestorer> {
+        Ok(Box::new(|| {
+            let mut stdout = io::stdout();
+            write!(stdout, "{DISABLE_MOUSE_CAPTURE}\x1b[?1049l\x1b[?25h")?;
+            stdout.flush()
+        }))
+    }
+
+    fn set_title(&mut self, title: &str) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text(SetTitle(title).to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text(SetClipboard(content).to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text("\x07".to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
     fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
         let (x, y) = self.buffered_terminal.cursor_position();
         Ok((x as u16, y as u16))
@@ -213,6 +401,26 @@ impl Backend for TermwizBackend {
         Ok(())
     }
 
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        self.buffered_terminal.add_change(Change::ScrollRegionUp {
+            first_row: region.start as usize,
+            region_size: region.len(),
+            scroll_count: line_count as usize,
+        });
+        self.reset_scroll_region();
+        Ok(())
+    }
+
+    fn scroll_region_down(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        self.buffered_terminal.add_change(Change::ScrollRegionDown {
+            first_row: region.start as usize,
+            region_size: region.len(),
+            scroll_count: line_count as usize,
+        });
+        self.reset_scroll_region();
+        Ok(())
+    }
+
     fn size(&self) -> Result<Rect, io::Error> {
         let (cols, rows) = self.buffered_terminal.dimensions();
         Ok(Rect::new(0, 0, u16_max(cols), u16_max(rows)))
@@ -241,6 +449,32 @@ impl Backend for TermwizBackend {
         })
     }
 
+    fn next_event(&mut self, timeout: Option<Duration>) -> io::Result<Option<Event>> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
+        self.read_event(timeout)
+    }
+
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> io::Result<Option<Color>> {
+        self.buffered_terminal
+            .terminal()
+            .render(&[Change::Text(QueryDefaultColor(color).to_string())])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let mut pending_events = std::mem::take(&mut self.pending_events);
+        let color = read_color_reply(
+            |timeout| self.read_event(Some(timeout)),
+            timeout,
+            &mut pending_events,
+        );
+        self.pending_events = pending_events;
+        color
+    }
+
     fn flush(&mut self) -> Result<(), io::Error> {
         self.buffered_terminal
             .flush()
@@ -249,6 +483,141 @@ impl Backend for TermwizBackend {
     }
 }
 
+impl TryFrom<InputEvent> for Event {
+    type Error = InputEvent;
+
+    /// Converts a termwiz input event, failing for unsupported events.
+    ///
+    /// Termwiz reports the buttons held down rather than button presses and releases, so mouse
+    /// events holding a button are reported as [`MouseEventKind::Down`] and the others as
+    /// [`MouseEventKind::Moved`]. [`TermwizBackend`] keeps track of the buttons to report drags and
+    /// releases.
+    fn try_from(event: InputEvent) -> Result<Self, Self::Error> {
+        match event {
+            InputEvent::Key(key) => KeyEvent::try_from(key)
+                .map(Event::Key)
+                .map_err(InputEvent::Key),
+            InputEvent::Mouse(mouse) => Ok(Event::Mouse(mouse.into())),
+            InputEvent::Resized { cols, rows } => Ok(Event::Resize(cols as u16, rows as u16)),
+            InputEvent::Paste(text) => Ok(Event::Paste(text)),
+            InputEvent::PixelMouse(_) | InputEvent::Wake => Err(event),
+        }
+    }
+}
+
+impl TryFrom<TKeyEvent> for KeyEvent {
+    type Error = TKeyEvent;
+
+    fn try_from(event: TKeyEvent) -> Result<Self, Self::Error> {
+        let mut modifiers = event.modifiers.into();
+        let code = match event.key {
+            TKeyCode::Char(c) => {
+                if c.is_uppercase() {
+                    modifiers |= KeyModifiers::SHIFT;
+                }
+                KeyCode::Char(c)
+            }
+            TKeyCode::Backspace => KeyCode::Backspace,
+            TKeyCode::Tab => KeyCode::Tab,
+            TKeyCode::Enter => KeyCode::Enter,
+            TKeyCode::Escape => KeyCode::Esc,
+            TKeyCode::Menu => KeyCode::Menu,
+            TKeyCode::Pause => KeyCode::Pause,
+            TKeyCode::CapsLock => KeyCode::CapsLock,
+            TKeyCode::PageUp => KeyCode::PageUp,
+            TKeyCode::PageDown => KeyCode::PageDown,
+            TKeyCode::End => KeyCode::End,
+            TKeyCode::Home => KeyCode::Home,
+            TKeyCode::LeftArrow | TKeyCode::ApplicationLeftArrow => KeyCode::Left,
+            TKeyCode::RightArrow | TKeyCode::ApplicationRightArrow => KeyCode::Right,
+            TKeyCode::UpArrow | TKeyCode::ApplicationUpArrow => KeyCode::Up,
+            TKeyCode::DownArrow | TKeyCode::ApplicationDownArrow => KeyCode::Down,
+            TKeyCode::PrintScreen => KeyCode::PrintScreen,
+            TKeyCode::Insert => KeyCode::Insert,
+            TKeyCode::Delete => KeyCode::Delete,
+            TKeyCode::Function(n) => KeyCode::F(n),
+            TKeyCode::NumLock => KeyCode::NumLock,
+            TKeyCode::ScrollLock => KeyCode::ScrollLock,
+            TKeyCode::Numpad0 => KeyCode::Char('0'),
+            TKeyCode::Numpad1 => KeyCode::Char('1'),
+            TKeyCode::Numpad2 => KeyCode::Char('2'),
+            TKeyCode::Numpad3 => KeyCode::Char('3'),
+            TKeyCode::Numpad4 => KeyCode::Char('4'),
+            TKeyCode::Numpad5 => KeyCode::Char('5'),
+            TKeyCode::Numpad6 => KeyCode::Char('6'),
+            TKeyCode::Numpad7 => KeyCode::Char('7'),
+            TKeyCode::Numpad8 => KeyCode::Char('8'),
+            TKeyCode::Numpad9 => KeyCode::Char('9'),
+            TKeyCode::Multiply => KeyCode::Char('*'),
+            TKeyCode::Add => KeyCode::Char('+'),
+            TKeyCode::Subtract => KeyCode::Char('-'),
+            TKeyCode::Decimal => KeyCode::Char('.'),
+            TKeyCode::Divide => KeyCode::Char('/'),
+            _ => return Err(event),
+        };
+        Ok(KeyEvent::new(code, modifiers))
+    }
+}
+
+impl From<Modifiers> for KeyModifiers {
+    fn from(modifiers: Modifiers) -> Self {
+        let mut result = KeyModifiers::empty();
+        result.set(KeyModifiers::SHIFT, modifiers.contains(Modifiers::SHIFT));
+        result.set(KeyModifiers::CONTROL, modifiers.contains(Modifiers::CTRL));
+        result.set(KeyModifiers::ALT, modifiers.contains(Modifiers::ALT));
+        result.set(KeyModifiers::SUPER, modifiers.contains(Modifiers::SUPER));
+        result
+    }
+}
+
+impl From<TMouseEvent> for MouseEvent {
+    fn from(event: TMouseEvent) -> Self {
+        let buttons = event.mouse_buttons;
+        let kind = if buttons.contains(MouseButtons::VERT_WHEEL) {
+            if buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                MouseEventKind::ScrollUp
+            } else {
+                MouseEventKind::ScrollDown
+            }
+        } else if buttons.contains(MouseButtons::HORZ_WHEEL) {
+            if buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                MouseEventKind::ScrollLeft
+            } else {
+                MouseEventKind::ScrollRight
+            }
+        } else {
+            pressed_button(buttons).map_or(MouseEventKind::Moved, MouseEventKind::Down)
+        };
+        // positions reported by terminal escape sequences start at 1, unlike the Windows console
+        let offset = u16::from(!cfg!(windows));
+        MouseEvent {
+            kind,
+            column: event.x.saturating_sub(offset),
+            row: event.y.saturating_sub(offset),
+            modifiers: event.modifiers.into(),
+        }
+    }
+}
+
+/// Enables the mouse reporting modes that termwiz enables in raw mode.
+const ENABLE_MOUSE_CAPTURE: &str = "\x1b[?1003h\x1b[?1006h";
+
+/// Disables the modes enabled by [`ENABLE_MOUSE_CAPTURE`].
+const DISABLE_MOUSE_CAPTURE: &str = "\x1b[?1006l\x1b[?1003l";
+
+/// Returns the first button held down in `buttons`.
+fn pressed_button(buttons: MouseButtons) -> Option<MouseButton> {
+    if buttons.contains(MouseButtons::LEFT) {
+        Some(MouseButton::Left)
+    } else if buttons.contains(MouseButtons::RIGHT) {
+        Some(MouseButton::Right)
+    } else if buttons.contains(MouseButtons::MIDDLE) {
+        Some(MouseButton::Middle)
+    } else {
+        None
+    }
+}
+
 impl From<Color> for ColorAttribute {
     fn from(color: Color) -> ColorAttribute {
         match color {
@@ -276,7 +645,78 @@ impl From<Color> for ColorAttribute {
     }
 }
 
+impl From<CursorStyle> for CursorShape {
+    fn from(style: CursorStyle) -> CursorShape {
+        match style {
+            CursorStyle::DefaultUserShape => CursorShape::Default,
+            CursorStyle::BlinkingBlock => CursorShape::BlinkingBlock,
+            CursorStyle::SteadyBlock => CursorShape::SteadyBlock,
+            CursorStyle::BlinkingUnderline => CursorShape::BlinkingUnderline,
+            CursorStyle::SteadyUnderline => CursorShape::SteadyUnderline,
+            CursorStyle::BlinkingBar => CursorShape::BlinkingBar,
+            CursorStyle::SteadyBar => CursorShape::SteadyBar,
+        }
+    }
+}
+
 #[inline]
 fn u16_max(i: usize) -> u16 {
     u16::try_from(i).unwrap_or(u16::MAX)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_key_event() {
+        let event = InputEvent::Key(TKeyEvent {
+            key: TKeyCode::Char('c'),
+            modifiers: Modifiers::CTRL,
+        });
+        assert_eq!(
+            Event::try_from(event),
+            Ok(Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            )))
+        );
+    }
+
+    #[test]
+    fn convert_mouse_event() {
+        let mouse = |mouse_buttons| {
+            Event::try_from(InputEvent::Mouse(TMouseEvent {
+                x: 1,
+                y: 2,
+                mouse_buttons,
+                modifiers: Modifiers::NONE,
+            }))
+        };
+        let event = |kind| {
+            Ok(Event::Mouse(MouseEvent {
+                kind,
+                column: 0,
+                row: 1,
+                modifiers: KeyModifiers::empty(),
+            }))
+        };
+        assert_eq!(
+            mouse(MouseButtons::LEFT),
+            event(MouseEventKind::Down(MouseButton::Left))
+        );
+        assert_eq!(mouse(MouseButtons::NONE), event(MouseEventKind::Moved));
+        assert_eq!(
+            mouse(MouseButtons::VERT_WHEEL | MouseButtons::WHEEL_POSITIVE),
+            event(MouseEventKind::ScrollUp)
+        );
+    }
+
+    #[test]
+    fn convert_resize_event() {
+        assert_eq!(
+            Event::try_from(InputEvent::Resized { cols: 80, rows: 24 }),
+            Ok(Event::Resize(80, 24))
+        );
+    }
+}
@@ -2,16 +2,29 @@
 //! It is used in the integration tests to verify the correctness of the library.
 
 use std::{
-    fmt::{Display, Write},
+    collections::VecDeque,
+    fmt::{self, Debug, Display, Write},
+    hash::{Hash, Hasher},
     io,
+    ops::Range,
+    path::Path,
+    sync::Arc,
+    time::Duration,
 };
 
 use unicode_width::UnicodeWidthStr;
 
+mod snapshot;
+
 use crate::{
-    backend::{Backend, ClearType, WindowSize},
+    backend::{
+        ansi::{read_color_reply, QueryDefaultColor},
+        Backend, ClearType, CursorStyle, DefaultColor, WindowSize,
+    },
     buffer::{Buffer, Cell},
+    event::{Event, KeyCode, KeyEvent, KeyModifiers},
     layout::{Rect, Size},
+    style::Color,
 };
 
 /// A [`Backend`] implementation used for integration testing that that renders to an in memory
@@ -39,7 +52,112 @@ pub struct TestBackend {
     buffer: Buffer,
     height: u16,
     cursor: bool,
+    cursor_style: CursorStyle,
+    raw_mode: bool,
+    alternate_screen: bool,
+    mouse_capture: bool,
+    title: Option<String>,
+    clipboard: Option<String>,
+    bells: usize,
+    default_foreground: Option<Color>,
+    default_background: Option<Color>,
     pos: (u16, u16),
+    /// The input returned by [`Backend::next_event`] and read by queries.
+    input: VecDeque<TestReply>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    responder: Option<Responder>,
+}
+
+/// Input sent by the terminal simulated by a [`TestBackend`] in reply to a query, as returned by
+/// the responder set with [`TestBackend::set_responder`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TestReply {
+    /// Text sent by the terminal, e.g. `"\x1b]11;rgb:0000/0000/0000\x07"`.
+    ///
+    /// The text is reported as key events, the way the input parsers of the other backends report
+    /// a reply they don't know about: `ESC` followed by a character as that character with `Alt`,
+    /// and other control characters, such as `BEL`, as the matching letter with `Ctrl`.
+    Text(String),
+    /// An event received while waiting for the reply, e.g. a key pressed by the user.
+    Event(Event),
+    /// A delay before the following input. It counts towards the timeout of the query, without
+    /// actually sleeping.
+    Delay(Duration),
+}
+
+/// The responder set with [`TestBackend::set_responder`].
+///
+/// Backends sharing the same responder are equal.
+#[derive(Clone)]
+struct Responder(Arc<RespondFn>);
+
+type RespondFn = dyn Fn(&str) -> Vec<TestReply> + Send + Sync;
+
+impl Responder {
+    fn as_ptr(&self) -> *const u8 {
+        Arc::as_ptr(&self.0).cast()
+    }
+}
+
+impl Debug for Responder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Responder")
+    }
+}
+
+impl PartialEq for Responder {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ptr() == other.as_ptr()
+    }
+}
+
+impl Eq for Responder {}
+
+impl Hash for Responder {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ptr().hash(state);
+    }
+}
+
+/// Asserts that `buffer` matches the snapshot file at `path`, including the colors, modifiers and
+/// hyperlinks of each cell.
+///
+/// Relative paths are resolved from the directory of the crate being tested, so snapshots are
+/// usually stored under `tests/`. A snapshot is a readable text file listing the content of each
+/// row, then a map giving the style of each cell and the definition of each style:
+///
+/// ```text
+/// area: x=0 y=0 width=8 height=2
+/// content:
+/// "Hello   "
+/// "World!  "
+/// styles:
+/// "aaaaa   "
+/// "bbbbbb  "
+/// a: fg=Red modifier=BOLD
+/// b: fg=#FF8000 bg=236 link=https://ratatui.rs
+/// ```
+///
+/// When the `RATATUI_UPDATE_SNAPSHOTS` environment variable is set, missing or outdated snapshots
+/// are written instead of failing the assertion. Otherwise the panic message shows the rows that
+/// differ, rendered with their colors unless `NO_COLOR` is set, and lists the differing cells.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ratatui::{backend::assert_buffer_snapshot, prelude::*, widgets::*};
+///
+/// let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 3));
+/// Block::default()
+///     .borders(Borders::ALL)
+///     .border_style(Style::new().red())
+///     .render(buffer.area, &mut buffer);
+/// assert_buffer_snapshot(&buffer, "tests/snapshots/block.snap");
+/// ```
+#[track_caller]
+pub fn assert_buffer_snapshot<P: AsRef<Path>>(buffer: &Buffer, path: P) {
+    snapshot::assert_snapshot(buffer, path.as_ref());
 }
 
 /// Returns a string representation of the given buffer for debugging purpose.
@@ -49,8 +167,8 @@ pub struct TestBackend {
 /// If a cell is hidden by a multi-width symbol, it is added to the overwritten vector and
 /// displayed at the end of the line.
 fn buffer_view(buffer: &Buffer) -> String {
-    let mut view = String::with_capacity(buffer.content.len() + buffer.area.height as usize * 3);
-    for cells in buffer.content.chunks(buffer.area.width as usize) {
+    let mut view = String::with_capacity(buffer.content().len() + buffer.area.height as usize * 3);
+    for cells in buffer.content().chunks(buffer.area.width as usize) {
         let mut overwritten = vec![];
         let mut skip: usize = 0;
         view.push('"');
@@ -79,7 +197,18 @@ impl TestBackend {
             height,
             buffer: Buffer::empty(Rect::new(0, 0, width, height)),
             cursor: false,
+            cursor_style: CursorStyle::DefaultUserShape,
+            raw_mode: false,
+            alternate_screen: false,
+            mouse_capture: false,
+            title: None,
+            clipboard: None,
+            bells: 0,
+            default_foreground: None,
+            default_background: None,
             pos: (0, 0),
+            input: VecDeque::new(),
+            responder: None,
         }
     }
 
@@ -88,6 +217,236 @@ impl TestBackend {
         &self.buffer
     }
 
+    /// Returns the target of the hyperlink drawn at the given position, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{backend::TestBackend, prelude::*};
+    ///
+    /// let mut terminal = Terminal::new(TestBackend::new(10, 1))?;
+    /// terminal.draw(|frame| {
+    ///     let span = Span::raw("ratatui").hyperlink("https://ratatui.rs");
+    ///     frame.buffer_mut().set_span(0, 0, &span, 10);
+    /// })?;
+    /// assert_eq!(terminal.backend().hyperlink(0, 0), Some("https://ratatui.rs"));
+    /// assert_eq!(terminal.backend().hyperlink(7, 0), None);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn hyperlink(&self, x: u16, y: u16) -> Option<&str> {
+        self.buffer.get(x, y).hyperlink()
+    }
+
+    /// Returns the cursor style last set with [`Backend::set_cursor_style`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{
+    ///     backend::{CursorStyle, TestBackend},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let mut terminal = Terminal::new(TestBackend::new(10, 1))?;
+    /// terminal.draw(|frame| {
+    ///     frame.set_cursor(0, 0);
+    ///     frame.set_cursor_style(CursorStyle::SteadyBar);
+    /// })?;
+    /// assert_eq!(terminal.backend().cursor_style(), CursorStyle::SteadyBar);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn cursor_style(&self) -> CursorStyle {
+        self.cursor_style
+    }
+
+    /// Returns whether raw mode is enabled, see [`Backend::enable_raw_mode`].
+    pub fn raw_mode_enabled(&self) -> bool {
+        self.raw_mode
+    }
+
+    /// Returns whether the alternate screen is active, see [`Backend::enter_alternate_screen`].
+    pub fn alternate_screen_active(&self) -> bool {
+        self.alternate_screen
+    }
+
+    /// Returns whether mouse capture is enabled, see [`Backend::enable_mouse_capture`].
+    pub fn mouse_capture_enabled(&self) -> bool {
+        self.mouse_capture
+    }
+
+    /// Returns the window title last set with [`Backend::set_title`], if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{backend::TestBackend, prelude::*};
+    ///
+    /// let mut terminal = Terminal::new(TestBackend::new(10, 1))?;
+    /// terminal.set_title("ratatui")?;
+    /// assert_eq!(terminal.backend().title(), Some("ratatui"));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the content last copied to the clipboard with [`Backend::set_clipboard`], if any.
+    pub fn clipboard(&self) -> Option<&str> {
+        self.clipboard.as_deref()
+    }
+
+    /// Returns the number of times the bell was rung with [`Backend::bell`].
+    pub fn bell_count(&self) -> usize {
+        self.bells
+    }
+
+    /// Sets the color the backend replies with when queried with
+    /// [`Backend::query_default_color`], unless a responder is set with
+    /// [`TestBackend::set_responder`].
+    ///
+    /// Without a color, the terminal doesn't answer, so the query returns `Ok(None)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use ratatui::{
+    ///     backend::{DefaultColor, TestBackend},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let mut backend = TestBackend::new(10, 1);
+    /// backend.set_default_color(DefaultColor::Background, Some(Color::Rgb(30, 30, 46)));
+    /// let mut terminal = Terminal::new(backend)?;
+    /// let timeout = Duration::from_millis(100);
+    /// assert_eq!(
+    ///     terminal.query_default_color(DefaultColor::Background, timeout)?,
+    ///     Some(Color::Rgb(30, 30, 46))
+    /// );
+    /// assert_eq!(terminal.query_default_color(DefaultColor::Foreground, timeout)?, None);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn set_default_color(&mut self, default_color: DefaultColor, color: Option<Color>) {
+        match default_color {
+            DefaultColor::Foreground => self.default_foreground = color,
+            DefaultColor::Background => self.default_background = color,
+        }
+    }
+
+    /// Sets the function simulating the replies of the terminal to queries, such as
+    /// [`Backend::query_default_color`].
+    ///
+    /// The responder is called with the escape sequence of each query, and returns the input the
+    /// terminal sends afterwards. The input is queued after the events already queued, and the
+    /// query reads and parses the reply from the queue like the other backends do. The events
+    /// received before the reply are returned by [`Backend::next_event`] afterwards, as is the
+    /// input left after the reply, e.g. a reply that didn't arrive before the timeout of the
+    /// query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use ratatui::{
+    ///     backend::{DefaultColor, TestBackend, TestReply},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let mut backend = TestBackend::new(10, 1);
+    /// backend.set_responder(|query| match query {
+    ///     // the terminal answers late to background color queries
+    ///     "\x1b]11;?\x1b\\" => vec![
+    ///         TestReply::Delay(Duration::from_millis(200)),
+    ///         TestReply::Text("\x1b]11;rgb:1e1e/1e1e/2e2e\x07".into()),
+    ///     ],
+    ///     _ => vec![TestReply::Text("\x1b]10;rgb:cdcd/d6d6/f4f4\x07".into())],
+    /// });
+    /// let mut terminal = Terminal::new(backend)?;
+    /// let timeout = Duration::from_millis(100);
+    /// assert_eq!(
+    ///     terminal.query_default_color(DefaultColor::Foreground, timeout)?,
+    ///     Some(Color::Rgb(205, 214, 244))
+    /// );
+    /// assert_eq!(terminal.query_default_color(DefaultColor::Background, timeout)?, None);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn set_responder<F>(&mut self, responder: F)
+    where
+        F: Fn(&str) -> Vec<TestReply> + Send + Sync + 'static,
+    {
+        self.responder = Some(Responder(Arc::new(responder)));
+    }
+
+    /// Returns the reply to a query of the default `color` set with
+    /// [`TestBackend::set_default_color`].
+    fn default_color_reply(&self, color: DefaultColor) -> Vec<TestReply> {
+        let (code, color) = match color {
+            DefaultColor::Foreground => (10, self.default_foreground),
+            DefaultColor::Background => (11, self.default_background),
+        };
+        color
+            .and_then(Color::to_rgb)
+            .map(|(r, g, b)| {
+                TestReply::Text(format!(
+                    "\x1b]{code};rgb:{r:02x}{r:02x}/{g:02x}{g:02x}/{b:02x}{b:02x}\x1b\\"
+                ))
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the next queued event, unless the delays queued before it add up to more than
+    /// `timeout`. The delays that passed are subtracted from `timeout`.
+    fn next_queued_event(&mut self, timeout: &mut Duration) -> Option<Event> {
+        while let Some(input) = self.input.pop_front() {
+            match input {
+                TestReply::Text(text) => {
+                    for event in key_events(&text).into_iter().rev() {
+                        self.input.push_front(TestReply::Event(event));
+                    }
+                }
+                TestReply::Event(event) => return Some(event),
+                TestReply::Delay(delay) if delay > *timeout => {
+                    self.input.push_front(TestReply::Delay(delay - *timeout));
+                    *timeout = Duration::ZERO;
+                    return None;
+                }
+                TestReply::Delay(delay) => *timeout -= delay,
+            }
+        }
+        None
+    }
+
+    /// Queues an input event to be returned by [`Backend::next_event`].
+    ///
+    /// Once the queue is empty, [`Backend::next_event`] returns `Ok(None)` when given a timeout
+    /// and an error of kind [`io::ErrorKind::UnexpectedEof`] otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use ratatui::{
+    ///     backend::TestBackend,
+    ///     event::{Event, KeyCode},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let mut backend = TestBackend::new(10, 1);
+    /// backend.push_event(Event::Key(KeyCode::Enter.into()));
+    /// let mut terminal = Terminal::new(backend)?;
+    /// assert!(terminal.poll_event(Duration::ZERO)?);
+    /// assert_eq!(terminal.read_event()?, Event::Key(KeyCode::Enter.into()));
+    /// assert!(!terminal.poll_event(Duration::ZERO)?);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn push_event(&mut self, event: Event) {
+        self.input.push_back(TestReply::Event(event));
+    }
+
     /// Resizes the TestBackend to the specified width and height.
     pub fn resize(&mut self, width: u16, height: u16) {
         self.buffer.resize(Rect::new(0, 0, width, height));
@@ -95,6 +454,15 @@ impl TestBackend {
         self.height = height;
     }
 
+    /// Asserts that the TestBackend's buffer matches the snapshot file at `path`, including the
+    /// colors, modifiers and hyperlinks of each cell.
+    ///
+    /// See [`assert_buffer_snapshot`] for details.
+    #[track_caller]
+    pub fn assert_snapshot<P: AsRef<Path>>(&self, path: P) {
+        snapshot::assert_snapshot(&self.buffer, path.as_ref());
+    }
+
     /// Asserts that the TestBackend's buffer is equal to the expected buffer.
     /// If the buffers are not equal, a panic occurs with a detailed error message
     /// showing the differences between the expected and actual buffers.
@@ -144,6 +512,8 @@ impl Display for TestBackend {
 }
 
 impl Backend for TestBackend {
+    type Error = io::Error;
+
     fn draw<'a, I>(&mut self, content: I) -> Result<(), io::Error>
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
@@ -165,6 +535,80 @@ impl Backend for TestBackend {
         Ok(())
     }
 
+    fn set_cursor_style(&mut self, style: CursorStyle) -> io::Result<()> {
+        self.cursor_style = style;
+        Ok(())
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        self.raw_mode = true;
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        self.raw_mode = false;
+        Ok(())
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        self.alternate_screen = true;
+        Ok(())
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        self.alternate_screen = false;
+        Ok(())
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        self.mouse_capture = true;
+        Ok(())
+    }
+
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        self.mouse_capture = false;
+        Ok(())
+    }
+
+    fn set_title(&mut self, title: &str) -> io::Result<()> {
+        self.title = Some(title.to_string());
+        Ok(())
+    }
+
+    fn set_clipboard(&mut self, content: &str) -> io::Result<()> {
+        self.clipboard = Some(content.to_string());
+        Ok(())
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        self.bells += 1;
+        Ok(())
+    }
+
+    fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> io::Result<Option<Color>> {
+        let replies = match &self.responder {
+            Some(Responder(responder)) => responder(&QueryDefaultColor(color).to_string()),
+            None => self.default_color_reply(color),
+        };
+        self.input.extend(replies);
+        // the delays of the replies are simulated, so the time left is tracked here
+        let mut remaining = timeout;
+        let mut skipped = VecDeque::new();
+        let color = read_color_reply(
+            |_| Ok::<_, io::Error>(self.next_queued_event(&mut remaining)),
+            timeout,
+            &mut skipped,
+        )?;
+        for event in skipped.into_iter().rev() {
+            self.input.push_front(TestReply::Event(event));
+        }
+        Ok(color)
+    }
+
     fn get_cursor(&mut self) -> Result<(u16, u16), io::Error> {
         Ok(self.pos)
     }
@@ -184,26 +628,47 @@ impl Backend for TestBackend {
             ClearType::All => self.clear()?,
             ClearType::AfterCursor => {
                 let index = self.buffer.index_of(self.pos.0, self.pos.1) + 1;
-                self.buffer.content[index..].fill(Cell::default());
+                self.buffer.content_mut()[index..].fill(Cell::default());
             }
             ClearType::BeforeCursor => {
                 let index = self.buffer.index_of(self.pos.0, self.pos.1);
-                self.buffer.content[..index].fill(Cell::default());
+                self.buffer.content_mut()[..index].fill(Cell::default());
             }
             ClearType::CurrentLine => {
                 let line_start_index = self.buffer.index_of(0, self.pos.1);
                 let line_end_index = self.buffer.index_of(self.width - 1, self.pos.1);
-                self.buffer.content[line_start_index..=line_end_index].fill(Cell::default());
+                self.buffer.content_mut()[line_start_index..=line_end_index].fill(Cell::default());
             }
             ClearType::UntilNewLine => {
                 let index = self.buffer.index_of(self.pos.0, self.pos.1);
                 let line_end_index = self.buffer.index_of(self.width - 1, self.pos.1);
-                self.buffer.content[index..=line_end_index].fill(Cell::default());
+                self.buffer.content_mut()[index..=line_end_index].fill(Cell::default());
             }
         }
         Ok(())
     }
 
+    fn scroll_region_up(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        let start = self.buffer.index_of(0, region.start);
+        let end = start + region.len() * self.width as usize;
+        let lines = &mut self.buffer.content_mut()[start..end];
+        let n = (line_count as usize * self.width as usize).min(lines.len());
+        lines.rotate_left(n);
+        let len = lines.len();
+        lines[len - n..].fill(Cell::default());
+        Ok(())
+    }
+
+    fn scroll_region_down(&mut self, region: Range<u16>, line_count: u16) -> io::Result<()> {
+        let start = self.buffer.index_of(0, region.start);
+        let end = start + region.len() * self.width as usize;
+        let lines = &mut self.buffer.content_mut()[start..end];
+        let n = (line_count as usize * self.width as usize).min(lines.len());
+        lines.rotate_right(n);
+        lines[..n].fill(Cell::default());
+        Ok(())
+    }
+
     /// Inserts n line breaks at the current cursor position.
     ///
     /// After the insertion, the cursor x position will be incremented by 1 (unless it's already
@@ -234,7 +699,7 @@ impl Backend for TestBackend {
             self.set_cursor(0, rotate_by)?;
             self.clear_region(ClearType::BeforeCursor)?;
             self.buffer
-                .content
+                .content_mut()
                 .rotate_left((self.width * rotate_by).into());
         }
 
@@ -260,11 +725,44 @@ impl Backend for TestBackend {
         })
     }
 
+    fn next_event(&mut self, timeout: Option<Duration>) -> io::Result<Option<Event>> {
+        match self.next_queued_event(&mut timeout.unwrap_or(Duration::MAX)) {
+            Some(event) => Ok(Some(event)),
+            None if timeout.is_some() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no more events queued",
+            )),
+        }
+    }
+
     fn flush(&mut self) -> Result<(), io::Error> {
         Ok(())
     }
 }
 
+/// Returns the key events the input parsers of the backends report for `text` sent by the terminal.
+fn key_events(text: &str) -> Vec<Event> {
+    let mut events = vec![];
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' => match chars.next() {
+                Some(c) => KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT),
+                None => KeyCode::Esc.into(),
+            },
+            c if c < ' ' => KeyEvent::new(
+                KeyCode::Char((c as u8 + b'`') as char),
+                KeyModifiers::CONTROL,
+            ),
+            c if c.is_uppercase() => KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT),
+            c => KeyCode::Char(c).into(),
+        };
+        events.push(Event::Key(key));
+    }
+    events
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,7 +776,18 @@ mod tests {
                 height: 2,
                 buffer: Buffer::with_lines(vec!["          "; 2]),
                 cursor: false,
+                cursor_style: CursorStyle::DefaultUserShape,
+                raw_mode: false,
+                alternate_screen: false,
+                mouse_capture: false,
+                title: None,
+                clipboard: None,
+                bells: 0,
+                default_foreground: None,
+                default_background: None,
                 pos: (0, 0),
+                input: VecDeque::new(),
+                responder: None,
             }
         );
     }
@@ -346,6 +855,17 @@ mod tests {
         backend.assert_buffer(&Buffer::with_lines(vec!["a         "; 2]));
     }
 
+    #[test]
+    fn draw_hyperlink() {
+        let mut backend = TestBackend::new(2, 1);
+        let mut cell = Cell::default();
+        cell.set_symbol("a")
+            .set_hyperlink(Some("https://ratatui.rs"));
+        backend.draw([(1, 0, &cell)].into_iter()).unwrap();
+        assert_eq!(backend.hyperlink(0, 0), None);
+        assert_eq!(backend.hyperlink(1, 0), Some("https://ratatui.rs"));
+    }
+
     #[test]
     fn hide_cursor() {
         let mut backend = TestBackend::new(10, 2);
@@ -360,6 +880,197 @@ mod tests {
         assert!(backend.cursor);
     }
 
+    #[test]
+    fn terminal_modes() {
+        let mut backend = TestBackend::new(10, 2);
+        backend.enable_raw_mode().unwrap();
+        backend.enter_alternate_screen().unwrap();
+        backend.enable_mouse_capture().unwrap();
+        assert!(backend.raw_mode_enabled());
+        assert!(backend.alternate_screen_active());
+        assert!(backend.mouse_capture_enabled());
+        backend.disable_raw_mode().unwrap();
+        backend.leave_alternate_screen().unwrap();
+        backend.disable_mouse_capture().unwrap();
+        assert!(!backend.raw_mode_enabled());
+        assert!(!backend.alternate_screen_active());
+        assert!(!backend.mouse_capture_enabled());
+    }
+
+    #[test]
+    fn title_clipboard_and_bell() {
+        let mut backend = TestBackend::new(10, 2);
+        assert_eq!(backend.title(), None);
+        assert_eq!(backend.clipboard(), None);
+        assert_eq!(backend.bell_count(), 0);
+        backend.set_title("title").unwrap();
+        backend.set_clipboard("copied").unwrap();
+        backend.bell().unwrap();
+        backend.bell().unwrap();
+        assert_eq!(backend.title(), Some("title"));
+        assert_eq!(backend.clipboard(), Some("copied"));
+        assert_eq!(backend.bell_count(), 2);
+    }
+
+    #[test]
+    fn query_default_color() {
+        let mut backend = TestBackend::new(10, 2);
+        let timeout = Duration::ZERO;
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Foreground, timeout)
+                .unwrap(),
+            None
+        );
+        backend.set_default_color(DefaultColor::Foreground, Some(Color::Rgb(255, 255, 255)));
+        backend.set_default_color(DefaultColor::Background, Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Foreground, timeout)
+                .unwrap(),
+            Some(Color::Rgb(255, 255, 255))
+        );
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, timeout)
+                .unwrap(),
+            Some(Color::Rgb(0, 0, 0))
+        );
+    }
+
+    /// Returns a backend that replies to background color queries with `replies`.
+    fn responding(replies: Vec<TestReply>) -> TestBackend {
+        let mut backend = TestBackend::new(10, 2);
+        backend.set_responder(move |query| {
+            assert_eq!(query, "\x1b]11;?\x1b\\");
+            replies.clone()
+        });
+        backend
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyCode::Char(c).into())
+    }
+
+    #[test]
+    fn query_default_color_with_responder() {
+        let reply = TestReply::Text("\x1b]11;rgb:0000/8080/ffff\x07".into());
+        let mut backend = responding(vec![reply]);
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::ZERO)
+                .unwrap(),
+            Some(Color::Rgb(0, 128, 255))
+        );
+        assert_eq!(backend.next_event(Some(Duration::ZERO)).unwrap(), None);
+    }
+
+    #[test]
+    fn query_default_color_with_slow_reply() {
+        let replies = vec![
+            TestReply::Delay(Duration::from_millis(60)),
+            TestReply::Text("\x1b]11;".into()),
+            TestReply::Delay(Duration::from_millis(60)),
+            TestReply::Text("rgb:ff/ff/ff\x07".into()),
+        ];
+        let mut backend = responding(replies.clone());
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::from_millis(150))
+                .unwrap(),
+            Some(Color::Rgb(255, 255, 255))
+        );
+
+        // the end of the reply arrives after the query timed out, so it is returned as key events
+        let mut backend = responding(replies);
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::from_millis(100))
+                .unwrap(),
+            None
+        );
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('r')));
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('g')));
+    }
+
+    #[test]
+    fn query_default_color_without_reply() {
+        let mut backend = responding(vec![]);
+        backend.push_event(key('a'));
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::from_millis(100))
+                .unwrap(),
+            None
+        );
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('a')));
+        assert_eq!(backend.next_event(Some(Duration::ZERO)).unwrap(), None);
+    }
+
+    #[test]
+    fn query_default_color_with_malformed_reply() {
+        let replies = vec![
+            TestReply::Text("\x1b]11;rgb:zzzz/0000/0000\x1b\\".into()),
+            TestReply::Event(key('a')),
+        ];
+        let mut backend = responding(replies);
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::ZERO)
+                .unwrap(),
+            None
+        );
+        // the malformed reply is consumed up to its terminator
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('a')));
+    }
+
+    #[test]
+    fn query_default_color_with_interleaved_events() {
+        let replies = vec![
+            TestReply::Event(Event::FocusLost),
+            TestReply::Text("\x1b]11;rgb:0000/0000/0000\x07".into()),
+            TestReply::Event(key('b')),
+        ];
+        let mut backend = responding(replies);
+        backend.push_event(key('a'));
+        assert_eq!(
+            backend
+                .query_default_color(DefaultColor::Background, Duration::ZERO)
+                .unwrap(),
+            Some(Color::Rgb(0, 0, 0))
+        );
+        // the events received before and after the reply keep their order
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('a')));
+        assert_eq!(backend.next_event(None).unwrap(), Some(Event::FocusLost));
+        assert_eq!(backend.next_event(None).unwrap(), Some(key('b')));
+        assert_eq!(backend.next_event(Some(Duration::ZERO)).unwrap(), None);
+    }
+
+    #[test]
+    fn reply_text_as_key_events() {
+        let ctrl = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        let alt = KeyEvent::new(KeyCode::Char('\\'), KeyModifiers::ALT);
+        let shift = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert_eq!(
+            key_events("a\x07\x1b\\A\x1b"),
+            [
+                key('a'),
+                Event::Key(ctrl),
+                Event::Key(alt),
+                Event::Key(shift),
+                Event::Key(KeyCode::Esc.into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_cursor_style() {
+        let mut backend = TestBackend::new(10, 2);
+        assert_eq!(backend.cursor_style(), CursorStyle::DefaultUserShape);
+        backend.set_cursor_style(CursorStyle::BlinkingBar).unwrap();
+        assert_eq!(backend.cursor_style(), CursorStyle::BlinkingBar);
+    }
+
     #[test]
     fn get_cursor() {
         let mut backend = TestBackend::new(10, 2);
@@ -498,6 +1209,24 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn scroll_region_up() {
+        let mut backend = TestBackend::new(1, 5);
+        backend.buffer = Buffer::with_lines(vec!["a", "b", "c", "d", "e"]);
+        backend.scroll_region_up(1..4, 1).unwrap();
+        backend.assert_buffer(&Buffer::with_lines(vec!["a", "c", "d", " ", "e"]));
+        backend.scroll_region_up(0..5, 10).unwrap();
+        backend.assert_buffer(&Buffer::with_lines(vec![" "; 5]));
+    }
+
+    #[test]
+    fn scroll_region_down() {
+        let mut backend = TestBackend::new(1, 5);
+        backend.buffer = Buffer::with_lines(vec!["a", "b", "c", "d", "e"]);
+        backend.scroll_region_down(1..4, 2).unwrap();
+        backend.assert_buffer(&Buffer::with_lines(vec!["a", " ", " ", "b", "e"]));
+    }
+
     #[test]
     fn append_lines_not_at_last_line() {
         let mut backend = TestBackend::new(10, 5);
@@ -681,4 +1410,21 @@ mod tests {
         let mut backend = TestBackend::new(10, 2);
         backend.flush().unwrap();
     }
+
+    #[test]
+    fn next_event() {
+        let mut backend = TestBackend::new(10, 2);
+        backend.push_event(Event::FocusGained);
+        backend.push_event(Event::Resize(20, 4));
+        assert_eq!(backend.next_event(None).unwrap(), Some(Event::FocusGained));
+        assert_eq!(
+            backend.next_event(Some(Duration::ZERO)).unwrap(),
+            Some(Event::Resize(20, 4))
+        );
+        assert_eq!(backend.next_event(Some(Duration::ZERO)).unwrap(), None);
+        assert_eq!(
+            backend.next_event(None).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
 }
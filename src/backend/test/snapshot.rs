@@ -0,0 +1,519 @@
+//! Golden file snapshots of buffers, including their styles.
+//!
+//! A snapshot is a text file with the content of each row of the buffer, followed by a map of the
+//! style of each cell and the definition of each style used in the map:
+//!
+//! ```text
+//! area: x=0 y=0 width=8 height=2
+//! content:
+//! "Hello   "
+//! "World!  "
+//! styles:
+//! "aaaaa   "
+//! "bbbbbb  "
+//! a: fg=Red modifier=BOLD
+//! b: fg=#FF8000 bg=236 link=https://ratatui.rs
+//! ```
+//!
+//! Cells with the default style are shown as a space in the style map.
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    backend::ansi::{Bg, Fg, ModifierDiff, ResetAttributes},
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+/// The environment variable that makes failing snapshot assertions update the snapshot files.
+const UPDATE_VAR: &str = "RATATUI_UPDATE_SNAPSHOTS";
+
+/// Maximum number of differing cells listed when a snapshot doesn't match.
+const MAX_LISTED_DIFFERENCES: usize = 20;
+
+/// The style of a cell, as stored in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CellStyle {
+    fg: Color,
+    bg: Color,
+    #[cfg(feature = "underline-color")]
+    underline_color: Color,
+    modifier: Modifier,
+    hyperlink: Option<String>,
+}
+
+impl CellStyle {
+    fn of(cell: &Cell) -> CellStyle {
+        CellStyle {
+            fg: cell.fg,
+            bg: cell.bg,
+            #[cfg(feature = "underline-color")]
+            underline_color: cell.underline_color,
+            modifier: cell.modifier,
+            hyperlink: cell.hyperlink().map(String::from),
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == CellStyle::of(&Cell::default())
+    }
+
+    fn apply(&self, cell: &mut Cell) {
+        cell.fg = self.fg;
+        cell.bg = self.bg;
+        #[cfg(feature = "underline-color")]
+        {
+            cell.underline_color = self.underline_color;
+        }
+        cell.modifier = self.modifier;
+        cell.set_hyperlink(self.hyperlink.as_deref());
+    }
+
+    /// Parses the attributes of a style definition, e.g. `fg=Red modifier=BOLD | ITALIC`.
+    fn parse(definition: &str) -> Result<CellStyle, String> {
+        let mut style = CellStyle::of(&Cell::default());
+        // the hyperlink comes last and may contain anything but a line break
+        let (attributes, hyperlink) = match definition.split_once("link=") {
+            Some((attributes, hyperlink)) => (attributes, Some(hyperlink.to_string())),
+            None => (definition, None),
+        };
+        style.hyperlink = hyperlink;
+        let (attributes, modifier) = match attributes.split_once("modifier=") {
+            Some((attributes, modifier)) => (attributes, Some(modifier)),
+            None => (attributes, None),
+        };
+        if let Some(modifier) = modifier {
+            for name in modifier.split('|').map(str::trim) {
+                style.modifier |= Modifier::from_name(name)
+                    .ok_or_else(|| format!("unknown modifier {name:?}"))?;
+            }
+        }
+        for attribute in attributes.split_whitespace() {
+            let (key, value) = attribute
+                .split_once('=')
+                .ok_or_else(|| format!("invalid style attribute {attribute:?}"))?;
+            let color = value
+                .parse::<Color>()
+                .map_err(|_| format!("invalid color {value:?}"))?;
+            match key {
+                "fg" => style.fg = color,
+                "bg" => style.bg = color,
+                #[cfg(feature = "underline-color")]
+                "underline" => style.underline_color = color,
+                #[cfg(not(feature = "underline-color"))]
+                "underline" => {}
+                _ => return Err(format!("unknown style attribute {key:?}")),
+            }
+        }
+        Ok(style)
+    }
+
+    /// Writes the non-default attributes of the style.
+    fn describe(&self) -> String {
+        let mut attributes = vec![];
+        if self.fg != Color::Reset {
+            attributes.push(format!("fg={}", self.fg));
+        }
+        if self.bg != Color::Reset {
+            attributes.push(format!("bg={}", self.bg));
+        }
+        #[cfg(feature = "underline-color")]
+        if self.underline_color != Color::Reset {
+            attributes.push(format!("underline={}", self.underline_color));
+        }
+        if !self.modifier.is_empty() {
+            attributes.push(format!("modifier={:?}", self.modifier));
+        }
+        if let Some(hyperlink) = &self.hyperlink {
+            attributes.push(format!("link={hyperlink}"));
+        }
+        attributes.join(" ")
+    }
+}
+
+/// Returns the key used for the `index`th style in the style map.
+fn style_key(index: usize) -> char {
+    const KEYS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    KEYS.get(index).map_or_else(
+        || char::from_u32(0x100 + (index - KEYS.len()) as u32).unwrap_or('?'),
+        |key| char::from(*key),
+    )
+}
+
+/// Serializes a buffer to the snapshot format.
+pub(super) fn serialize(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut output = format!(
+        "area: x={} y={} width={} height={}\ncontent:\n",
+        area.x, area.y, area.width, area.height
+    );
+    let rows = || buffer.content().chunks(area.width.max(1) as usize);
+    for row in rows() {
+        output.push('"');
+        let mut skip = 0;
+        for cell in row {
+            if skip == 0 {
+                output.push_str(cell.symbol());
+            }
+            skip = std::cmp::max(skip, cell.symbol().width()).saturating_sub(1);
+        }
+        output.push_str("\"\n");
+    }
+
+    output.push_str("styles:\n");
+    let mut styles: Vec<CellStyle> = vec![];
+    for row in rows() {
+        output.push('"');
+        for cell in row {
+            let style = CellStyle::of(cell);
+            if style.is_default() {
+                output.push(' ');
+                continue;
+            }
+            let index = styles.iter().position(|s| *s == style).unwrap_or_else(|| {
+                styles.push(style);
+                styles.len() - 1
+            });
+            output.push(style_key(index));
+        }
+        output.push_str("\"\n");
+    }
+    for (index, style) in styles.iter().enumerate() {
+        let _ = writeln!(output, "{}: {}", style_key(index), style.describe());
+    }
+    output
+}
+
+/// Parses a snapshot back into a buffer.
+pub(super) fn deserialize(snapshot: &str) -> Result<Buffer, String> {
+    let mut lines = snapshot.lines();
+    let area = lines
+        .next()
+        .and_then(|line| line.strip_prefix("area: "))
+        .ok_or("missing area")?;
+    let mut values = area.split_whitespace().map(|value| {
+        value
+            .split_once('=')
+            .and_then(|(_, value)| value.parse::<u16>().ok())
+            .ok_or_else(|| format!("invalid area {area:?}"))
+    });
+    let mut next_value = || values.next().unwrap_or(Err("incomplete area".to_string()));
+    let area = Rect::new(next_value()?, next_value()?, next_value()?, next_value()?);
+
+    let mut quoted_rows = |section: &str| -> Result<Vec<&str>, String> {
+        if lines.next() != Some(section) {
+            return Err(format!("missing {section:?} section"));
+        }
+        (0..area.height)
+            .map(|_| {
+                lines
+                    .next()
+                    .and_then(|line| line.strip_prefix('"'))
+                    .and_then(|line| line.strip_suffix('"'))
+                    .ok_or_else(|| format!("missing row in {section:?} section"))
+            })
+            .collect()
+    };
+    let content = quoted_rows("content:")?;
+    let style_map = quoted_rows("styles:")?;
+
+    let mut styles = vec![];
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (key, definition) = line
+            .split_once(": ")
+            .ok_or_else(|| format!("invalid style definition {line:?}"))?;
+        let key = key
+            .chars()
+            .next()
+            .ok_or_else(|| format!("invalid style definition {line:?}"))?;
+        styles.push((key, CellStyle::parse(definition)?));
+    }
+
+    let mut buffer = Buffer::empty(area);
+    for (y, (row, style_row)) in content.iter().zip(style_map).enumerate() {
+        let y = area.y + y as u16;
+        buffer.set_stringn(area.x, y, row, area.width as usize, Default::default());
+        for (x, key) in style_row.chars().take(area.width as usize).enumerate() {
+            if key == ' ' {
+                continue;
+            }
+            let (_, style) = styles
+                .iter()
+                .find(|(k, _)| *k == key)
+                .ok_or_else(|| format!("undefined style {key:?}"))?;
+            style.apply(buffer.get_mut(area.x + x as u16, y));
+        }
+    }
+    Ok(buffer)
+}
+
+/// Resolves paths relative to the directory of the crate being tested.
+fn resolve(path: &Path) -> PathBuf {
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) if path.is_relative() => Path::new(&dir).join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Compares `buffer` to the snapshot at `path`, or writes the snapshot if [`UPDATE_VAR`] is set.
+#[track_caller]
+pub(super) fn assert_snapshot(buffer: &Buffer, path: &Path) {
+    let update = env::var_os(UPDATE_VAR).is_some_and(|value| !value.is_empty());
+    if let Err(message) = check_snapshot(buffer, &resolve(path), update) {
+        panic!("{message}");
+    }
+}
+
+/// Compares `buffer` to the snapshot at `path`, or writes the snapshot when `update` is true.
+fn check_snapshot(buffer: &Buffer, path: &Path, update: bool) -> Result<(), String> {
+    let actual = serialize(buffer);
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+        }
+        if fs::read_to_string(path).ok().as_deref() != Some(actual.as_str()) {
+            fs::write(path, actual)
+                .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        }
+        return Ok(());
+    }
+    let expected = fs::read_to_string(path).map_err(|error| {
+        format!(
+            "failed to read snapshot {}: {error}\nrun the test with {UPDATE_VAR}=1 to create it",
+            path.display()
+        )
+    })?;
+    if expected.replace("\r\n", "\n") == actual {
+        return Ok(());
+    }
+    let diff = match deserialize(&expected) {
+        Ok(expected) => diff(&expected, buffer, env::var_os("NO_COLOR").is_none()),
+        Err(error) => format!("the snapshot is invalid ({error}), got:\n{actual}"),
+    };
+    Err(format!(
+        "snapshot {} does not match\n{diff}\nrun the test with {UPDATE_VAR}=1 to update it",
+        path.display()
+    ))
+}
+
+/// Renders a row of cells, with their styles when `color` is true.
+fn render_row(cells: &[Cell], color: bool) -> String {
+    let mut row = String::new();
+    let mut skip = 0;
+    for cell in cells {
+        if skip == 0 {
+            if color {
+                let modifier = ModifierDiff {
+                    from: Modifier::empty(),
+                    to: cell.modifier,
+                };
+                let _ = write!(
+                    row,
+                    "{ResetAttributes}{}{}{modifier}",
+                    Fg(cell.fg),
+                    Bg(cell.bg)
+                );
+            }
+            row.push_str(cell.symbol());
+        }
+        skip = std::cmp::max(skip, cell.symbol().width()).saturating_sub(1);
+    }
+    if color {
+        let _ = write!(row, "{ResetAttributes}");
+    }
+    row
+}
+
+/// Describes the cell-level differences between two buffers.
+///
+/// Rows that differ are rendered side by side with their styles when `color` is true, with the
+/// differing cells marked underneath, followed by a list of the differing cells.
+pub(super) fn diff(expected: &Buffer, actual: &Buffer, color: bool) -> String {
+    if expected.area != actual.area {
+        return format!(
+            "expected area {:?}, got {:?}\nexpected:\n{}\ngot:\n{}",
+            expected.area,
+            actual.area,
+            serialize(expected),
+            serialize(actual)
+        );
+    }
+    let width = expected.area.width as usize;
+    let differs =
+        |a: &Cell, b: &Cell| a.symbol() != b.symbol() || CellStyle::of(a) != CellStyle::of(b);
+    let mut rows = String::from("expected | got\n");
+    let mut cells = vec![];
+    for (y, (expected_row, actual_row)) in expected
+        .content()
+        .chunks(width.max(1))
+        .zip(actual.content().chunks(width.max(1)))
+        .enumerate()
+    {
+        let markers: String = expected_row
+            .iter()
+            .zip(actual_row)
+            .map(|(e, a)| if differs(e, a) { '^' } else { ' ' })
+            .collect();
+        if !markers.contains('^') {
+            continue;
+        }
+        let _ = writeln!(
+            rows,
+            "{} | {}  row {y}",
+            render_row(expected_row, color),
+            render_row(actual_row, color)
+        );
+        let _ = writeln!(rows, "{markers} | {}", markers.trim_end());
+        for (x, (e, a)) in expected_row.iter().zip(actual_row).enumerate() {
+            if differs(e, a) {
+                cells.push((x, y, e, a));
+            }
+        }
+    }
+    let _ = writeln!(rows, "differences:");
+    for (x, y, e, a) in cells.iter().take(MAX_LISTED_DIFFERENCES) {
+        let _ = writeln!(
+            rows,
+            "  ({x}, {y}): expected {:?} {}, got {:?} {}",
+            e.symbol(),
+            CellStyle::of(e).describe(),
+            a.symbol(),
+            CellStyle::of(a).describe()
+        );
+    }
+    if cells.len() > MAX_LISTED_DIFFERENCES {
+        let _ = writeln!(
+            rows,
+            "  ... and {} more",
+            cells.len() - MAX_LISTED_DIFFERENCES
+        );
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Style, Stylize};
+
+    fn styled_buffer() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buffer.set_string(0, 0, "Hello", Style::new().red().bold());
+        buffer.set_string(0, 1, "World!", Style::new().fg(Color::Rgb(255, 128, 0)));
+        buffer.get_mut(5, 1).set_bg(Color::Indexed(236));
+        buffer
+            .get_mut(7, 1)
+            .set_hyperlink(Some("https://ratatui.rs"));
+        buffer
+    }
+
+    #[test]
+    fn serialize_buffer() {
+        assert_eq!(
+            serialize(&styled_buffer()),
+            indoc::indoc! {r#"
+                area: x=0 y=0 width=8 height=2
+                content:
+                "Hello   "
+                "World!  "
+                styles:
+                "aaaaa   "
+                "bbbbbc d"
+                a: fg=Red modifier=BOLD
+                b: fg=#FF8000
+                c: fg=#FF8000 bg=236
+                d: link=https://ratatui.rs
+            "#}
+        );
+    }
+
+    #[test]
+    fn deserialize_round_trip() {
+        let buffer = styled_buffer();
+        assert_eq!(deserialize(&serialize(&buffer)), Ok(buffer));
+    }
+
+    #[test]
+    fn deserialize_wide_symbols() {
+        let buffer = Buffer::with_lines(vec!["コン ", "abcde"]);
+        assert_eq!(deserialize(&serialize(&buffer)), Ok(buffer));
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        assert!(deserialize("").is_err());
+        assert!(deserialize("area: x=0 y=0 width=1 height=1\ncontent:\n").is_err());
+        assert!(
+            deserialize("area: x=0 y=0 width=1 height=1\ncontent:\n\"a\"\nstyles:\n\"z\"\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn style_keys() {
+        assert_eq!(style_key(0), 'a');
+        assert_eq!(style_key(26), 'A');
+        assert_eq!(style_key(61), '9');
+        assert_eq!(style_key(62), 'Ā');
+    }
+
+    #[test]
+    fn diff_lists_differing_cells() {
+        let expected = styled_buffer();
+        let mut actual = styled_buffer();
+        actual.get_mut(1, 0).set_symbol("a");
+        actual.get_mut(2, 1).set_fg(Color::Blue);
+        assert_eq!(
+            diff(&expected, &actual, false),
+            indoc::indoc! {r#"
+                expected | got
+                Hello    | Hallo     row 0
+                 ^       |  ^
+                World!   | World!    row 1
+                  ^      |   ^
+                differences:
+                  (1, 0): expected "e" fg=Red modifier=BOLD, got "a" fg=Red modifier=BOLD
+                  (2, 1): expected "r" fg=#FF8000, got "r" fg=Blue
+            "#}
+        );
+    }
+
+    #[test]
+    fn diff_colors_rows() {
+        let expected = Buffer::with_lines(vec!["a"]);
+        let mut actual = expected.clone();
+        actual.get_mut(0, 0).set_fg(Color::Red);
+        let diff = diff(&expected, &actual, true);
+        assert!(diff.contains("\x1b[0m\x1b[39m\x1b[49ma\x1b[0m | \x1b[0m\x1b[31m\x1b[49ma\x1b[0m"));
+    }
+
+    #[test]
+    fn check_snapshot_update_and_compare() {
+        let dir = env::temp_dir().join(format!("ratatui-snapshots-{}", std::process::id()));
+        let path = dir.join("nested").join("buffer.snap");
+        let buffer = styled_buffer();
+
+        let error = check_snapshot(&buffer, &path, false).unwrap_err();
+        assert!(error.starts_with("failed to read snapshot"));
+
+        check_snapshot(&buffer, &path, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), serialize(&buffer));
+        check_snapshot(&buffer, &path, false).unwrap();
+
+        let mut changed = buffer.clone();
+        changed.get_mut(0, 0).set_symbol("J");
+        let error = check_snapshot(&changed, &path, false).unwrap_err();
+        assert!(error.contains("does not match"));
+        assert!(error.contains(r#"(0, 0): expected "H""#));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,8 +1,12 @@
 use std::{
     cmp::min,
+    collections::hash_map::DefaultHasher,
     fmt::{Debug, Formatter, Result},
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
+use compact_str::CompactString;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
@@ -12,7 +16,15 @@ use crate::{
     text::{Line, Span},
 };
 
+mod export;
+mod layer;
+pub(crate) use layer::composite_layers;
+pub use layer::Layer;
+
 /// A buffer cell
+///
+/// The symbol of the cell is stored inline, without allocating, unless it is longer than 24 bytes
+/// (12 bytes on 32-bit platforms).
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell {
@@ -22,13 +34,15 @@ pub struct Cell {
                 the value. Use `Cell::set_symbol` to update the field. Use `Cell::default` to \
                 create `Cell` instance"
     )]
-    pub symbol: String,
+    pub symbol: CompactString,
     pub fg: Color,
     pub bg: Color,
     #[cfg(feature = "underline-color")]
     pub underline_color: Color,
     pub modifier: Modifier,
     pub skip: bool,
+    /// The target of the hyperlink the cell is part of, if any.
+    pub hyperlink: Option<CompactString>,
 }
 
 #[allow(deprecated)] // For Cell::symbol
@@ -38,14 +52,25 @@ impl Cell {
     }
 
     pub fn set_symbol(&mut self, symbol: &str) -> &mut Cell {
-        self.symbol.clear();
-        self.symbol.push_str(symbol);
+        self.symbol = CompactString::new(symbol);
         self
     }
 
+    /// Returns the number of columns taken by the cell's symbol.
+    ///
+    /// Symbols starting with an escape sequence (such as an inline image written by the
+    /// [`Image`](crate::widgets::Image) widget) only ever advance the cursor by one column.
+    pub(crate) fn symbol_width(&self) -> usize {
+        if self.symbol.starts_with('\x1b') {
+            1
+        } else {
+            self.symbol.width()
+        }
+    }
+
     pub fn set_char(&mut self, ch: char) -> &mut Cell {
-        self.symbol.clear();
-        self.symbol.push(ch);
+        let mut buf = [0; 4];
+        self.symbol = CompactString::new(ch.encode_utf8(&mut buf));
         self
     }
 
@@ -92,6 +117,19 @@ impl Cell {
             .add_modifier(self.modifier)
     }
 
+    /// Returns the target of the hyperlink the cell is part of, if any.
+    pub fn hyperlink(&self) -> Option<&str> {
+        self.hyperlink.as_deref()
+    }
+
+    /// Sets the target of the hyperlink the cell is part of, or removes it when `None`.
+    ///
+    /// Backends emit the hyperlink as an `OSC 8` sequence around the cell's symbol.
+    pub fn set_hyperlink(&mut self, hyperlink: Option<&str>) -> &mut Cell {
+        self.hyperlink = hyperlink.map(CompactString::new);
+        self
+    }
+
     /// Sets the cell to be skipped when copying (diffing) the buffer to the screen.
     ///
     /// This is helpful when it is necessary to prevent the buffer from overwriting a cell that is
@@ -102,8 +140,7 @@ impl Cell {
     }
 
     pub fn reset(&mut self) {
-        self.symbol.clear();
-        self.symbol.push(' ');
+        self.symbol = CompactString::new_inline(" ");
         self.fg = Color::Reset;
         self.bg = Color::Reset;
         #[cfg(feature = "underline-color")]
@@ -112,6 +149,7 @@ impl Cell {
         }
         self.modifier = Modifier::empty();
         self.skip = false;
+        self.hyperlink = None;
     }
 }
 
@@ -119,13 +157,14 @@ impl Default for Cell {
     fn default() -> Cell {
         #[allow(deprecated)] // For Cell::symbol
         Cell {
-            symbol: " ".into(),
+            symbol: CompactString::new_inline(" "),
             fg: Color::Reset,
             bg: Color::Reset,
             #[cfg(feature = "underline-color")]
             underline_color: Color::Reset,
             modifier: Modifier::empty(),
             skip: false,
+            hyperlink: None,
         }
     }
 }
@@ -162,7 +201,10 @@ pub struct Buffer {
     pub area: Rect,
     /// The content of the buffer. The length of this Vec should always be equal to area.width *
     /// area.height
-    pub content: Vec<Cell>,
+    content: Vec<Cell>,
+    /// The hashes of the rows that were not written to since they were last hashed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    row_hashes: RowHashes,
 }
 
 impl Buffer {
@@ -179,7 +221,13 @@ impl Buffer {
         for _ in 0..size {
             content.push(cell.clone());
         }
-        Buffer { area, content }
+        let mut buffer = Buffer {
+            area,
+            content,
+            row_hashes: RowHashes::default(),
+        };
+        buffer.hash_equal_rows();
+        buffer
     }
 
     /// Returns a Buffer containing the given lines
@@ -202,6 +250,16 @@ impl Buffer {
         &self.content
     }
 
+    /// Returns the content of the buffer as a mutable slice.
+    ///
+    /// The buffer can't tell which cells are changed through the slice, so every row is hashed
+    /// again the next time it is diffed. Prefer [`Buffer::get_mut`] and the other methods that
+    /// write to the buffer, which only invalidate the rows they change.
+    pub fn content_mut(&mut self) -> &mut [Cell] {
+        self.row_hashes.invalidate_all(self.area);
+        &mut self.content
+    }
+
     /// Returns the area covered by this buffer
     pub fn area(&self) -> &Rect {
         &self.area
@@ -216,9 +274,19 @@ impl Buffer {
     /// Returns a mutable reference to Cell at the given coordinates
     pub fn get_mut(&mut self, x: u16, y: u16) -> &mut Cell {
         let i = self.index_of(x, y);
+        self.row_hashes.invalidate(y - self.area.y);
         &mut self.content[i]
     }
 
+    /// Returns the hash of the row at index `y` of the buffer, which is kept until the row is
+    /// written to.
+    pub(crate) fn row_hash(&self, y: u16) -> u64 {
+        let width = self.area.width as usize;
+        let start = y as usize * width;
+        self.row_hashes
+            .get(y as usize, &self.content[start..start + width])
+    }
+
     /// Returns the index in the `Vec<Cell>` for the given global (x, y) coordinates.
     ///
     /// Global coordinates are offset by the Buffer's area offset (`x`/`y`).
@@ -315,9 +383,23 @@ impl Buffer {
     where
         S: AsRef<str>,
     {
+        self.set_linked_stringn(x, y, string.as_ref(), width, style, None)
+    }
+
+    /// Same as [`Buffer::set_stringn`], but also sets the hyperlink of the written cells.
+    fn set_linked_stringn(
+        &mut self,
+        x: u16,
+        y: u16,
+        string: &str,
+        width: usize,
+        style: Style,
+        hyperlink: Option<&str>,
+    ) -> (u16, u16) {
         let mut index = self.index_of(x, y);
+        self.row_hashes.invalidate(y - self.area.y);
         let mut x_offset = x as usize;
-        let graphemes = UnicodeSegmentation::graphemes(string.as_ref(), true);
+        let graphemes = UnicodeSegmentation::graphemes(string, true);
         let max_offset = min(self.area.right() as usize, width.saturating_add(x as usize));
         for s in graphemes {
             let width = s.width();
@@ -330,8 +412,10 @@ impl Buffer {
                 break;
             }
 
-            self.content[index].set_symbol(s);
-            self.content[index].set_style(style);
+            self.content[index]
+                .set_symbol(s)
+                .set_style(style)
+                .set_hyperlink(hyperlink);
             // Reset following cells if multi-width (they would be hidden by the grapheme),
             for i in index + 1..index + width {
                 self.content[i].reset();
@@ -349,12 +433,13 @@ impl Buffer {
             if remaining_width == 0 {
                 break;
             }
-            let pos = self.set_stringn(
+            let pos = self.set_linked_stringn(
                 x,
                 y,
                 span.content.as_ref(),
                 remaining_width as usize,
                 span.style,
+                span.hyperlink.as_deref(),
             );
             let w = pos.0.saturating_sub(x);
             x = pos.0;
@@ -364,7 +449,14 @@ impl Buffer {
     }
 
     pub fn set_span(&mut self, x: u16, y: u16, span: &Span<'_>, width: u16) -> (u16, u16) {
-        self.set_stringn(x, y, span.content.as_ref(), width as usize, span.style)
+        self.set_linked_stringn(
+            x,
+            y,
+            span.content.as_ref(),
+            width as usize,
+            span.style,
+            span.hyperlink.as_deref(),
+        )
     }
 
     #[deprecated(
@@ -397,6 +489,7 @@ impl Buffer {
             self.content.resize(length, Cell::default());
         }
         self.area = area;
+        self.row_hashes.invalidate_all(area);
     }
 
     /// Reset all cells in the buffer
@@ -404,6 +497,17 @@ impl Buffer {
         for c in &mut self.content {
             c.reset();
         }
+        self.hash_equal_rows();
+    }
+
+    /// Sets the hashes of all the rows to the hash of the first one, after they were all set to the
+    /// same cells.
+    fn hash_equal_rows(&mut self) {
+        let width = self.area.width as usize;
+        match self.content.get(..width) {
+            Some(row) if width > 0 => self.row_hashes.fill(self.area, hash_row(row)),
+            _ => self.row_hashes.invalidate_all(self.area),
+        }
     }
 
     /// Merge an other buffer into this one
@@ -434,6 +538,162 @@ impl Buffer {
             self.content[k] = other.content[i].clone();
         }
         self.area = area;
+        self.row_hashes.invalidate_all(area);
+    }
+
+    /// Copies the cells of `source` in `area` to this buffer, with the top left corner of `area`
+    /// at (`x`, `y`).
+    ///
+    /// `area` is clipped to the area of `source`, keeping each copied cell at the same distance
+    /// from (`x`, `y`), and the cells that would be copied outside of this buffer are skipped.
+    /// Wide characters cut by the edges of the copied area are replaced with blank cells.
+    ///
+    /// This allows rendering a widget once to an offscreen buffer of any size, and then copying
+    /// the part of it that is visible to the frame.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{prelude::*, widgets::*};
+    ///
+    /// // a long list rendered once, then scrolled without rendering it again
+    /// let content_area = Rect::new(0, 0, 20, 100);
+    /// let mut content = Buffer::empty(content_area);
+    /// let items: Vec<_> = (0..100).map(|i| ListItem::new(format!("Item {i}"))).collect();
+    /// Widget::render(List::new(items), content_area, &mut content);
+    ///
+    /// let mut frame = Buffer::empty(Rect::new(0, 0, 20, 3));
+    /// let scroll = 42;
+    /// frame.copy_from(&content, Rect::new(0, scroll, 20, 3), 0, 0);
+    /// assert_eq!(frame.get(0, 0).symbol(), "I");
+    /// assert_eq!(frame.get(5, 0).symbol(), "4");
+    /// ```
+    pub fn copy_from(&mut self, source: &Buffer, area: Rect, x: u16, y: u16) {
+        if !area.intersects(source.area) {
+            return;
+        }
+        let clipped = area.intersection(source.area);
+        let x = u32::from(x) + u32::from(clipped.x - area.x);
+        let y = u32::from(y) + u32::from(clipped.y - area.y);
+        let area = clipped;
+        let left = x.max(u32::from(self.area.left()));
+        let right = (x + u32::from(area.width)).min(u32::from(self.area.right()));
+        if left >= right {
+            return;
+        }
+        for row in 0..area.height {
+            let target_y = y + u32::from(row);
+            if target_y < u32::from(self.area.top()) || target_y >= u32::from(self.area.bottom()) {
+                continue;
+            }
+            let target_y = target_y as u16;
+            let source_x = area.x + (left - x) as u16;
+            let source_start = source.index_of(source_x, area.y + row);
+            let target_start = self.index_of(left as u16, target_y);
+            let width = (right - left) as usize;
+            self.row_hashes.invalidate(target_y - self.area.y);
+            self.content[target_start..target_start + width]
+                .clone_from_slice(&source.content[source_start..source_start + width]);
+            self.clip_wide_characters(left as u16, right as u16, target_y);
+        }
+    }
+
+    /// Sets all the cells of the buffer in `area` to a copy of `cell`.
+    ///
+    /// Wide characters cut by the edges of `area` are replaced with blank cells.
+    pub fn fill(&mut self, area: Rect, cell: &Cell) {
+        if !area.intersects(self.area) {
+            return;
+        }
+        let area = area.intersection(self.area);
+        for y in area.top()..area.bottom() {
+            let start = self.index_of(area.x, y);
+            self.row_hashes.invalidate(y - self.area.y);
+            for target in &mut self.content[start..start + area.width as usize] {
+                target.clone_from(cell);
+            }
+            self.clip_wide_characters(area.left(), area.right(), y);
+        }
+    }
+
+    /// Moves the rows of the buffer in `area` up by `amount` rows. The rows moved out of `area`
+    /// are discarded, and the rows at the bottom of `area` are reset.
+    ///
+    /// Wide characters cut by the left and right edges of `area` are replaced with blank cells.
+    pub fn scroll_up(&mut self, area: Rect, amount: u16) {
+        self.scroll(area, i32::from(amount));
+    }
+
+    /// Moves the rows of the buffer in `area` down by `amount` rows. The rows moved out of `area`
+    /// are discarded, and the rows at the top of `area` are reset.
+    ///
+    /// Wide characters cut by the left and right edges of `area` are replaced with blank cells.
+    pub fn scroll_down(&mut self, area: Rect, amount: u16) {
+        self.scroll(area, -i32::from(amount));
+    }
+
+    /// Implements [`Buffer::scroll_up`] for a positive `amount`, and [`Buffer::scroll_down`] for a
+    /// negative one.
+    fn scroll(&mut self, area: Rect, amount: i32) {
+        if amount == 0 || !area.intersects(self.area) {
+            return;
+        }
+        let area = area.intersection(self.area);
+        for y in area.top()..area.bottom() {
+            self.row_hashes.invalidate(y - self.area.y);
+        }
+        let width = area.width as usize;
+        let rows = i32::from(area.top())..i32::from(area.bottom());
+        let moved = amount.unsigned_abs().min(u32::from(area.height)) as usize;
+        // Each row is swapped with the row `amount` rows away, starting from the side the rows move
+        // to, so that the rows that are moved out of the area end up in the rows to reset.
+        let targets: Vec<i32> = if amount > 0 {
+            rows.clone().collect()
+        } else {
+            rows.clone().rev().collect()
+        };
+        for &y in &targets[..targets.len() - moved] {
+            let (first, second) = if amount > 0 {
+                (y, y + amount)
+            } else {
+                (y + amount, y)
+            };
+            let first = self.index_of(area.x, first as u16);
+            let second = self.index_of(area.x, second as u16);
+            let (head, tail) = self.content.split_at_mut(second);
+            head[first..first + width].swap_with_slice(&mut tail[..width]);
+        }
+        for &y in &targets[targets.len() - moved..] {
+            let start = self.index_of(area.x, y as u16);
+            for cell in &mut self.content[start..start + width] {
+                cell.reset();
+            }
+        }
+        for y in rows {
+            self.clip_wide_characters(area.left(), area.right(), y as u16);
+        }
+    }
+
+    /// Replaces the wide characters cut by the columns `left` and `right` of row `y` with blank
+    /// cells, after the cells between them were replaced.
+    fn clip_wide_characters(&mut self, left: u16, right: u16, y: u16) {
+        if left > self.area.left() && self.get(left - 1, y).symbol_width() > 1 {
+            self.get_mut(left - 1, y).set_symbol(" ");
+        }
+        let mut covered = 0;
+        for x in left..right {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let cell = self.get_mut(x, y);
+            let width = cell.symbol_width();
+            if x as usize + width > right as usize {
+                cell.set_symbol(" ");
+            } else {
+                covered = width.saturating_sub(1);
+            }
+        }
     }
 
     /// Builds a minimal sequence of coordinates and Cells necessary to update the UI from
@@ -442,6 +702,11 @@ impl Buffer {
     /// We're assuming that buffers are well-formed, that is no double-width cell is followed by
     /// a non-blank cell.
     ///
+    /// Each buffer keeps the hashes of its rows until they are written to. Rows whose hash is known
+    /// in both buffers are compared by hash, and the other rows cell by cell, so diffing the frames
+    /// of an application that mostly sits idle is cheap. The rows of a reset buffer share the hash
+    /// of a blank row, and a row that is equal to a row with a known hash gets the same hash.
+    ///
     /// # Multi-width characters handling:
     ///
     /// ```text
@@ -467,6 +732,8 @@ impl Buffer {
     pub fn diff<'a>(&self, other: &'a Buffer) -> Vec<(u16, u16, &'a Cell)> {
         let previous_buffer = &self.content;
         let next_buffer = &other.content;
+        // compare whole rows first, as most rows don't change between two frames
+        let width = (self.area.width as usize).max(1);
 
         let mut updates: Vec<(u16, u16, &Cell)> = vec![];
         // Cells invalidated by drawing/replacing preceding multi-width characters:
@@ -474,21 +741,163 @@ impl Buffer {
         // Cells from the current buffer to skip due to preceding multi-width characters taking
         // their place (the skipped cells should be blank anyway), or due to per-cell-skipping:
         let mut to_skip: usize = 0;
-        for (i, (current, previous)) in next_buffer.iter().zip(previous_buffer.iter()).enumerate() {
-            if !current.skip && (current != previous || invalidated > 0) && to_skip == 0 {
-                let (x, y) = self.pos_of(i);
-                updates.push((x, y, &next_buffer[i]));
+        let rows = next_buffer.chunks(width).zip(previous_buffer.chunks(width));
+        for (row, (current_row, previous_row)) in rows.enumerate() {
+            let unchanged = match (
+                self.row_hashes.cached(row, previous_row),
+                other.row_hashes.cached(row, current_row),
+            ) {
+                (Some(previous), Some(current)) => previous == current,
+                // comparing the cells is cheaper than hashing them
+                (previous, current) => {
+                    let unchanged = current_row == previous_row;
+                    if let Some(hash) = previous.or(current).filter(|_| unchanged) {
+                        self.row_hashes.set(row, previous_row, hash);
+                        other.row_hashes.set(row, current_row, hash);
+                    }
+                    unchanged
+                }
+            };
+            if invalidated == 0 && to_skip == 0 && unchanged {
+                // Nothing to draw in this row, only the multi-width characters running past its
+                // end affect the next one.
+                (to_skip, invalidated) = overflow(current_row);
+                continue;
             }
+            for (i, (current, previous)) in current_row.iter().zip(previous_row).enumerate() {
+                if !current.skip && (current != previous || invalidated > 0) && to_skip == 0 {
+                    let (x, y) = self.pos_of(row * width + i);
+                    updates.push((x, y, current));
+                }
 
-            to_skip = current.symbol().width().saturating_sub(1);
+                to_skip = current.symbol_width().saturating_sub(1);
 
-            let affected_width = std::cmp::max(current.symbol().width(), previous.symbol().width());
-            invalidated = std::cmp::max(affected_width, invalidated).saturating_sub(1);
+                let affected_width = std::cmp::max(current.symbol_width(), previous.symbol_width());
+                invalidated = std::cmp::max(affected_width, invalidated).saturating_sub(1);
+            }
         }
         updates
     }
 }
 
+/// The cached hashes of the rows of a [`Buffer`], used to skip the rows that didn't change when
+/// diffing it.
+///
+/// A hash of 0 marks a row that was written to since it was last hashed. The hashes are filled in
+/// by methods that only borrow the buffer, such as [`Buffer::diff`], so they are stored in atomics.
+/// They are not part of the value of the buffer, so they are ignored when comparing or hashing it.
+#[derive(Debug, Default)]
+struct RowHashes {
+    /// The width of the rows the hashes were computed for.
+    width: u16,
+    hashes: Vec<AtomicU64>,
+}
+
+impl RowHashes {
+    /// Returns the hash of `row`, the row at index `y` of the buffer, hashing it if needed.
+    fn get(&self, y: usize, row: &[Cell]) -> u64 {
+        self.cached(y, row).unwrap_or_else(|| {
+            let hash = hash_row(row);
+            self.set(y, row, hash);
+            hash
+        })
+    }
+
+    /// Returns the hash of `row`, the row at index `y` of the buffer, if it is known.
+    fn cached(&self, y: usize, row: &[Cell]) -> Option<u64> {
+        self.slot(y, row)
+            .map(|hash| hash.load(Ordering::Relaxed))
+            .filter(|&hash| hash != 0)
+    }
+
+    /// Sets the hash of `row`, the row at index `y` of the buffer.
+    fn set(&self, y: usize, row: &[Cell], hash: u64) {
+        if let Some(slot) = self.slot(y, row) {
+            slot.store(hash, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns where the hash of `row`, the row at index `y` of the buffer, is kept, unless the
+    /// hashes are not kept for rows of its width.
+    fn slot(&self, y: usize, row: &[Cell]) -> Option<&AtomicU64> {
+        self.hashes
+            .get(y)
+            .filter(|_| row.len() == self.width as usize)
+    }
+
+    /// Marks the row at index `y` of the buffer as written to.
+    fn invalidate(&mut self, y: u16) {
+        if let Some(hash) = self.hashes.get_mut(y as usize) {
+            *hash.get_mut() = 0;
+        }
+    }
+
+    /// Marks all the rows of a buffer covering `area` as written to.
+    fn invalidate_all(&mut self, area: Rect) {
+        self.fill(area, 0);
+    }
+
+    /// Sets the hashes of all the rows of a buffer covering `area` to `hash`.
+    fn fill(&mut self, area: Rect, hash: u64) {
+        self.width = area.width;
+        self.hashes.truncate(area.height as usize);
+        for cached in &mut self.hashes {
+            *cached.get_mut() = hash;
+        }
+        self.hashes
+            .resize_with(area.height as usize, || AtomicU64::new(hash));
+    }
+}
+
+impl Clone for RowHashes {
+    fn clone(&self) -> Self {
+        RowHashes {
+            width: self.width,
+            hashes: self
+                .hashes
+                .iter()
+                .map(|hash| AtomicU64::new(hash.load(Ordering::Relaxed)))
+                .collect(),
+        }
+    }
+}
+
+impl PartialEq for RowHashes {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RowHashes {}
+
+impl Hash for RowHashes {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// Returns the hash of a row of cells, which is never 0.
+pub(crate) fn hash_row(row: &[Cell]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    row.hash(&mut hasher);
+    hasher.finish().max(1)
+}
+
+/// Returns the number of cells following `row` that are covered by its last cell and by all of its
+/// cells, as computed by [`Buffer::diff`] for a row that didn't change.
+fn overflow(row: &[Cell]) -> (usize, usize) {
+    let to_skip = row
+        .last()
+        .map_or(0, |cell| cell.symbol_width().saturating_sub(1));
+    // only symbols longer than a byte can be wider than a cell
+    let invalidated = row
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| cell.symbol().len() > 1)
+        .map(|(i, cell)| (i + cell.symbol_width()).saturating_sub(row.len()))
+        .max()
+        .unwrap_or(0);
+    (to_skip, invalidated)
+}
+
 /// Assert that two buffers are equal by comparing their areas and content.
 ///
 /// On panic, displays the areas or the content and a diff of the contents.
@@ -551,6 +960,8 @@ impl Debug for Buffer {
     /// * `content`: displayed as a list of strings representing the content of the buffer
     /// * `styles`: displayed as a list of: `{ x: 1, y: 2, fg: Color::Red, bg: Color::Blue,
     ///   modifier: Modifier::BOLD }` only showing a value when there is a change in style.
+    /// * `hyperlinks`: displayed as a list of: `{ x: 1, y: 2, hyperlink: Some("https://..") }` only
+    ///   showing a value when there is a change in hyperlink. Omitted when no cell has a hyperlink.
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         f.write_fmt(format_args!(
             "Buffer {{\n    area: {:?},\n    content: [\n",
@@ -558,6 +969,8 @@ impl Debug for Buffer {
         ))?;
         let mut last_style = None;
         let mut styles = vec![];
+        let mut last_hyperlink = None;
+        let mut hyperlinks = vec![];
         for (y, line) in self.content.chunks(self.area.width as usize).enumerate() {
             let mut overwritten = vec![];
             let mut skip: usize = 0;
@@ -569,6 +982,10 @@ impl Debug for Buffer {
                     overwritten.push((x, c.symbol()));
                 }
                 skip = std::cmp::max(skip, c.symbol().width()).saturating_sub(1);
+                if c.hyperlink() != last_hyperlink {
+                    last_hyperlink = c.hyperlink();
+                    hyperlinks.push((x, y, last_hyperlink));
+                }
                 #[cfg(feature = "underline-color")]
                 {
                     let style = (c.fg, c.bg, c.underline_color, c.modifier);
@@ -606,6 +1023,14 @@ impl Debug for Buffer {
                 s.0, s.1, s.2, s.3, s.4
             ))?;
         }
+        if !hyperlinks.is_empty() {
+            f.write_str("    ],\n    hyperlinks: [\n")?;
+            for (x, y, hyperlink) in hyperlinks {
+                f.write_fmt(format_args!(
+                    "        x: {x}, y: {y}, hyperlink: {hyperlink:?},\n"
+                ))?;
+            }
+        }
         f.write_str("    ]\n}")?;
         Ok(())
     }
@@ -672,6 +1097,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_implements_debug_with_hyperlinks() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buf.set_span(1, 0, &Span::raw("ab").hyperlink("https://a"), 6);
+        buf.set_span(0, 1, &Span::raw("cd").hyperlink("https://b"), 6);
+        assert!(format!("{buf:?}").ends_with(indoc::indoc!(
+            "
+                ],
+                hyperlinks: [
+                    x: 1, y: 0, hyperlink: Some(\"https://a\"),
+                    x: 3, y: 0, hyperlink: None,
+                    x: 0, y: 1, hyperlink: Some(\"https://b\"),
+                    x: 2, y: 1, hyperlink: None,
+                ]
+            }"
+        )));
+    }
+
+    #[test]
+    fn set_span_with_hyperlink() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
+        let span = Span::raw("a界").hyperlink("https://ratatui.rs");
+        buffer.set_span(0, 0, &span, 5);
+        assert_eq!(buffer.get(0, 0).hyperlink(), Some("https://ratatui.rs"));
+        assert_eq!(buffer.get(1, 0).hyperlink(), Some("https://ratatui.rs"));
+        // the cell hidden by the wide character is reset
+        assert_eq!(buffer.get(2, 0).hyperlink(), None);
+        assert_eq!(buffer.get(3, 0).hyperlink(), None);
+
+        // writing plain text removes the hyperlink
+        buffer.set_string(0, 0, "b", Style::default());
+        assert_eq!(buffer.get(0, 0).hyperlink(), None);
+        assert_eq!(buffer.get(1, 0).hyperlink(), Some("https://ratatui.rs"));
+    }
+
+    #[test]
+    fn set_line_with_hyperlink() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
+        let line = Line::from(vec![
+            Span::raw("ab").hyperlink("https://a"),
+            Span::raw("cd"),
+        ]);
+        buffer.set_line(0, 0, &line, 5);
+        let hyperlinks: Vec<_> = buffer.content.iter().map(Cell::hyperlink).collect();
+        assert_eq!(
+            hyperlinks,
+            vec![Some("https://a"), Some("https://a"), None, None, None]
+        );
+    }
+
     #[test]
     fn assert_buffer_eq_does_not_panic_on_equal_buffers() {
         let buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
@@ -905,13 +1380,112 @@ mod tests {
         let prev = Buffer::with_lines(vec!["123"]);
         let mut next = Buffer::with_lines(vec!["456"]);
         for i in 1..3 {
-            next.content[i].set_skip(true);
+            next.content_mut()[i].set_skip(true);
         }
 
         let diff = prev.diff(&next);
         assert_eq!(diff, vec![(0, 0, &cell("4"))],);
     }
 
+    #[test]
+    fn buffer_diffing_unchanged_rows() {
+        let mut prev = Buffer::with_lines(vec!["abc", "def", "ghi"]);
+        prev.area = Rect::new(2, 3, 3, 3);
+        let mut next = prev.clone();
+        next.get_mut(3, 4).set_symbol("x");
+
+        let diff = prev.diff(&next);
+        assert_eq!(diff, vec![(3, 4, &cell("x"))]);
+    }
+
+    #[test]
+    fn buffer_diffing_multi_width_past_unchanged_row() {
+        // the wide character at the end of the first row covers the start of the second one
+        let mut prev = Buffer::with_lines(vec!["ab", "cd"]);
+        prev.get_mut(1, 0).set_symbol("コ");
+        let mut next = prev.clone();
+        next.get_mut(0, 1).set_symbol("x");
+        next.get_mut(1, 1).set_symbol("y");
+
+        let diff = prev.diff(&next);
+        assert_eq!(diff, vec![(1, 1, &cell("y"))]);
+    }
+
+    #[test]
+    fn buffer_diffing_sees_writes_after_rows_were_hashed() {
+        type Write = fn(&mut Buffer);
+        let area = Rect::new(1, 1, 4, 3);
+        let writes: [(&str, Write); 9] = [
+            ("get_mut", |buf| {
+                buf.get_mut(2, 2).set_symbol("x");
+            }),
+            ("set_string", |buf| buf.set_string(1, 2, "x", Style::new())),
+            ("set_style", |buf| {
+                buf.set_style(Rect::new(3, 2, 1, 1), Style::new().fg(Color::Red));
+            }),
+            ("content_mut", |buf| {
+                buf.content_mut()[5].set_symbol("x");
+            }),
+            ("fill", |buf| {
+                buf.fill(Rect::new(2, 2, 2, 1), Cell::default().set_symbol("x"));
+            }),
+            ("scroll_up", |buf| buf.scroll_up(Rect::new(1, 1, 4, 2), 1)),
+            ("copy_from", |buf| {
+                let source = Buffer::with_lines(vec!["wxyz"]);
+                buf.copy_from(&source, source.area, 1, 2);
+            }),
+            ("reset", Buffer::reset),
+            ("resize", |buf| {
+                let area = buf.area;
+                buf.resize(Rect { height: 1, ..area });
+                buf.resize(area);
+            }),
+        ];
+        for (name, write) in writes {
+            let mut prev = Buffer::with_lines(vec!["abcd", "efgh", "ijkl"]);
+            prev.area = area;
+            let mut next = prev.clone();
+            // hash every row of both buffers, as the terminal does to find scrolled rows
+            for y in 0..area.height {
+                assert_eq!(prev.row_hash(y), next.row_hash(y), "{name}");
+            }
+            write(&mut next);
+            assert_ne!(prev, next, "{name} didn't change the buffer");
+            assert_ne!(prev.diff(&next), vec![], "{name}");
+        }
+    }
+
+    #[test]
+    fn buffer_diffing_reset_buffer() {
+        let area = Rect::new(0, 0, 3, 2);
+        let mut prev = Buffer::with_lines(vec!["abc", "def"]);
+        let mut next = prev.clone();
+        assert_eq!(prev.diff(&next), vec![]);
+
+        // the rows of a reset buffer keep the hash of a blank row until they are written to
+        next.reset();
+        assert_eq!(Buffer::empty(area).diff(&next), vec![]);
+        next.set_string(0, 1, "x", Style::new());
+        assert_eq!(Buffer::empty(area).diff(&next), vec![(0, 1, &cell("x"))]);
+
+        prev.reset();
+        assert_eq!(prev.diff(&next), vec![(0, 1, &cell("x"))]);
+    }
+
+    #[test]
+    fn buffer_row_hashes_are_not_part_of_the_value() {
+        let buffer = Buffer::with_lines(vec!["abc", "def"]);
+        let hashed = buffer.clone();
+        hashed.row_hash(0);
+        assert_eq!(hashed, buffer);
+        let hash = |buffer: &Buffer| {
+            let mut hasher = DefaultHasher::new();
+            buffer.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&hashed), hash(&buffer));
+    }
+
     #[test]
     fn buffer_merge() {
         let mut one = Buffer::filled(
@@ -1044,6 +1618,95 @@ mod tests {
         assert_eq!(skipped, vec![true, true, false, false, false, false]);
     }
 
+    #[test]
+    fn copy_from_crops_and_translates() {
+        let source = Buffer::with_lines(vec!["abcd", "efgh", "ijkl"]);
+        let mut buffer = Buffer::with_lines(vec!["....", "....", "...."]);
+        buffer.copy_from(&source, Rect::new(1, 1, 2, 2), 2, 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["..fg", "..jk", "...."]));
+    }
+
+    #[test]
+    fn copy_from_clips() {
+        let source = Buffer::with_lines(vec!["abc", "def"]);
+
+        // the part of the area outside of the source is not copied
+        let mut buffer = Buffer::with_lines(vec!["....", "...."]);
+        buffer.copy_from(&source, Rect::new(0, 1, 5, 5), 0, 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["def.", "...."]));
+
+        // the cells that would be copied outside of the buffer are skipped
+        let mut buffer = Buffer::with_lines(vec!["....", "...."]);
+        buffer.area = Rect::new(10, 10, 4, 2);
+        buffer.copy_from(&source, source.area, 12, 11);
+        let mut expected = Buffer::with_lines(vec!["....", "..ab"]);
+        expected.area = Rect::new(10, 10, 4, 2);
+        assert_buffer_eq!(buffer, expected);
+
+        let mut buffer = Buffer::with_lines(vec!["...."]);
+        buffer.copy_from(&source, source.area, 4, 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["...."]));
+    }
+
+    #[test]
+    fn copy_from_wide_characters() {
+        // the wide character of the source doesn't fit
+        let source = Buffer::with_lines(vec!["aコ"]);
+        let mut buffer = Buffer::with_lines(vec!["...."]);
+        buffer.copy_from(&source, Rect::new(0, 0, 2, 1), 0, 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["a .."]));
+
+        // the wide character of the buffer is partly overwritten
+        let mut buffer = Buffer::with_lines(vec!["コ.."]);
+        buffer.copy_from(&source, Rect::new(0, 0, 1, 1), 1, 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec![" a.."]));
+    }
+
+    #[test]
+    fn fill() {
+        let mut buffer = Buffer::with_lines(vec!["abcd", "efgh"]);
+        let mut cell = Cell::default();
+        cell.set_symbol("x").set_fg(Color::Red);
+        buffer.fill(Rect::new(2, 1, 5, 5), &cell);
+        let mut expected = Buffer::with_lines(vec!["abcd", "efxx"]);
+        expected.set_style(Rect::new(2, 1, 2, 1), Style::new().fg(Color::Red));
+        assert_buffer_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn scroll_up() {
+        let mut buffer = Buffer::with_lines(vec!["abc", "def", "ghi", "jkl"]);
+        buffer.scroll_up(Rect::new(1, 0, 2, 3), 1);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["aef", "dhi", "g  ", "jkl"]));
+
+        let mut buffer = Buffer::with_lines(vec!["abc", "def"]);
+        buffer.scroll_up(buffer.area, 5);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["   ", "   "]));
+    }
+
+    #[test]
+    fn scroll_down() {
+        let mut buffer = Buffer::with_lines(vec!["abc", "def", "ghi", "jkl"]);
+        buffer.scroll_down(Rect::new(0, 1, 3, 3), 2);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["abc", "   ", "   ", "def"]));
+    }
+
+    #[test]
+    fn scroll_by_zero_rows() {
+        let mut buffer = Buffer::with_lines(vec!["abc", "def"]);
+        buffer.scroll_up(Rect::new(0, 0, 3, 2), 0);
+        buffer.scroll_down(Rect::new(0, 0, 3, 2), 0);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["abc", "def"]));
+    }
+
+    #[test]
+    fn scroll_wide_characters() {
+        // the wide character moved into the area doesn't fit
+        let mut buffer = Buffer::with_lines(vec!["abc", "aコ"]);
+        buffer.scroll_up(Rect::new(0, 0, 2, 2), 1);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["a c", "   "]));
+    }
+
     #[test]
     fn with_lines_accepts_into_lines() {
         use crate::style::Stylize;
@@ -1061,5 +1724,9 @@ mod tests {
         assert_eq!(cell.symbol(), "あ");
         cell.set_symbol("👨‍👩‍👧‍👦"); // Multiple code units combined with ZWJ
         assert_eq!(cell.symbol(), "👨‍👩‍👧‍👦");
+        cell.set_char('ß');
+        assert_eq!(cell.symbol(), "ß");
+        cell.reset();
+        assert_eq!(cell, Cell::default());
     }
 }
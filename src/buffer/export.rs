@@ -0,0 +1,488 @@
+use std::fmt::Write as _;
+
+#[cfg(feature = "underline-color")]
+use crate::backend::UnderlineColor;
+use crate::{
+    backend::{Bg, Fg, Hyperlink, ModifierDiff, ResetAttributes},
+    buffer::{Buffer, Cell},
+    style::Modifier,
+};
+
+/// A color as its red, green and blue components.
+type Rgb = (u8, u8, u8);
+
+/// The foreground color of cells with a [`Color::Reset`](crate::style::Color::Reset) foreground
+/// in HTML and SVG exports.
+const DEFAULT_FG: Rgb = (229, 229, 229);
+
+/// The background color of cells with a [`Color::Reset`](crate::style::Color::Reset) background
+/// in HTML and SVG exports.
+const DEFAULT_BG: Rgb = (0, 0, 0);
+
+/// The width of a cell in SVG exports, in pixels.
+const CELL_WIDTH: usize = 10;
+
+/// The height of a cell in SVG exports, in pixels.
+const CELL_HEIGHT: usize = 20;
+
+/// The font size of SVG exports, in pixels.
+const FONT_SIZE: usize = 16;
+
+impl Buffer {
+    /// Returns the content of the buffer as a string with ANSI escape sequences for its styles
+    /// and hyperlinks, with one line per row.
+    ///
+    /// Each line ends with the styles reset, so that the lines can be written to logs separately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::prelude::*;
+    ///
+    /// let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
+    /// buffer.set_string(0, 0, "Hi", Style::new().red());
+    /// assert_eq!(buffer.to_ansi_string(), "\x1b[31mHi\x1b[39m   ");
+    /// ```
+    pub fn to_ansi_string(&self) -> String {
+        let mut output = String::new();
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                output.push('\n');
+            }
+            let mut previous = Cell::default();
+            for cell in visible_cells(row) {
+                if cell.modifier != previous.modifier {
+                    let diff = ModifierDiff {
+                        from: previous.modifier,
+                        to: cell.modifier,
+                    };
+                    let _ = write!(output, "{diff}");
+                }
+                if cell.fg != previous.fg {
+                    let _ = write!(output, "{}", Fg(cell.fg));
+                }
+                if cell.bg != previous.bg {
+                    let _ = write!(output, "{}", Bg(cell.bg));
+                }
+                #[cfg(feature = "underline-color")]
+                if cell.underline_color != previous.underline_color {
+                    let _ = write!(output, "{}", UnderlineColor(cell.underline_color));
+                }
+                if cell.hyperlink() != previous.hyperlink() {
+                    let _ = write!(output, "{}", Hyperlink(cell.hyperlink()));
+                }
+                output.push_str(cell.symbol());
+                previous = cell.clone();
+            }
+            if previous.hyperlink().is_some() {
+                let _ = write!(output, "{}", Hyperlink(None));
+            }
+            if previous.style() != Cell::default().style() {
+                let _ = write!(output, "{ResetAttributes}");
+            }
+        }
+        output
+    }
+
+    /// Returns the content of the buffer as a standalone HTML `<pre>` element, with the styles
+    /// of the cells as inline CSS.
+    ///
+    /// Named and indexed colors use the xterm palette, on a black background. Blinking is not
+    /// exported, and cells showing an image (whose symbol is an escape sequence) are exported as
+    /// blank cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::{backend::TestBackend, prelude::*, widgets::*};
+    ///
+    /// let mut terminal = Terminal::new(TestBackend::new(20, 3))?;
+    /// let frame = terminal.draw(|frame| {
+    ///     let block = Block::default().borders(Borders::ALL).title("Dashboard");
+    ///     frame.render_widget(block, frame.size());
+    /// })?;
+    /// let html = frame.buffer.to_html();
+    /// assert!(html.starts_with("<pre"));
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut output = format!(
+            "<pre style=\"font-family:monospace;color:{};background-color:{}\">",
+            hex(DEFAULT_FG),
+            hex(DEFAULT_BG)
+        );
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                output.push('\n');
+            }
+            for run in runs(row) {
+                let style = css(run.cell);
+                if let Some(hyperlink) = run.cell.hyperlink() {
+                    output.push_str("<a href=\"");
+                    escape(hyperlink, &mut output);
+                    output.push_str("\">");
+                }
+                if style.is_empty() {
+                    escape(&run.text, &mut output);
+                } else {
+                    let _ = write!(output, "<span style=\"{style}\">");
+                    escape(&run.text, &mut output);
+                    output.push_str("</span>");
+                }
+                if run.cell.hyperlink().is_some() {
+                    output.push_str("</a>");
+                }
+            }
+        }
+        output.push_str("</pre>\n");
+        output
+    }
+
+    /// Returns the content of the buffer as an SVG image of a monospace grid, with cells of 10 by
+    /// 20 pixels.
+    ///
+    /// Named and indexed colors use the xterm palette, on a black background. All the underline
+    /// styles are drawn as a single underline, blinking is not exported, and cells showing an
+    /// image (whose symbol is an escape sequence) are exported as blank cells.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui::{backend::TestBackend, prelude::*, widgets::*};
+    ///
+    /// let mut terminal = Terminal::new(TestBackend::new(40, 10))?;
+    /// let frame = terminal.draw(|frame| {
+    ///     let block = Block::default().borders(Borders::ALL).title("Dashboard");
+    ///     frame.render_widget(block, frame.size());
+    /// })?;
+    /// std::fs::write("dashboard.svg", frame.buffer.to_svg())?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn to_svg(&self) -> String {
+        let width = self.area.width as usize * CELL_WIDTH;
+        let height = self.area.height as usize * CELL_HEIGHT;
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" \
+             font-size=\"{FONT_SIZE}\" xml:space=\"preserve\">\n\
+             <rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>\n",
+            hex(DEFAULT_BG)
+        );
+        let rows: Vec<_> = self.rows().map(runs).collect();
+        for (y, runs) in rows.iter().enumerate() {
+            for run in runs {
+                let (_, bg) = colors(run.cell);
+                if let Some(bg) = bg {
+                    let _ = writeln!(
+                        output,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{CELL_HEIGHT}\" fill=\"{}\"/>",
+                        run.x * CELL_WIDTH,
+                        y * CELL_HEIGHT,
+                        run.width * CELL_WIDTH,
+                        hex(bg)
+                    );
+                }
+            }
+        }
+        for (y, runs) in rows.iter().enumerate() {
+            for run in runs {
+                let modifier = run.cell.modifier;
+                let decorations = decorations(modifier);
+                if (run.text.trim().is_empty() && decorations.is_empty())
+                    || modifier.contains(Modifier::HIDDEN)
+                {
+                    continue;
+                }
+                if let Some(hyperlink) = run.cell.hyperlink() {
+                    output.push_str("<a href=\"");
+                    escape(hyperlink, &mut output);
+                    output.push_str("\">");
+                }
+                let (fg, _) = colors(run.cell);
+                let _ = write!(
+                    output,
+                    "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" \
+                     fill=\"{}\"",
+                    run.x * CELL_WIDTH,
+                    y * CELL_HEIGHT + CELL_HEIGHT * 3 / 4,
+                    run.width * CELL_WIDTH,
+                    hex(fg.unwrap_or(DEFAULT_FG))
+                );
+                if modifier.contains(Modifier::BOLD) {
+                    output.push_str(" font-weight=\"bold\"");
+                }
+                if modifier.contains(Modifier::ITALIC) {
+                    output.push_str(" font-style=\"italic\"");
+                }
+                if !decorations.is_empty() {
+                    let _ = write!(output, " text-decoration=\"{}\"", decorations.join(" "));
+                }
+                output.push('>');
+                escape(&run.text, &mut output);
+                output.push_str("</text>");
+                if run.cell.hyperlink().is_some() {
+                    output.push_str("</a>");
+                }
+                output.push('\n');
+            }
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+
+    /// Returns the rows of the buffer.
+    fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.content.chunks(self.area.width.max(1) as usize)
+    }
+}
+
+/// A run of consecutive cells of a row that have the same style.
+struct Run<'a> {
+    /// The column of the first cell, relative to the start of the row.
+    x: usize,
+    /// The number of columns covered by the run.
+    width: usize,
+    /// The symbols of the cells of the run.
+    text: String,
+    /// The first cell of the run, which has the style of the whole run.
+    cell: &'a Cell,
+}
+
+/// Returns the cells of `row` that are not covered by a preceding wide character.
+fn visible_cells(row: &[Cell]) -> impl Iterator<Item = &Cell> {
+    let mut covered = 0;
+    row.iter().filter(move |cell| {
+        if covered > 0 {
+            covered -= 1;
+            return false;
+        }
+        covered = cell.symbol_width().saturating_sub(1);
+        true
+    })
+}
+
+/// Splits `row` into runs of cells with the same style. The cells covered by a wide character
+/// belong to the run of the wide character.
+fn runs(row: &[Cell]) -> Vec<Run<'_>> {
+    let mut runs: Vec<Run> = vec![];
+    let mut covered = 0;
+    for (x, cell) in row.iter().enumerate() {
+        if covered > 0 {
+            covered -= 1;
+            if let Some(run) = runs.last_mut() {
+                run.width += 1;
+            }
+            continue;
+        }
+        covered = cell.symbol_width().saturating_sub(1);
+        // images can't be exported, only the escape sequence that draws them
+        let symbol = if cell.symbol().starts_with('\x1b') {
+            " "
+        } else {
+            cell.symbol()
+        };
+        match runs.last_mut() {
+            Some(run)
+                if run.cell.style() == cell.style() && run.cell.hyperlink() == cell.hyperlink() =>
+            {
+                run.text.push_str(symbol);
+                run.width += 1;
+            }
+            _ => runs.push(Run {
+                x,
+                width: 1,
+                text: symbol.to_string(),
+                cell,
+            }),
+        }
+    }
+    runs
+}
+
+/// Returns the foreground and background colors the cell is displayed with, or `None` for the
+/// default colors.
+fn colors(cell: &Cell) -> (Option<Rgb>, Option<Rgb>) {
+    let (mut fg, mut bg) = (cell.fg.to_rgb(), cell.bg.to_rgb());
+    if cell.modifier.contains(Modifier::REVERSED) {
+        (fg, bg) = (
+            Some(bg.unwrap_or(DEFAULT_BG)),
+            Some(fg.unwrap_or(DEFAULT_FG)),
+        );
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        // dim text is drawn halfway between its color and the background
+        let (r1, g1, b1) = fg.unwrap_or(DEFAULT_FG);
+        let (r2, g2, b2) = bg.unwrap_or(DEFAULT_BG);
+        let mix = |a: u8, b: u8| ((u16::from(a) + u16::from(b)) / 2) as u8;
+        fg = Some((mix(r1, r2), mix(g1, g2), mix(b1, b2)));
+    }
+    if cell.modifier.contains(Modifier::HIDDEN) {
+        fg = Some(bg.unwrap_or(DEFAULT_BG));
+    }
+    (fg, bg)
+}
+
+/// Returns the CSS `text-decoration-line` values of the modifier.
+fn decorations(modifier: Modifier) -> Vec<&'static str> {
+    let mut decorations = vec![];
+    if !modifier.underline().is_empty() {
+        decorations.push("underline");
+    }
+    if modifier.contains(Modifier::OVERLINED) {
+        decorations.push("overline");
+    }
+    if modifier.contains(Modifier::CROSSED_OUT) {
+        decorations.push("line-through");
+    }
+    decorations
+}
+
+/// Returns the inline CSS style of the cell, which is empty for cells with the default style.
+fn css(cell: &Cell) -> String {
+    let mut style = String::new();
+    let (fg, bg) = colors(cell);
+    if let Some(fg) = fg {
+        let _ = write!(style, "color:{};", hex(fg));
+    }
+    if let Some(bg) = bg {
+        let _ = write!(style, "background-color:{};", hex(bg));
+    }
+    if cell.modifier.contains(Modifier::BOLD) {
+        style.push_str("font-weight:bold;");
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        style.push_str("font-style:italic;");
+    }
+    let decorations = decorations(cell.modifier);
+    if !decorations.is_empty() {
+        let _ = write!(style, "text-decoration-line:{};", decorations.join(" "));
+    }
+    let underline_style = match cell.modifier.underline() {
+        Modifier::DOUBLE_UNDERLINED => "double",
+        Modifier::CURLY_UNDERLINED => "wavy",
+        Modifier::DOTTED_UNDERLINED => "dotted",
+        Modifier::DASHED_UNDERLINED => "dashed",
+        _ => "",
+    };
+    if !underline_style.is_empty() {
+        let _ = write!(style, "text-decoration-style:{underline_style};");
+    }
+    #[cfg(feature = "underline-color")]
+    if let Some(color) = cell.underline_color.to_rgb() {
+        if !cell.modifier.underline().is_empty() {
+            let _ = write!(style, "text-decoration-color:{};", hex(color));
+        }
+    }
+    style
+}
+
+/// Formats an RGB color as a CSS hexadecimal color.
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Appends `text` to `output`, escaping the characters that have a meaning in HTML and XML.
+fn escape(text: &str, output: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            c if c.is_control() => {}
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::Rect,
+        style::{Color, Style, Stylize},
+    };
+
+    #[test]
+    fn ansi_string() {
+        let mut buffer = Buffer::with_lines(vec!["ab cd", "コ   "]);
+        buffer.set_style(Rect::new(1, 0, 2, 1), Style::new().red().bold());
+        buffer
+            .get_mut(3, 1)
+            .set_hyperlink(Some("https://ratatui.rs"));
+        assert_eq!(
+            buffer.to_ansi_string(),
+            "a\x1b[1m\x1b[31mb \x1b[22m\x1b[39mcd\n\
+             コ \x1b]8;;https://ratatui.rs\x1b\\ \x1b]8;;\x1b\\ "
+        );
+    }
+
+    #[test]
+    fn html() {
+        let mut buffer = Buffer::with_lines(vec!["a<b", "コ&"]);
+        buffer.set_style(Rect::new(1, 0, 2, 1), Style::new().red().on_blue().italic());
+        buffer
+            .get_mut(2, 1)
+            .set_hyperlink(Some("https://ratatui.rs/?a=\"b\""));
+        assert_eq!(
+            buffer.to_html(),
+            "<pre style=\"font-family:monospace;color:#e5e5e5;background-color:#000000\">a\
+             <span style=\"color:#cd0000;background-color:#0000ee;font-style:italic;\">&lt;b</span>\n\
+             コ<a href=\"https://ratatui.rs/?a=&quot;b&quot;\">&amp;</a></pre>\n"
+        );
+    }
+
+    #[test]
+    fn css_modifiers() {
+        let mut cell = Cell::default();
+        cell.set_style(Style::new().reversed().underlined().crossed_out());
+        assert_eq!(
+            css(&cell),
+            "color:#000000;background-color:#e5e5e5;\
+             text-decoration-line:underline line-through;"
+        );
+
+        let mut cell = Cell::default();
+        cell.set_style(Style::new().fg(Color::White).dim().curly_underlined());
+        assert_eq!(
+            css(&cell),
+            "color:#7f7f7f;text-decoration-line:underline;text-decoration-style:wavy;"
+        );
+
+        let mut cell = Cell::default();
+        cell.set_style(Style::new().bg(Color::Indexed(21)).hidden().bold());
+        assert_eq!(
+            css(&cell),
+            "color:#0000ff;background-color:#0000ff;font-weight:bold;"
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let mut buffer = Buffer::with_lines(vec!["ab  ", "コ <"]);
+        buffer.set_style(Rect::new(1, 0, 2, 1), Style::new().on_green().bold());
+        assert_eq!(
+            buffer.to_svg(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"40\" height=\"40\" \
+             viewBox=\"0 0 40 40\" font-family=\"monospace\" font-size=\"16\" \
+             xml:space=\"preserve\">\n\
+             <rect width=\"40\" height=\"40\" fill=\"#000000\"/>\n\
+             <rect x=\"10\" y=\"0\" width=\"20\" height=\"20\" fill=\"#00cd00\"/>\n\
+             <text x=\"0\" y=\"15\" textLength=\"10\" lengthAdjust=\"spacingAndGlyphs\" \
+             fill=\"#e5e5e5\">a</text>\n\
+             <text x=\"10\" y=\"15\" textLength=\"20\" lengthAdjust=\"spacingAndGlyphs\" \
+             fill=\"#e5e5e5\" font-weight=\"bold\">b </text>\n\
+             <text x=\"0\" y=\"35\" textLength=\"40\" lengthAdjust=\"spacingAndGlyphs\" \
+             fill=\"#e5e5e5\">コ &lt;</text>\n\
+             </svg>\n"
+        );
+    }
+
+    #[test]
+    fn images_are_exported_as_blank_cells() {
+        let mut buffer = Buffer::with_lines(vec!["ab"]);
+        buffer.get_mut(0, 0).set_symbol("\x1b_Gf=100;AAAA\x1b\\");
+        assert!(buffer.to_html().contains("> b</pre>"));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+/// A [`Buffer`] drawn over other buffers, used to display popups and floating windows without
+/// hiding everything behind them.
+///
+/// Layers are rendered to like any other buffer and then drawn over the content of a frame with
+/// [`Frame::render_layer`], or over any buffer with [`Layer::composite`]. Layers with a higher
+/// [`z_index`](Layer::z_index) are drawn over the ones with a lower one. The content of the frame
+/// itself is at z-index 0, below the layers that have the same z-index.
+///
+/// Unless the layer is [opaque](Layer::opaque), its cells are transparent where nothing was
+/// drawn:
+///
+/// - cells with a blank symbol and a [`Color::Reset`] background let the cell below show through,
+/// - other cells with a [`Color::Reset`] background keep the background of the cell below.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui::{buffer::Layer, prelude::*, widgets::*};
+///
+/// # let mut terminal = Terminal::new(backend::TestBackend::new(20, 5))?;
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("In the background"), frame.size());
+///     let area = Rect::new(4, 1, 12, 3);
+///     let mut popup = Layer::new(area).z_index(1);
+///     Block::default()
+///         .borders(Borders::ALL)
+///         .render(area, popup.buffer_mut());
+///     frame.render_layer(popup);
+/// })?;
+/// # std::io::Result::Ok(())
+/// ```
+///
+/// [`Frame::render_layer`]: crate::terminal::Frame::render_layer
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Layer {
+    buffer: Buffer,
+    z_index: i32,
+    offset: (i32, i32),
+    opaque: bool,
+}
+
+impl Layer {
+    /// Creates a transparent layer covering `area`, at z-index 0.
+    pub fn new(area: Rect) -> Layer {
+        Layer::from(Buffer::empty(area))
+    }
+
+    /// Sets the z-index of the layer.
+    ///
+    /// Layers with a higher z-index are drawn over the ones with a lower one. Layers with the same
+    /// z-index are drawn in the order they were added.
+    pub fn z_index(mut self, z_index: i32) -> Layer {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Moves the layer by `x` columns and `y` rows from the area of its buffer when it is drawn.
+    ///
+    /// This moves a floating window without rendering it again. The parts of the layer moved
+    /// outside of the buffer it is drawn over are not drawn.
+    pub fn offset(mut self, x: i32, y: i32) -> Layer {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Sets whether the layer hides everything below it, including the cells where nothing was
+    /// drawn.
+    pub fn opaque(mut self, opaque: bool) -> Layer {
+        self.opaque = opaque;
+        self
+    }
+
+    /// Returns the content of the layer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the content of the layer to render to it.
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.buffer
+    }
+
+    /// Draws the layer over `target`, at the position of the area of the layer's buffer moved by
+    /// the offset of the layer.
+    ///
+    /// Wide characters of `target` that are partly covered by the layer are replaced with a blank
+    /// cell, and wide characters of the layer are only drawn when they fit in `target`.
+    pub fn composite(&self, target: &mut Buffer) {
+        let source = &self.buffer;
+        let left = i32::from(source.area.x) + self.offset.0;
+        let top = i32::from(source.area.y) + self.offset.1;
+        let x_start = left.max(i32::from(target.area.left()));
+        let x_end = (left + i32::from(source.area.width)).min(i32::from(target.area.right()));
+        let y_start = top.max(i32::from(target.area.top()));
+        let y_end = (top + i32::from(source.area.height)).min(i32::from(target.area.bottom()));
+        for y in y_start..y_end {
+            // number of cells still covered by the last wide character drawn from the layer
+            let mut covered = 0;
+            let mut drew_previous = false;
+            for x in x_start..x_end {
+                let cell = source.get((x - self.offset.0) as u16, (y - self.offset.1) as u16);
+                let (x, y) = (x as u16, y as u16);
+                if covered == 0 && !self.opaque && is_transparent(cell) {
+                    drew_previous = false;
+                    continue;
+                }
+                // a wide character of the target starting before this cell would be cut in half
+                if !drew_previous && x > target.area.left() {
+                    let previous = target.get_mut(x - 1, y);
+                    if previous.symbol_width() > 1 {
+                        previous.set_symbol(" ");
+                    }
+                }
+                let below = target.get_mut(x, y);
+                let bg = below.bg;
+                *below = cell.clone();
+                if !self.opaque && below.bg == Color::Reset {
+                    below.bg = bg;
+                }
+                if covered > 0 {
+                    covered -= 1;
+                } else if i32::from(x) + cell.symbol_width() as i32 > x_end {
+                    // the wide character doesn't fit in the part of the layer that is drawn
+                    below.set_symbol(" ");
+                } else {
+                    covered = cell.symbol_width().saturating_sub(1);
+                }
+                drew_previous = true;
+            }
+        }
+    }
+}
+
+impl From<Buffer> for Layer {
+    /// Creates a transparent layer at z-index 0 from the content of `buffer`.
+    fn from(buffer: Buffer) -> Layer {
+        Layer {
+            buffer,
+            z_index: 0,
+            offset: (0, 0),
+            opaque: false,
+        }
+    }
+}
+
+/// Draws `layers` over `buffer` by increasing z-index. The content of `buffer` is at z-index 0,
+/// below the layers that have the same z-index.
+pub(crate) fn composite_layers(buffer: &mut Buffer, mut layers: Vec<Layer>) {
+    // the sort is stable, so layers with the same z-index stay in the order they were added
+    layers.sort_by_key(|layer| layer.z_index);
+    let below = layers.partition_point(|layer| layer.z_index < 0);
+    if below > 0 {
+        let base = Layer::from(std::mem::replace(buffer, Buffer::empty(buffer.area)));
+        for layer in &layers[..below] {
+            layer.composite(buffer);
+        }
+        base.composite(buffer);
+    }
+    for layer in &layers[below..] {
+        layer.composite(buffer);
+    }
+}
+
+/// Returns whether nothing was drawn in `cell`, in which case the cell below it shows through.
+fn is_transparent(cell: &Cell) -> bool {
+    cell.symbol() == " "
+        && cell.bg == Color::Reset
+        && !cell
+            .modifier
+            .intersects(Modifier::REVERSED | Modifier::CROSSED_OUT | Modifier::OVERLINED)
+        && cell.modifier.underline().is_empty()
+        && cell.hyperlink().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_buffer_eq,
+        style::{Style, Stylize},
+    };
+
+    fn layer(area: Rect, lines: &[&str]) -> Layer {
+        let mut layer = Layer::new(area);
+        for (y, line) in lines.iter().enumerate() {
+            layer
+                .buffer_mut()
+                .set_string(area.x, area.y + y as u16, line, Style::new());
+        }
+        layer
+    }
+
+    #[test]
+    fn blank_cells_are_transparent() {
+        let mut buffer = Buffer::with_lines(vec!["abcde", "fghij"]);
+        layer(Rect::new(1, 0, 3, 2), &[" x ", "y z"]).composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["abxde", "fyhzj"]));
+    }
+
+    #[test]
+    fn reset_background_keeps_the_background_below() {
+        let mut buffer = Buffer::with_lines(vec!["abc"]);
+        buffer.set_style(buffer.area, Style::new().bg(Color::Blue));
+        let mut layer = layer(Rect::new(0, 0, 3, 1), &["x  "]);
+        layer.buffer_mut().get_mut(0, 0).set_fg(Color::Red);
+        layer.buffer_mut().get_mut(2, 0).set_bg(Color::Green);
+        layer.composite(&mut buffer);
+
+        let mut expected = Buffer::with_lines(vec!["xb "]);
+        expected.set_style(expected.area, Style::new().bg(Color::Blue));
+        expected.get_mut(0, 0).set_fg(Color::Red);
+        expected.get_mut(2, 0).set_bg(Color::Green);
+        assert_buffer_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn visible_modifiers_are_not_transparent() {
+        let mut buffer = Buffer::with_lines(vec!["abc"]);
+        let mut layer = Layer::new(Rect::new(0, 0, 3, 1));
+        layer
+            .buffer_mut()
+            .get_mut(1, 0)
+            .set_style(Style::new().reversed());
+        layer.composite(&mut buffer);
+        let mut expected = Buffer::with_lines(vec!["a c"]);
+        expected.get_mut(1, 0).set_style(Style::new().reversed());
+        assert_buffer_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn opaque_layer_hides_everything_below() {
+        let mut buffer = Buffer::with_lines(vec!["abcde"]);
+        buffer.set_style(buffer.area, Style::new().bg(Color::Blue));
+        layer(Rect::new(1, 0, 3, 1), &[" x "])
+            .opaque(true)
+            .composite(&mut buffer);
+        let mut expected = Buffer::with_lines(vec!["a x e"]);
+        expected.set_style(Rect::new(0, 0, 1, 1), Style::new().bg(Color::Blue));
+        expected.set_style(Rect::new(4, 0, 1, 1), Style::new().bg(Color::Blue));
+        assert_buffer_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn offset_moves_and_clips_the_layer() {
+        let mut buffer = Buffer::with_lines(vec!["abc", "def"]);
+        layer(Rect::new(0, 0, 2, 2), &["xy", "zw"])
+            .offset(-1, 1)
+            .composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["abc", "yef"]));
+
+        let mut buffer = Buffer::with_lines(vec!["abc", "def"]);
+        layer(Rect::new(0, 0, 2, 2), &["xy", "zw"])
+            .offset(2, 5)
+            .composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["abc", "def"]));
+    }
+
+    #[test]
+    fn wide_characters_are_not_cut_in_half() {
+        // the wide character below is partly covered
+        let mut buffer = Buffer::with_lines(vec!["コab"]);
+        layer(Rect::new(1, 0, 2, 1), &["x "]).composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec![" xab"]));
+
+        // the wide character of the layer doesn't fit
+        let mut buffer = Buffer::with_lines(vec!["abc"]);
+        layer(Rect::new(0, 0, 4, 1), &["xyコ"]).composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["xy "]));
+
+        // the wide character of the layer covers the next cell
+        let mut buffer = Buffer::with_lines(vec!["abc"]);
+        layer(Rect::new(0, 0, 3, 1), &["コ"]).composite(&mut buffer);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["コc"]));
+    }
+
+    #[test]
+    fn layers_are_drawn_by_z_index() {
+        let mut buffer = Buffer::with_lines(vec!["a   "]);
+        let layers = vec![
+            layer(Rect::new(0, 0, 4, 1), &["  1 "]).z_index(1),
+            layer(Rect::new(0, 0, 4, 1), &[" 00 "]),
+            layer(Rect::new(0, 0, 4, 1), &["bbbb"]).z_index(-1),
+        ];
+        composite_layers(&mut buffer, layers);
+        assert_buffer_eq!(buffer, Buffer::with_lines(vec!["a01b"]));
+    }
+}
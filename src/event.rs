@@ -0,0 +1,192 @@
+//! Backend agnostic input events.
+//!
+//! Each backend reads input in its own format. This module provides a common [`Event`] model so
+//! that input handling can be written once and work with every backend. Events are read with
+//! [`Terminal::poll_event`] and [`Terminal::read_event`], and the native events of each backend
+//! can be converted with [`TryFrom`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use ratatui::{
+//!     event::{Event, KeyCode},
+//!     prelude::*,
+//! };
+//!
+//! # fn run<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), B::Error> {
+//! loop {
+//!     if terminal.poll_event(Duration::from_millis(250))? {
+//!         if let Event::Key(key) = terminal.read_event()? {
+//!             if key.code == KeyCode::Char('q') {
+//!                 return Ok(());
+//!             }
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`Terminal::poll_event`]: crate::Terminal::poll_event
+//! [`Terminal::read_event`]: crate::Terminal::read_event
+use bitflags::bitflags;
+
+/// An input event.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    /// The terminal gained focus
+    FocusGained,
+    /// The terminal lost focus
+    FocusLost,
+    /// A key was pressed, repeated or released
+    Key(KeyEvent),
+    /// A mouse button was pressed or released, the mouse moved or the wheel scrolled
+    Mouse(MouseEvent),
+    /// Text was pasted in the terminal (requires bracketed paste to be enabled)
+    Paste(String),
+    /// The terminal was resized to the given number of columns and rows
+    Resize(u16, u16),
+}
+
+/// A keyboard event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyEvent {
+    /// The key
+    pub code: KeyCode,
+    /// The modifiers held down when the key was pressed
+    pub modifiers: KeyModifiers,
+    /// Whether the key was pressed, repeated or released
+    ///
+    /// Most terminals only report key presses.
+    pub kind: KeyEventKind,
+}
+
+impl KeyEvent {
+    /// Creates a key press event.
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+        }
+    }
+}
+
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> Self {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+}
+
+/// A key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyCode {
+    Backspace,
+    Enter,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Tab,
+    /// Shift + Tab
+    BackTab,
+    Delete,
+    Insert,
+    /// A function key, e.g. `F(1)` for F1
+    F(u8),
+    /// A character
+    ///
+    /// Characters are reported as typed, e.g. `Char('A')` with the [`KeyModifiers::SHIFT`]
+    /// modifier.
+    Char(char),
+    Null,
+    Esc,
+    CapsLock,
+    ScrollLock,
+    NumLock,
+    PrintScreen,
+    Pause,
+    Menu,
+    /// The "Begin" key (often mapped to the 5 key when Num Lock is off)
+    KeypadBegin,
+}
+
+/// The kind of a [`KeyEvent`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyEventKind {
+    #[default]
+    Press,
+    Repeat,
+    Release,
+}
+
+bitflags! {
+    /// Modifier keys held down during a [`KeyEvent`] or a [`MouseEvent`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::event::KeyModifiers;
+    ///
+    /// let modifiers = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+    /// assert!(modifiers.contains(KeyModifiers::CONTROL));
+    /// ```
+    #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct KeyModifiers: u8 {
+        const SHIFT   = 0b0000_0001;
+        const CONTROL = 0b0000_0010;
+        const ALT     = 0b0000_0100;
+        const SUPER   = 0b0000_1000;
+    }
+}
+
+/// A mouse event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MouseEvent {
+    /// What happened
+    pub kind: MouseEventKind,
+    /// The column of the mouse cursor, starting at 0
+    pub column: u16,
+    /// The row of the mouse cursor, starting at 0
+    pub row: u16,
+    /// The modifiers held down during the event
+    pub modifiers: KeyModifiers,
+}
+
+/// The kind of a [`MouseEvent`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MouseEventKind {
+    /// A button was pressed
+    Down(MouseButton),
+    /// A button was released
+    Up(MouseButton),
+    /// The mouse moved while a button was held down
+    Drag(MouseButton),
+    /// The mouse moved without any button held down
+    Moved,
+    ScrollDown,
+    ScrollUp,
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
@@ -89,8 +89,10 @@
 //! [Termwiz].
 //!
 //! Most applications should enter the Alternate Screen when starting and leave it when exiting and
-//! also enable raw mode to disable line buffering and enable reading key events. See the [`backend`
-//! module] and the [Backends] section of the [Ratatui Website] for more info.
+//! also enable raw mode to disable line buffering and enable reading key events. A
+//! [`TerminalSession`] does both for as long as it lives and restores the terminal when it is
+//! dropped, even on panic. See the [`backend` module] and the [Backends] section of the [Ratatui
+//! Website] for more info.
 //!
 //! ### Drawing the UI
 //!
@@ -110,17 +112,11 @@
 //!
 //! ```rust,no_run
 //! use std::io::{self, stdout};
-//! use crossterm::{
-//!     event::{self, Event, KeyCode},
-//!     ExecutableCommand,
-//!     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen}
-//! };
+//! use crossterm::event::{self, Event, KeyCode};
 //! use ratatui::{prelude::*, widgets::*};
 //!
 //! fn main() -> io::Result<()> {
-//!     enable_raw_mode()?;
-//!     stdout().execute(EnterAlternateScreen)?;
-//!     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+//!     let mut terminal = TerminalSession::new(CrosstermBackend::new(stdout()))?;
 //!
 //!     let mut should_quit = false;
 //!     while !should_quit {
@@ -128,8 +124,7 @@
 //!         should_quit = handle_events()?;
 //!     }
 //!
-//!     disable_raw_mode()?;
-//!     stdout().execute(LeaveAlternateScreen)?;
+//!     terminal.restore()?;
 //!     Ok(())
 //! }
 //!
@@ -349,6 +344,7 @@
 
 pub mod backend;
 pub mod buffer;
+pub mod event;
 pub mod layout;
 pub mod style;
 pub mod symbols;
@@ -357,6 +353,9 @@ pub mod text;
 pub mod widgets;
 
 #[doc(inline)]
-pub use self::terminal::{CompletedFrame, Frame, Terminal, TerminalOptions, Viewport};
+pub use self::terminal::{
+    CompletedFrame, Frame, FrameStats, SessionOptions, Terminal, TerminalOptions, TerminalSession,
+    Viewport,
+};
 
 pub mod prelude;
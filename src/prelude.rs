@@ -29,6 +29,9 @@ pub use crate::{
     layout::{self, Alignment, Constraint, Corner, Direction, Layout, Margin, Rect},
     style::{self, Color, Modifier, Style, Styled, Stylize},
     symbols::{self, Marker},
-    terminal::{CompletedFrame, Frame, Terminal, TerminalOptions, Viewport},
+    terminal::{
+        CompletedFrame, Frame, FrameStats, SessionOptions, Terminal, TerminalOptions,
+        TerminalSession, Viewport,
+    },
     text::{self, Line, Masked, Span, Text},
 };
@@ -67,13 +67,18 @@ use bitflags::bitflags;
 mod stylize;
 pub use stylize::{Styled, Stylize};
 mod color;
-pub use color::Color;
+pub use color::{Color, ColorDepth};
 
 bitflags! {
     /// Modifier changes the way a piece of text is displayed.
     ///
     /// They are bitflags so they can easily be composed.
     ///
+    /// A terminal draws at most one kind of underline per cell. When several underline modifiers
+    /// are set, the curly, dotted, dashed, double and single underlines take precedence in that
+    /// order. The extended underline styles and [`OVERLINED`](Modifier::OVERLINED) are not
+    /// supported by every terminal, which usually draw a single underline or ignore them instead.
+    ///
     /// ## Examples
     ///
     /// ```rust
@@ -93,6 +98,31 @@ bitflags! {
         const REVERSED          = 0b0000_0100_0000;
         const HIDDEN            = 0b0000_1000_0000;
         const CROSSED_OUT       = 0b0001_0000_0000;
+        const DOUBLE_UNDERLINED = 0b0010_0000_0000;
+        const CURLY_UNDERLINED  = 0b0100_0000_0000;
+        const DOTTED_UNDERLINED = 0b1000_0000_0000;
+        const DASHED_UNDERLINED = 0b0001_0000_0000_0000;
+        const OVERLINED         = 0b0010_0000_0000_0000;
+    }
+}
+
+impl Modifier {
+    /// The underline modifiers, by order of precedence.
+    pub(crate) const UNDERLINES: [Modifier; 5] = [
+        Modifier::CURLY_UNDERLINED,
+        Modifier::DOTTED_UNDERLINED,
+        Modifier::DASHED_UNDERLINED,
+        Modifier::DOUBLE_UNDERLINED,
+        Modifier::UNDERLINED,
+    ];
+
+    /// Returns the underline modifier that is drawn for these modifiers, or an empty modifier if
+    /// the text is not underlined.
+    pub(crate) fn underline(self) -> Modifier {
+        Modifier::UNDERLINES
+            .into_iter()
+            .find(|underline| self.contains(*underline))
+            .unwrap_or_else(Modifier::empty)
     }
 }
 
@@ -421,6 +451,11 @@ mod tests {
             Modifier::REVERSED,
             Modifier::HIDDEN,
             Modifier::CROSSED_OUT,
+            Modifier::DOUBLE_UNDERLINED,
+            Modifier::CURLY_UNDERLINED,
+            Modifier::DOTTED_UNDERLINED,
+            Modifier::DASHED_UNDERLINED,
+            Modifier::OVERLINED,
         ];
 
         let mut buffer = Buffer::empty(Rect::new(0, 0, 1, 1));
@@ -448,13 +483,35 @@ mod tests {
         assert_eq!(format!("{:?}", Modifier::REVERSED), "REVERSED");
         assert_eq!(format!("{:?}", Modifier::HIDDEN), "HIDDEN");
         assert_eq!(format!("{:?}", Modifier::CROSSED_OUT), "CROSSED_OUT");
+        assert_eq!(
+            format!("{:?}", Modifier::CURLY_UNDERLINED),
+            "CURLY_UNDERLINED"
+        );
+        assert_eq!(format!("{:?}", Modifier::OVERLINED), "OVERLINED");
         assert_eq!(
             format!("{:?}", Modifier::BOLD | Modifier::DIM),
             "BOLD | DIM"
         );
         assert_eq!(
             format!("{:?}", Modifier::all()),
-            "BOLD | DIM | ITALIC | UNDERLINED | SLOW_BLINK | RAPID_BLINK | REVERSED | HIDDEN | CROSSED_OUT"
+            "BOLD | DIM | ITALIC | UNDERLINED | SLOW_BLINK | RAPID_BLINK | REVERSED | HIDDEN | CROSSED_OUT | DOUBLE_UNDERLINED | CURLY_UNDERLINED | DOTTED_UNDERLINED | DASHED_UNDERLINED | OVERLINED"
+        );
+    }
+
+    #[test]
+    fn modifier_underline() {
+        assert_eq!(Modifier::BOLD.underline(), Modifier::empty());
+        assert_eq!(
+            (Modifier::BOLD | Modifier::UNDERLINED).underline(),
+            Modifier::UNDERLINED
+        );
+        assert_eq!(
+            (Modifier::UNDERLINED | Modifier::CURLY_UNDERLINED).underline(),
+            Modifier::CURLY_UNDERLINED
+        );
+        assert_eq!(
+            (Modifier::DOUBLE_UNDERLINED | Modifier::DASHED_UNDERLINED).underline(),
+            Modifier::DASHED_UNDERLINED
         );
     }
 
@@ -589,6 +646,26 @@ mod tests {
             Style::new().crossed_out(),
             Style::new().add_modifier(Modifier::CROSSED_OUT)
         );
+        assert_eq!(
+            Style::new().double_underlined(),
+            Style::new().add_modifier(Modifier::DOUBLE_UNDERLINED)
+        );
+        assert_eq!(
+            Style::new().curly_underlined(),
+            Style::new().add_modifier(Modifier::CURLY_UNDERLINED)
+        );
+        assert_eq!(
+            Style::new().dotted_underlined(),
+            Style::new().add_modifier(Modifier::DOTTED_UNDERLINED)
+        );
+        assert_eq!(
+            Style::new().dashed_underlined(),
+            Style::new().add_modifier(Modifier::DASHED_UNDERLINED)
+        );
+        assert_eq!(
+            Style::new().overlined(),
+            Style::new().add_modifier(Modifier::OVERLINED)
+        );
 
         // Remove Modifiers
         assert_eq!(
@@ -627,6 +704,14 @@ mod tests {
             Style::new().not_crossed_out(),
             Style::new().remove_modifier(Modifier::CROSSED_OUT)
         );
+        assert_eq!(
+            Style::new().not_curly_underlined(),
+            Style::new().remove_modifier(Modifier::CURLY_UNDERLINED)
+        );
+        assert_eq!(
+            Style::new().not_overlined(),
+            Style::new().remove_modifier(Modifier::OVERLINED)
+        );
 
         // reset
         assert_eq!(Style::new().reset(), Style::reset());
@@ -112,6 +112,8 @@ pub enum Color {
     /// fallback to the default text color. Crossterm and Termion do not have this capability and
     /// the display will be unpredictable (e.g. Terminal.app may display glitched blinking text).
     /// See <https://github.com/ratatui-org/ratatui/issues/475> for an example of this problem.
+    /// Set a [`ColorDepth`] on the terminal to convert these colors to the nearest color the
+    /// terminal supports.
     ///
     /// See also: <https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit>
     ///
@@ -245,6 +247,194 @@ impl Display for Color {
     }
 }
 
+/// The range of colors a terminal is able to display.
+///
+/// Terminals that don't support 24-bit color display [`Color::Rgb`] unpredictably, and terminals
+/// limited to the 16 ANSI colors may not understand [`Color::Indexed`] either. Setting
+/// [`TerminalOptions::color_depth`] makes the [`Terminal`] convert these colors to the nearest
+/// color the terminal can display before they are passed to the backend.
+///
+/// The depth can be detected from the environment with [`ColorDepth::detect`] or set by hand.
+///
+/// # Example
+///
+/// ```
+/// use ratatui::{prelude::*, style::ColorDepth};
+///
+/// assert_eq!(Color::Rgb(255, 0, 0).downsample(ColorDepth::Palette256), Color::Indexed(196));
+/// assert_eq!(Color::Rgb(250, 10, 10).downsample(ColorDepth::Ansi16), Color::LightRed);
+/// ```
+///
+/// [`TerminalOptions::color_depth`]: crate::terminal::TerminalOptions::color_depth
+/// [`Terminal`]: crate::terminal::Terminal
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ColorDepth {
+    /// 24-bit true color. All colors are passed to the backend unchanged.
+    #[default]
+    TrueColor,
+    /// The 256 color palette. [`Color::Rgb`] is converted to the nearest [`Color::Indexed`] color.
+    Palette256,
+    /// The 16 ANSI colors. [`Color::Rgb`] and [`Color::Indexed`] are converted to the nearest
+    /// named color.
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Detects the color depth of the terminal from the `COLORTERM` and `TERM` environment
+    /// variables.
+    ///
+    /// `COLORTERM=truecolor` or `COLORTERM=24bit` indicate true color support, a `TERM` containing
+    /// `256color` indicates the 256 color palette and any other `TERM` falls back to the 16 ANSI
+    /// colors. On Windows, where `TERM` is usually not set, true color is assumed.
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        Self::from_env(colorterm.as_deref(), term.as_deref())
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        match term {
+            Some(term) if term.contains("truecolor") || term.contains("24bit") => {
+                ColorDepth::TrueColor
+            }
+            Some(term) if term.ends_with("-direct") => ColorDepth::TrueColor,
+            Some(term) if term.contains("256color") => ColorDepth::Palette256,
+            Some(_) => ColorDepth::Ansi16,
+            None if cfg!(windows) => ColorDepth::TrueColor,
+            None => ColorDepth::Ansi16,
+        }
+    }
+}
+
+/// The RGB values of the 16 ANSI colors, as used by xterm.
+const ANSI_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The channel values used by the 6x6x6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+    /// Converts the color to the nearest color that can be displayed with the given
+    /// [`ColorDepth`].
+    ///
+    /// Named colors and [`Color::Reset`] are always returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::{prelude::*, style::ColorDepth};
+    ///
+    /// let color = Color::Rgb(0, 0, 0);
+    /// assert_eq!(color.downsample(ColorDepth::TrueColor), Color::Rgb(0, 0, 0));
+    /// assert_eq!(color.downsample(ColorDepth::Palette256), Color::Indexed(16));
+    /// assert_eq!(color.downsample(ColorDepth::Ansi16), Color::Black);
+    /// ```
+    pub fn downsample(self, depth: ColorDepth) -> Color {
+        match (self, depth) {
+            (_, ColorDepth::TrueColor) => self,
+            (Color::Rgb(r, g, b), ColorDepth::Palette256) => {
+                Color::Indexed(nearest_indexed(r, g, b))
+            }
+            (Color::Indexed(i), ColorDepth::Ansi16) if i < 16 => ANSI_COLORS[i as usize].0,
+            (Color::Indexed(i), ColorDepth::Ansi16) => {
+                let (r, g, b) = indexed_to_rgb(i);
+                nearest_ansi(r, g, b)
+            }
+            (Color::Rgb(r, g, b), ColorDepth::Ansi16) => nearest_ansi(r, g, b),
+            _ => self,
+        }
+    }
+}
+
+impl Color {
+    /// Returns the RGB value of the color, using the xterm values for named and indexed colors, or
+    /// `None` for [`Color::Reset`].
+    pub(crate) fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Reset => None,
+            Color::Indexed(i) => Some(indexed_to_rgb(i)),
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+            named => ANSI_COLORS
+                .iter()
+                .find(|(color, _)| *color == named)
+                .map(|(_, rgb)| *rgb),
+        }
+    }
+}
+
+/// Squared euclidean distance between two RGB colors.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = r1.abs_diff(r2) as u32;
+    let dg = g1.abs_diff(g2) as u32;
+    let db = b1.abs_diff(b2) as u32;
+    dr * dr + dg * dg + db * db
+}
+
+/// Returns the RGB value of an entry in the 256 color palette.
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_COLORS[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// Returns the index of the entry in the color cube or grayscale ramp of the 256 color palette
+/// that is nearest to the given RGB color. The first 16 entries are skipped as their values vary
+/// between terminals.
+fn nearest_indexed(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(value))
+            .unwrap_or_default() as u8
+    };
+    let cube = 16 + 36 * nearest_level(r) + 6 * nearest_level(g) + nearest_level(b);
+    let average = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23);
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|&index| distance(indexed_to_rgb(index), (r, g, b)))
+        .unwrap_or(cube)
+}
+
+/// Returns the named color that is nearest to the given RGB color.
+fn nearest_ansi(r: u8, g: u8, b: u8) -> Color {
+    ANSI_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _)| *color)
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -359,4 +549,92 @@ mod tests {
         assert_eq!(format!("{}", Color::Rgb(255, 0, 0)), "#FF0000");
         assert_eq!(format!("{}", Color::Reset), "Reset");
     }
+
+    #[test]
+    fn color_depth_from_env() {
+        use ColorDepth::*;
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), Some("xterm")),
+            TrueColor
+        );
+        assert_eq!(ColorDepth::from_env(Some("24bit"), None), TrueColor);
+        assert_eq!(ColorDepth::from_env(None, Some("xterm-direct")), TrueColor);
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-256color")),
+            Palette256
+        );
+        assert_eq!(
+            ColorDepth::from_env(Some("yes"), Some("screen-256color")),
+            Palette256
+        );
+        assert_eq!(ColorDepth::from_env(None, Some("xterm")), Ansi16);
+        assert_eq!(ColorDepth::from_env(None, Some("linux")), Ansi16);
+    }
+
+    #[test]
+    fn downsample_true_color() {
+        let colors = [
+            Color::Reset,
+            Color::Red,
+            Color::Rgb(1, 2, 3),
+            Color::Indexed(200),
+        ];
+        for color in colors {
+            assert_eq!(color.downsample(ColorDepth::TrueColor), color);
+        }
+    }
+
+    #[test]
+    fn downsample_palette256() {
+        let depth = ColorDepth::Palette256;
+        assert_eq!(Color::Rgb(0, 0, 0).downsample(depth), Color::Indexed(16));
+        assert_eq!(
+            Color::Rgb(255, 255, 255).downsample(depth),
+            Color::Indexed(231)
+        );
+        assert_eq!(Color::Rgb(255, 0, 0).downsample(depth), Color::Indexed(196));
+        assert_eq!(
+            Color::Rgb(0, 140, 210).downsample(depth),
+            Color::Indexed(32)
+        );
+        // grays prefer the grayscale ramp over the color cube
+        assert_eq!(
+            Color::Rgb(128, 128, 128).downsample(depth),
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            Color::Rgb(30, 30, 30).downsample(depth),
+            Color::Indexed(234)
+        );
+        // named and indexed colors are left alone
+        assert_eq!(Color::Indexed(42).downsample(depth), Color::Indexed(42));
+        assert_eq!(Color::Blue.downsample(depth), Color::Blue);
+        assert_eq!(Color::Reset.downsample(depth), Color::Reset);
+    }
+
+    #[test]
+    fn downsample_ansi16() {
+        let depth = ColorDepth::Ansi16;
+        assert_eq!(Color::Rgb(0, 0, 0).downsample(depth), Color::Black);
+        assert_eq!(Color::Rgb(255, 255, 255).downsample(depth), Color::White);
+        assert_eq!(Color::Rgb(200, 10, 20).downsample(depth), Color::Red);
+        assert_eq!(Color::Rgb(250, 80, 80).downsample(depth), Color::LightRed);
+        assert_eq!(Color::Rgb(120, 120, 130).downsample(depth), Color::DarkGray);
+        assert_eq!(Color::Indexed(1).downsample(depth), Color::Red);
+        assert_eq!(Color::Indexed(15).downsample(depth), Color::White);
+        assert_eq!(Color::Indexed(196).downsample(depth), Color::LightRed);
+        assert_eq!(Color::Indexed(232).downsample(depth), Color::Black);
+        assert_eq!(Color::Green.downsample(depth), Color::Green);
+        assert_eq!(Color::Reset.downsample(depth), Color::Reset);
+    }
+
+    #[test]
+    fn to_rgb() {
+        assert_eq!(Color::Reset.to_rgb(), None);
+        assert_eq!(Color::LightBlue.to_rgb(), Some((92, 92, 255)));
+        assert_eq!(Color::Indexed(1).to_rgb(), Some((205, 0, 0)));
+        assert_eq!(Color::Indexed(21).to_rgb(), Some((0, 0, 255)));
+        assert_eq!(Color::Indexed(244).to_rgb(), Some((128, 128, 128)));
+        assert_eq!(Color::Rgb(1, 2, 3).to_rgb(), Some((1, 2, 3)));
+    }
 }
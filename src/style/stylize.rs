@@ -167,6 +167,11 @@ pub trait Stylize<'a, T>: Sized {
     modifier!(reversed);
     modifier!(hidden);
     modifier!(crossed_out);
+    modifier!(double_underlined);
+    modifier!(curly_underlined);
+    modifier!(dotted_underlined);
+    modifier!(dashed_underlined);
+    modifier!(overlined);
 }
 
 impl<'a, T, U> Stylize<'a, T> for U
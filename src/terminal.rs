@@ -29,12 +29,21 @@
 //! [`backend`]: crate::backend
 //! [`Backend`]: crate::backend::Backend
 //! [`Buffer`]: crate::buffer::Buffer
-use std::{fmt, io};
+use std::{
+    collections::VecDeque,
+    fmt,
+    ops::{Deref, DerefMut, Range},
+    panic,
+    sync::{Mutex, Once},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    backend::{Backend, ClearType},
-    buffer::Buffer,
+    backend::{is_unsupported, Backend, ClearType, CursorStyle, DefaultColor, PanicRestorer},
+    buffer::{composite_layers, hash_row, Buffer, Cell, Layer},
+    event::Event,
     layout::Rect,
+    style::{Color, ColorDepth},
     widgets::{StatefulWidget, Widget},
 };
 
@@ -44,7 +53,8 @@ use crate::{
 /// When the viewport is fullscreen, the whole terminal is used to draw the application.
 ///
 /// When the viewport is inline, it is drawn inline with the rest of the terminal. The height of
-/// the viewport is fixed, but the width is the same as the terminal width.
+/// the viewport is either fixed or adjusted to the content of each frame, and the width is the same
+/// as the terminal width.
 ///
 /// When the viewport is fixed, it is drawn in a fixed area of the terminal. The area is specified
 /// by a [`Rect`].
@@ -60,6 +70,40 @@ pub enum Viewport {
     /// The viewport's height is fixed and specified in number of lines. The width is the same as
     /// the terminal's width. The viewport is drawn below the cursor position.
     Inline(u16),
+    /// The viewport is inline with the rest of the terminal and its height follows the content.
+    ///
+    /// Each frame is rendered in an area of `max` lines (or the terminal's height if smaller).
+    /// The viewport is then resized to the rows that were drawn on, but never less than `min`
+    /// lines. When it grows, the terminal is scrolled up if there is not enough room below the
+    /// viewport. When it shrinks, the freed lines are cleared. Widgets should therefore be laid
+    /// out from the top of [`Frame::size`] and only use the rows they need.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::io::stdout;
+    /// # use ratatui::{prelude::*, widgets::Paragraph};
+    /// let backend = CrosstermBackend::new(stdout());
+    /// let viewport = Viewport::InlineAuto { min: 1, max: 10 };
+    /// let mut terminal = Terminal::with_options(
+    ///     backend,
+    ///     TerminalOptions { viewport, ..Default::default() },
+    /// )?;
+    /// for tasks in 1..=5 {
+    ///     terminal.draw(|frame| {
+    ///         // the viewport grows by one line on each draw
+    ///         let lines: Vec<Line> = (1..=tasks).map(|i| format!("task {i}").into()).collect();
+    ///         frame.render_widget(Paragraph::new(lines), frame.size());
+    ///     })?;
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    InlineAuto {
+        /// The minimum height of the viewport
+        min: u16,
+        /// The maximum height of the viewport
+        max: u16,
+    },
     /// The viewport is drawn in a fixed area of the terminal. The area is specified by a [`Rect`].
     Fixed(Rect),
 }
@@ -69,6 +113,7 @@ impl fmt::Display for Viewport {
         match self {
             Viewport::Fullscreen => write!(f, "Fullscreen"),
             Viewport::Inline(height) => write!(f, "Inline({})", height),
+            Viewport::InlineAuto { min, max } => write!(f, "InlineAuto({min}..={max})"),
             Viewport::Fixed(area) => write!(f, "Fixed({})", area),
         }
     }
@@ -79,6 +124,17 @@ impl fmt::Display for Viewport {
 pub struct TerminalOptions {
     /// Viewport used to draw to the terminal
     pub viewport: Viewport,
+    /// Range of colors supported by the terminal. Colors outside of this range are converted to
+    /// the nearest supported color before being drawn. Use [`ColorDepth::detect`] to detect it
+    /// from the environment.
+    pub color_depth: ColorDepth,
+    /// Whether each frame drawn by [`Terminal::draw`] is wrapped in a synchronized update, which
+    /// keeps terminals that support it from displaying partially drawn frames. See
+    /// [`Backend::begin_synchronized_update`].
+    pub synchronized_output: bool,
+    /// Number of frames whose [`FrameStats`] are kept by the terminal, see
+    /// [`Terminal::frame_stats`]. None are kept by default.
+    pub frame_stats_history: usize,
 }
 
 /// An interface to interact and draw [`Frame`]s on the user's terminal.
@@ -140,8 +196,23 @@ where
     current: usize,
     /// Whether the cursor is currently hidden
     hidden_cursor: bool,
+    /// Whether raw mode was enabled with [`Terminal::enable_raw_mode`]
+    raw_mode: bool,
+    /// Whether the alternate screen was entered with [`Terminal::enter_alternate_screen`]
+    alternate_screen: bool,
+    /// Whether mouse capture was enabled with [`Terminal::enable_mouse_capture`]
+    mouse_capture: bool,
+    /// The cursor style last set on the backend
+    cursor_style: CursorStyle,
     /// Viewport
     viewport: Viewport,
+    /// Range of colors supported by the terminal
+    color_depth: ColorDepth,
+    /// Whether frames are drawn within a synchronized update
+    synchronized_output: bool,
+    /// Whether the backend supports scrolling regions of the screen. Set to false the first time
+    /// the backend reports that it doesn't.
+    scroll_regions_supported: bool,
     /// Area of the viewport
     viewport_area: Rect,
     /// Last known size of the terminal. Used to detect if the internal buffers have to be resized.
@@ -149,6 +220,12 @@ where
     /// Last known position of the cursor. Used to find the new area when the viewport is inlined
     /// and the terminal resized.
     last_known_cursor_pos: (u16, u16),
+    /// Event received by [`Terminal::poll_event`] and not read yet
+    pending_event: Option<Event>,
+    /// Statistics of the most recent frames, oldest first
+    frame_stats: VecDeque<FrameStats>,
+    /// Maximum number of frames kept in `frame_stats`
+    frame_stats_history: usize,
 }
 
 impl<B> Drop for Terminal<B>
@@ -162,6 +239,11 @@ where
                 eprintln!("Failed to show the cursor: {err}");
             }
         }
+        if self.cursor_style != CursorStyle::DefaultUserShape {
+            if let Err(err) = self.set_cursor_style(CursorStyle::DefaultUserShape) {
+                eprintln!("Failed to restore the cursor style: {err}");
+            }
+        }
     }
 }
 
@@ -180,11 +262,12 @@ where
     /// let terminal = Terminal::new(backend)?;
     /// # std::io::Result::Ok(())
     /// ```
-    pub fn new(backend: B) -> io::Result<Terminal<B>> {
+    pub fn new(backend: B) -> Result<Terminal<B>, B::Error> {
         Terminal::with_options(
             backend,
             TerminalOptions {
                 viewport: Viewport::Fullscreen,
+                ..Default::default()
             },
         )
     }
@@ -200,18 +283,21 @@ where
     /// let viewport = Viewport::Fixed(Rect::new(0, 0, 10, 10));
     /// let terminal = Terminal::with_options(
     ///     backend,
-    ///     TerminalOptions { viewport },
+    ///     TerminalOptions { viewport, ..Default::default() },
     /// )?;
     /// # std::io::Result::Ok(())
     /// ```
-    pub fn with_options(mut backend: B, options: TerminalOptions) -> io::Result<Terminal<B>> {
+    pub fn with_options(mut backend: B, options: TerminalOptions) -> Result<Terminal<B>, B::Error> {
         let size = match options.viewport {
-            Viewport::Fullscreen | Viewport::Inline(_) => backend.size()?,
+            Viewport::Fullscreen | Viewport::Inline(_) | Viewport::InlineAuto { .. } => {
+                backend.size()?
+            }
             Viewport::Fixed(area) => area,
         };
         let (viewport_area, cursor_pos) = match options.viewport {
             Viewport::Fullscreen => (size, (0, 0)),
             Viewport::Inline(height) => compute_inline_size(&mut backend, height, size, 0)?,
+            Viewport::InlineAuto { min, .. } => compute_inline_size(&mut backend, min, size, 0)?,
             Viewport::Fixed(area) => (area, (area.left(), area.top())),
         };
         Ok(Terminal {
@@ -219,10 +305,20 @@ where
             buffers: [Buffer::empty(viewport_area), Buffer::empty(viewport_area)],
             current: 0,
             hidden_cursor: false,
+            raw_mode: false,
+            alternate_screen: false,
+            mouse_capture: false,
+            cursor_style: CursorStyle::DefaultUserShape,
             viewport: options.viewport,
+            color_depth: options.color_depth,
+            synchronized_output: options.synchronized_output,
+            scroll_regions_supported: true,
             viewport_area,
             last_known_size: size,
             last_known_cursor_pos: cursor_pos,
+            pending_event: None,
+            frame_stats: VecDeque::with_capacity(options.frame_stats_history),
+            frame_stats_history: options.frame_stats_history,
         })
     }
 
@@ -230,8 +326,11 @@ where
     pub fn get_frame(&mut self) -> Frame {
         Frame {
             cursor_position: None,
-            viewport_area: self.viewport_area,
+            cursor_style: CursorStyle::DefaultUserShape,
+            // larger than the viewport area while drawing a `Viewport::InlineAuto`
+            viewport_area: self.buffers[self.current].area,
             buffer: self.current_buffer_mut(),
+            layers: Vec::new(),
         }
     }
 
@@ -250,26 +349,146 @@ where
         &mut self.backend
     }
 
+    /// Gets the range of colors the terminal draws with
+    pub fn color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    /// Sets the range of colors the terminal draws with. Cells drawn from now on will have their
+    /// colors converted to the nearest color within this range.
+    pub fn set_color_depth(&mut self, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+    }
+
+    /// Gets the statistics of the most recent frames drawn by [`Terminal::draw`], oldest first.
+    ///
+    /// At most [`TerminalOptions::frame_stats_history`] frames are kept, none by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::{prelude::*, backend::TestBackend};
+    /// let options = TerminalOptions { frame_stats_history: 60, ..Default::default() };
+    /// let mut terminal = Terminal::with_options(TestBackend::new(10, 1), options)?;
+    /// for _ in 0..100 {
+    ///     terminal.draw(|frame| {})?;
+    /// }
+    /// let stats = terminal.frame_stats();
+    /// assert_eq!(stats.len(), 60);
+    /// let cells: usize = stats.iter().map(|stats| stats.cells_changed).sum();
+    /// assert_eq!(cells, 0);
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn frame_stats(&self) -> &VecDeque<FrameStats> {
+        &self.frame_stats
+    }
+
+    /// Sets the number of frames whose statistics are kept, dropping the oldest ones if there
+    /// are more. See [`Terminal::frame_stats`].
+    pub fn set_frame_stats_history(&mut self, len: usize) {
+        self.frame_stats_history = len;
+        let excess = self.frame_stats.len().saturating_sub(len);
+        self.frame_stats.drain(..excess);
+    }
+
     /// Obtains a difference between the previous and the current buffer and passes it to the
     /// current backend for drawing.
-    pub fn flush(&mut self) -> io::Result<()> {
+    ///
+    /// When rows have moved vertically between the two buffers, as happens when scrolling a list
+    /// or a paragraph, the rows are scrolled on the terminal with
+    /// [`Backend::scroll_region_up`] or [`Backend::scroll_region_down`] and only the remaining
+    /// differences are drawn.
+    ///
+    /// Colors that the terminal can't display, according to its [`ColorDepth`], are converted
+    /// to the nearest supported color.
+    pub fn flush(&mut self) -> Result<(), B::Error> {
+        self.flush_diff().map(|_| ())
+    }
+
+    /// Implements [`Terminal::flush`], returning the number of cells that changed and the time
+    /// spent computing the difference between the buffers.
+    fn flush_diff(&mut self) -> Result<(usize, Duration), B::Error> {
+        if self.scroll_regions_supported {
+            self.scroll_shifted_rows()?;
+        }
+        let diff_start = Instant::now();
         let previous_buffer = &self.buffers[1 - self.current];
         let current_buffer = &self.buffers[self.current];
         let updates = previous_buffer.diff(current_buffer);
+        let cells_changed = updates.len();
         if let Some((col, row, _)) = updates.last() {
             self.last_known_cursor_pos = (*col, *row);
         }
-        self.backend.draw(updates.into_iter())
+        if self.color_depth == ColorDepth::TrueColor {
+            let diff_time = diff_start.elapsed();
+            self.backend.draw(updates.into_iter())?;
+            return Ok((cells_changed, diff_time));
+        }
+        let depth = self.color_depth;
+        let cells: Vec<(u16, u16, Cell)> = updates
+            .into_iter()
+            .map(|(x, y, cell)| {
+                let mut cell = cell.clone();
+                cell.fg = cell.fg.downsample(depth);
+                cell.bg = cell.bg.downsample(depth);
+                #[cfg(feature = "underline-color")]
+                {
+                    cell.underline_color = cell.underline_color.downsample(depth);
+                }
+                (x, y, cell)
+            })
+            .collect();
+        let diff_time = diff_start.elapsed();
+        self.backend
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?;
+        Ok((cells_changed, diff_time))
+    }
+
+    /// Scrolls the rows that moved vertically between the previous and the current buffer on the
+    /// terminal, and shifts the previous buffer to match what is now displayed.
+    fn scroll_shifted_rows(&mut self) -> Result<(), B::Error> {
+        // Scrolling moves whole lines, so it can't be used when the viewport is narrower than the
+        // terminal.
+        if self.viewport_area.x != 0 || self.viewport_area.width != self.last_known_size.width {
+            return Ok(());
+        }
+        let previous_buffer = &self.buffers[1 - self.current];
+        let current_buffer = &self.buffers[self.current];
+        let Some(shift) = find_vertical_shift(previous_buffer, current_buffer) else {
+            return Ok(());
+        };
+        let top = self.viewport_area.y;
+        let result = match &shift {
+            VerticalShift::Up(rows, n) => self
+                .backend
+                .scroll_region_up(top + rows.start..top + rows.end, *n),
+            VerticalShift::Down(rows, n) => self
+                .backend
+                .scroll_region_down(top + rows.start..top + rows.end, *n),
+        };
+        match result {
+            Err(err) if is_unsupported(&err) => {
+                self.scroll_regions_supported = false;
+                return Ok(());
+            }
+            result => result?,
+        }
+        shift.apply(&mut self.buffers[1 - self.current]);
+        Ok(())
     }
 
     /// Updates the Terminal so that internal buffers match the requested size.
     ///
     /// Requested size will be saved so the size can remain consistent when rendering. This leads
     /// to a full clear of the screen.
-    pub fn resize(&mut self, size: Rect) -> io::Result<()> {
+    pub fn resize(&mut self, size: Rect) -> Result<(), B::Error> {
         let next_area = match self.viewport {
             Viewport::Fullscreen => size,
-            Viewport::Inline(height) => {
+            Viewport::Inline(_) | Viewport::InlineAuto { .. } => {
+                let height = match self.viewport {
+                    Viewport::Inline(height) => height,
+                    _ => self.viewport_area.height,
+                };
                 let offset_in_previous_viewport = self
                     .last_known_cursor_pos
                     .1
@@ -292,9 +511,9 @@ where
     }
 
     /// Queries the backend for size and resizes if it doesn't match the previous size.
-    pub fn autoresize(&mut self) -> io::Result<()> {
+    pub fn autoresize(&mut self) -> Result<(), B::Error> {
         // fixed viewports do not get autoresized
-        if matches!(self.viewport, Viewport::Fullscreen | Viewport::Inline(_)) {
+        if !matches!(self.viewport, Viewport::Fixed(_)) {
             let size = self.size()?;
             if size != self.last_known_size {
                 self.resize(size)?;
@@ -308,6 +527,9 @@ where
     ///
     /// This is the main entry point for drawing to the terminal.
     ///
+    /// When [`TerminalOptions::synchronized_output`] is enabled, the frame is drawn within a
+    /// synchronized update so that the terminal displays it all at once.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -322,23 +544,86 @@ where
     /// })?;
     /// # std::io::Result::Ok(())
     /// ```
-    pub fn draw<F>(&mut self, f: F) -> io::Result<CompletedFrame>
+    pub fn draw<F>(&mut self, f: F) -> Result<CompletedFrame, B::Error>
     where
         F: FnOnce(&mut Frame),
     {
         // Autoresize - otherwise we get glitches if shrinking or potential desync between widgets
         // and the terminal (if growing), which may OOB.
         self.autoresize()?;
+        let bytes_before = self.backend.bytes_written().ok();
 
+        self.expand_inline_auto_viewport();
+        let render_start = Instant::now();
         let mut frame = self.get_frame();
         f(&mut frame);
+        composite_layers(frame.buffer, std::mem::take(&mut frame.layers));
+        let render_time = render_start.elapsed();
+        let flush_start = Instant::now();
         // We can't change the cursor position right away because we have to flush the frame to
         // stdout first. But we also can't keep the frame around, since it holds a &mut to
         // Buffer. Thus, we're taking the important data out of the Frame and dropping it.
-        let cursor_position = frame.cursor_position;
+        let mut cursor_position = frame.cursor_position;
+        let cursor_style = frame.cursor_style;
+
+        let mut content_height = None;
+        if let Viewport::InlineAuto { min, .. } = self.viewport {
+            let (height, scrolled) = self.grow_inline_auto_viewport(min)?;
+            content_height = Some(height);
+            cursor_position = cursor_position.map(|(x, y)| (x, y.saturating_sub(scrolled)));
+        }
+
+        if self.synchronized_output {
+            self.backend.begin_synchronized_update()?;
+        }
+        let written = self.write_frame(cursor_position, cursor_style, content_height);
+        // The update is ended even if writing the frame failed, otherwise the terminal would stop
+        // showing anything until its own timeout.
+        let ended = if self.synchronized_output {
+            self.backend.end_synchronized_update()
+        } else {
+            Ok(())
+        };
+        let (cells_changed, diff_time) = written?;
+        ended?;
+
+        // Flush
+        self.backend.flush()?;
 
+        let bytes_after = self.backend.bytes_written().ok();
+        let stats = FrameStats {
+            cells_changed,
+            bytes_written: bytes_before
+                .zip(bytes_after)
+                .map(|(before, after)| after.saturating_sub(before)),
+            render_time,
+            diff_time,
+            flush_time: flush_start.elapsed().saturating_sub(diff_time),
+        };
+        if self.frame_stats_history > 0 {
+            if self.frame_stats.len() == self.frame_stats_history {
+                self.frame_stats.pop_front();
+            }
+            self.frame_stats.push_back(stats);
+        }
+
+        Ok(CompletedFrame {
+            buffer: &self.buffers[1 - self.current],
+            area: self.last_known_size,
+            stats,
+        })
+    }
+
+    /// Writes the difference between the buffers and the cursor of a frame to the backend, then
+    /// swaps the buffers. Returns the number of cells changed and the time spent diffing.
+    fn write_frame(
+        &mut self,
+        cursor_position: Option<(u16, u16)>,
+        cursor_style: CursorStyle,
+        content_height: Option<u16>,
+    ) -> Result<(usize, Duration), B::Error> {
         // Draw to stdout
-        self.flush()?;
+        let (cells_changed, diff_time) = self.flush_diff()?;
 
         match cursor_position {
             None => self.hide_cursor()?,
@@ -347,52 +632,177 @@ where
                 self.set_cursor(x, y)?;
             }
         }
+        if cursor_style != self.cursor_style {
+            match self.set_cursor_style(cursor_style) {
+                // the cursor keeps its shape on backends that cannot change it
+                Err(err) if is_unsupported(&err) => {}
+                result => result?,
+            }
+        }
 
         self.swap_buffers();
 
-        // Flush
-        self.backend.flush()?;
+        // The freed lines have been cleared by the flush, they can be removed from the viewport.
+        if let Some(height) = content_height.filter(|h| *h < self.viewport_area.height) {
+            self.set_viewport_area(Rect {
+                height,
+                ..self.viewport_area
+            });
+        }
+        Ok((cells_changed, diff_time))
+    }
 
-        Ok(CompletedFrame {
-            buffer: &self.buffers[1 - self.current],
-            area: self.last_known_size,
-        })
+    /// Gives the current buffer the largest area a [`Viewport::InlineAuto`] can grow to, so that
+    /// the frame can be rendered before the height of the viewport is known.
+    fn expand_inline_auto_viewport(&mut self) {
+        if let Viewport::InlineAuto { max, .. } = self.viewport {
+            let height = max.min(self.last_known_size.height);
+            let area = Rect {
+                height,
+                ..self.viewport_area
+            };
+            self.buffers[self.current].resize(area);
+        }
+    }
+
+    /// Grows a [`Viewport::InlineAuto`] to fit the content of the current buffer, scrolling the
+    /// terminal up when there is not enough room below the viewport.
+    ///
+    /// The current buffer is cropped to the viewport, keeping the lines freed when the viewport
+    /// shrinks so that the next flush clears them. Returns the height of the content, at least
+    /// `min`, and the number of lines the terminal scrolled.
+    fn grow_inline_auto_viewport(&mut self, min: u16) -> Result<(u16, u16), B::Error> {
+        let buffer = &self.buffers[self.current];
+        let blank = Cell::default();
+        let used = buffer
+            .content()
+            .chunks(buffer.area.width.max(1) as usize)
+            .rposition(|row| row.iter().any(|cell| *cell != blank))
+            .map_or(0, |y| y as u16 + 1);
+        let height = used.max(min).min(buffer.area.height);
+
+        let mut area = self.viewport_area;
+        let mut scrolled = 0;
+        if height > area.height {
+            // appending lines from the top of the viewport scrolls the terminal only if needed
+            self.backend.set_cursor(area.left(), area.top())?;
+            self.backend.append_lines(height - 1)?;
+            scrolled = (area.top() + height).saturating_sub(self.last_known_size.bottom());
+            area.y -= scrolled;
+            area.height = height;
+            // the lines below the previous viewport are blank
+            self.buffers[1 - self.current].resize(area);
+            self.viewport_area = area;
+        }
+        self.buffers[self.current].resize(area);
+        Ok((height, scrolled))
     }
 
     /// Hides the cursor.
-    pub fn hide_cursor(&mut self) -> io::Result<()> {
+    pub fn hide_cursor(&mut self) -> Result<(), B::Error> {
         self.backend.hide_cursor()?;
         self.hidden_cursor = true;
         Ok(())
     }
 
     /// Shows the cursor.
-    pub fn show_cursor(&mut self) -> io::Result<()> {
+    pub fn show_cursor(&mut self) -> Result<(), B::Error> {
         self.backend.show_cursor()?;
         self.hidden_cursor = false;
         Ok(())
     }
 
+    /// Sets the shape of the cursor and whether it blinks.
+    ///
+    /// The style is restored to [`CursorStyle::DefaultUserShape`] when the terminal is dropped.
+    /// Note that [`Terminal::draw`] applies the style set with [`Frame::set_cursor_style`], which
+    /// replaces the style set here.
+    pub fn set_cursor_style(&mut self, style: CursorStyle) -> Result<(), B::Error> {
+        self.backend.set_cursor_style(style)?;
+        self.cursor_style = style;
+        Ok(())
+    }
+
     /// Gets the current cursor position.
     ///
     /// This is the position of the cursor after the last draw call and is returned as a tuple of
     /// `(x, y)` coordinates.
-    pub fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+    pub fn get_cursor(&mut self) -> Result<(u16, u16), B::Error> {
         self.backend.get_cursor()
     }
 
     /// Sets the cursor position.
-    pub fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+    pub fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), B::Error> {
         self.backend.set_cursor(x, y)?;
         self.last_known_cursor_pos = (x, y);
         Ok(())
     }
 
+    /// Enables raw mode, see [`Backend::enable_raw_mode`].
+    ///
+    /// The terminal keeps track of the modes enabled with its methods, so that
+    /// [`Terminal::suspend`] can disable them and enable them again.
+    pub fn enable_raw_mode(&mut self) -> Result<(), B::Error> {
+        self.backend.enable_raw_mode()?;
+        self.raw_mode = true;
+        Ok(())
+    }
+
+    /// Disables raw mode, see [`Backend::disable_raw_mode`].
+    pub fn disable_raw_mode(&mut self) -> Result<(), B::Error> {
+        self.backend.disable_raw_mode()?;
+        self.raw_mode = false;
+        Ok(())
+    }
+
+    /// Switches to the alternate screen, see [`Backend::enter_alternate_screen`].
+    pub fn enter_alternate_screen(&mut self) -> Result<(), B::Error> {
+        self.backend.enter_alternate_screen()?;
+        self.alternate_screen = true;
+        Ok(())
+    }
+
+    /// Switches back to the main screen, see [`Backend::leave_alternate_screen`].
+    pub fn leave_alternate_screen(&mut self) -> Result<(), B::Error> {
+        self.backend.leave_alternate_screen()?;
+        self.alternate_screen = false;
+        Ok(())
+    }
+
+    /// Enables mouse capture, see [`Backend::enable_mouse_capture`].
+    pub fn enable_mouse_capture(&mut self) -> Result<(), B::Error> {
+        self.backend.enable_mouse_capture()?;
+        self.mouse_capture = true;
+        Ok(())
+    }
+
+    /// Disables mouse capture, see [`Backend::disable_mouse_capture`].
+    pub fn disable_mouse_capture(&mut self) -> Result<(), B::Error> {
+        self.backend.disable_mouse_capture()?;
+        self.mouse_capture = false;
+        Ok(())
+    }
+
+    /// Sets the title of the terminal window, see [`Backend::set_title`].
+    pub fn set_title(&mut self, title: &str) -> Result<(), B::Error> {
+        self.backend.set_title(title)
+    }
+
+    /// Copies `content` to the system clipboard, see [`Backend::set_clipboard`].
+    pub fn set_clipboard(&mut self, content: &str) -> Result<(), B::Error> {
+        self.backend.set_clipboard(content)
+    }
+
+    /// Rings the terminal bell, see [`Backend::bell`].
+    pub fn bell(&mut self) -> Result<(), B::Error> {
+        self.backend.bell()
+    }
+
     /// Clear the terminal and force a full redraw on the next draw call.
-    pub fn clear(&mut self) -> io::Result<()> {
+    pub fn clear(&mut self) -> Result<(), B::Error> {
         match self.viewport {
             Viewport::Fullscreen => self.backend.clear_region(ClearType::All)?,
-            Viewport::Inline(_) => {
+            Viewport::Inline(_) | Viewport::InlineAuto { .. } => {
                 self.backend
                     .set_cursor(self.viewport_area.left(), self.viewport_area.top())?;
                 self.backend.clear_region(ClearType::AfterCursor)?;
@@ -409,6 +819,90 @@ where
         Ok(())
     }
 
+    /// Stops the process until it is continued, restoring the terminal in the meantime.
+    ///
+    /// In raw mode, Ctrl-Z doesn't stop the process but is received as a key event. Calling this
+    /// method when it is received gives the usual job control behavior: mouse capture and raw
+    /// mode are disabled, the alternate screen is left and the cursor is shown, then the process
+    /// is stopped with `SIGTSTP`. When it is continued, for example with `fg`, the modes are
+    /// enabled again and the terminal is cleared, so that the next [`Terminal::draw`] redraws
+    /// everything.
+    ///
+    /// # Tracked modes
+    ///
+    /// Only the modes enabled with [`Terminal::enable_raw_mode`],
+    /// [`Terminal::enter_alternate_screen`] and [`Terminal::enable_mouse_capture`] are disabled
+    /// and enabled again, which includes the modes enabled by a [`TerminalSession`]. Modes enabled
+    /// directly through the backend library, for example with
+    /// `crossterm::terminal::enable_raw_mode`, are not known to the terminal and stay enabled
+    /// while the process is stopped, which leaves the shell unusable. Use a [`TerminalSession`]
+    /// or the methods of the terminal instead.
+    ///
+    /// This method is only available on Unix, with one of the `crossterm`, `termion` or `termwiz`
+    /// features enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::io::stdout;
+    /// # use ratatui::{event::{Event, KeyCode, KeyModifiers}, prelude::*};
+    /// let mut terminal = TerminalSession::new(CrosstermBackend::new(stdout()))?;
+    /// if let Event::Key(key) = terminal.read_event()? {
+    ///     if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+    ///         terminal.suspend()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[cfg(all(
+        unix,
+        any(feature = "crossterm", feature = "termion", feature = "termwiz")
+    ))]
+    pub fn suspend(&mut self) -> Result<(), B::Error> {
+        self.suspend_with(|_| {
+            signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)
+        })
+    }
+
+    /// Disables the tracked modes, calls `stop` and enables the modes again, see
+    /// [`Terminal::suspend`].
+    #[cfg(all(
+        unix,
+        any(feature = "crossterm", feature = "termion", feature = "termwiz")
+    ))]
+    fn suspend_with<F>(&mut self, stop: F) -> Result<(), B::Error>
+    where
+        F: FnOnce(&B) -> std::io::Result<()>,
+    {
+        let (raw_mode, alternate_screen, mouse_capture) =
+            (self.raw_mode, self.alternate_screen, self.mouse_capture);
+        if mouse_capture {
+            self.disable_mouse_capture()?;
+        }
+        if alternate_screen {
+            self.leave_alternate_screen()?;
+        }
+        if raw_mode {
+            self.disable_raw_mode()?;
+        }
+        self.show_cursor()?;
+        self.backend.flush()?;
+
+        // returns once the process is continued
+        stop(&self.backend)?;
+
+        if raw_mode {
+            self.enable_raw_mode()?;
+        }
+        if alternate_screen {
+            self.enter_alternate_screen()?;
+        }
+        if mouse_capture {
+            self.enable_mouse_capture()?;
+        }
+        self.clear()
+    }
+
     /// Clears the inactive buffer and swaps it with the current buffer
     pub fn swap_buffers(&mut self) {
         self.buffers[1 - self.current].reset();
@@ -416,10 +910,73 @@ where
     }
 
     /// Queries the real size of the backend.
-    pub fn size(&self) -> io::Result<Rect> {
+    pub fn size(&self) -> Result<Rect, B::Error> {
         self.backend.size()
     }
 
+    /// Waits for at most `timeout` for an input event to be available.
+    ///
+    /// Returns `true` if an event is available, in which case the next call to
+    /// [`Terminal::read_event`] returns it without blocking. Pass [`Duration::ZERO`] to check for
+    /// an event without waiting.
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::Unsupported`] if the backend cannot read
+    /// events. See [`Backend::next_event`].
+    pub fn poll_event(&mut self, timeout: Duration) -> Result<bool, B::Error> {
+        if self.pending_event.is_none() {
+            self.pending_event = self.backend.next_event(Some(timeout))?;
+        }
+        Ok(self.pending_event.is_some())
+    }
+
+    /// Reads the next input event, blocking until one is available.
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::Unsupported`] if the backend cannot read
+    /// events. See [`Backend::next_event`].
+    pub fn read_event(&mut self) -> Result<Event, B::Error> {
+        if let Some(event) = self.pending_event.take() {
+            return Ok(event);
+        }
+        loop {
+            if let Some(event) = self.backend.next_event(None)? {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Asks the terminal for one of its default colors and waits at most `timeout` for the answer.
+    ///
+    /// Returns the color as a [`Color::Rgb`], or `Ok(None)` when the terminal didn't answer in
+    /// time. Input events received while waiting are returned by the next calls to
+    /// [`Terminal::poll_event`] and [`Terminal::read_event`]. See
+    /// [`Backend::query_default_color`].
+    ///
+    /// # Example
+    ///
+    /// Choosing a theme that matches the background of the terminal:
+    ///
+    /// ```rust,no_run
+    /// use std::{io::stdout, time::Duration};
+    ///
+    /// use ratatui::{backend::DefaultColor, prelude::*};
+    ///
+    /// let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    /// let background =
+    ///     terminal.query_default_color(DefaultColor::Background, Duration::from_millis(100))?;
+    /// let dark = match background {
+    ///     Some(Color::Rgb(r, g, b)) => u32::from(r) + u32::from(g) + u32::from(b) < 384,
+    ///     _ => true,
+    /// };
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn query_default_color(
+        &mut self,
+        color: DefaultColor,
+        timeout: Duration,
+    ) -> Result<Option<Color>, B::Error> {
+        self.backend.query_default_color(color, timeout)
+    }
+
     /// Insert some content before the current inline viewport. This has no effect when the
     /// viewport is fullscreen.
     ///
@@ -463,11 +1020,14 @@ where
     ///     ])).render(buf.area, buf);
     /// });
     /// ```
-    pub fn insert_before<F>(&mut self, height: u16, draw_fn: F) -> io::Result<()>
+    pub fn insert_before<F>(&mut self, height: u16, draw_fn: F) -> Result<(), B::Error>
     where
         F: FnOnce(&mut Buffer),
     {
-        if !matches!(self.viewport, Viewport::Inline(_)) {
+        if !matches!(
+            self.viewport,
+            Viewport::Inline(_) | Viewport::InlineAuto { .. }
+        ) {
             return Ok(());
         }
 
@@ -497,7 +1057,7 @@ where
 
         // Split buffer into screen-sized chunks and draw
         let max_chunk_size = (self.viewport_area.top() * area.width).into();
-        for buffer_content_chunk in buffer.content.chunks(max_chunk_size) {
+        for buffer_content_chunk in buffer.content().chunks(max_chunk_size) {
             let chunk_size = buffer_content_chunk.len() as u16 / area.width;
 
             self.backend
@@ -520,12 +1080,95 @@ where
     }
 }
 
+/// Rows of a buffer that moved up or down by a number of lines.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum VerticalShift {
+    /// The rows in the range moved up by the given number of lines.
+    Up(Range<u16>, u16),
+    /// The rows in the range moved down by the given number of lines.
+    Down(Range<u16>, u16),
+}
+
+impl VerticalShift {
+    /// Shifts the rows of the buffer the same way the terminal scrolls them, filling the rows that
+    /// were scrolled in with empty cells.
+    fn apply(&self, buffer: &mut Buffer) {
+        let area = |rows: &Range<u16>| Rect {
+            y: buffer.area.y + rows.start,
+            height: rows.len() as u16,
+            ..buffer.area
+        };
+        match self {
+            VerticalShift::Up(rows, n) => buffer.scroll_up(area(rows), *n),
+            VerticalShift::Down(rows, n) => buffer.scroll_down(area(rows), *n),
+        }
+    }
+}
+
+/// Finds the vertical shift of rows between the previous and the current buffer that saves the
+/// most rows from being redrawn, if any.
+///
+/// Only the rows between the first and the last changed rows are considered. A shift is only
+/// worth it if more rows match the current buffer after it than before it, as it also moves the
+/// rows that didn't change, which then have to be redrawn. Rows are compared by the hashes the
+/// buffers keep to keep this cheap. A hash collision only means that more cells than necessary are
+/// drawn after the shift, as the shifted buffer is still diffed against the current one.
+fn find_vertical_shift(previous: &Buffer, current: &Buffer) -> Option<VerticalShift> {
+    if previous.area != current.area || previous.area.width == 0 {
+        return None;
+    }
+    let width = current.area.width as usize;
+    let rows = 0..current.area.height;
+    let previous_rows: Vec<u64> = rows.clone().map(|y| previous.row_hash(y)).collect();
+    let current_rows: Vec<u64> = rows.map(|y| current.row_hash(y)).collect();
+    // the rows scrolled into the region are empty
+    let blank_row = hash_row(&vec![Cell::default(); width]);
+    let changed = |y: usize| previous_rows[y] != current_rows[y];
+    let top = (0..current_rows.len()).find(|&y| changed(y))?;
+    let bottom = (0..current_rows.len()).rfind(|&y| changed(y))? + 1;
+    let unchanged = (top..bottom).filter(|&y| !changed(y)).count();
+    let matching = |shifted: &dyn Fn(usize) -> u64| {
+        (top..bottom)
+            .filter(|&y| current_rows[y] == shifted(y))
+            .count()
+    };
+
+    let mut best = None;
+    let mut best_gain = 0;
+    for n in 1..bottom - top {
+        let up = matching(&|y| {
+            previous_rows
+                .get(y + n)
+                .filter(|_| y + n < bottom)
+                .copied()
+                .unwrap_or(blank_row)
+        });
+        let down = matching(&|y| {
+            if y >= top + n {
+                previous_rows[y - n]
+            } else {
+                blank_row
+            }
+        });
+        let rows = top as u16..bottom as u16;
+        if up.saturating_sub(unchanged) > best_gain {
+            best_gain = up - unchanged;
+            best = Some(VerticalShift::Up(rows.clone(), n as u16));
+        }
+        if down.saturating_sub(unchanged) > best_gain {
+            best_gain = down - unchanged;
+            best = Some(VerticalShift::Down(rows, n as u16));
+        }
+    }
+    best
+}
+
 fn compute_inline_size<B: Backend>(
     backend: &mut B,
     height: u16,
     size: Rect,
     offset_in_previous_viewport: u16,
-) -> io::Result<(Rect, (u16, u16))> {
+) -> Result<(Rect, (u16, u16)), B::Error> {
     let pos = backend.get_cursor()?;
     let mut row = pos.1;
 
@@ -555,6 +1198,204 @@ fn compute_inline_size<B: Backend>(
     ))
 }
 
+/// Options to pass to [`TerminalSession::with_options`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SessionOptions {
+    /// Options of the [`Terminal`] created by the session
+    pub terminal: TerminalOptions,
+    /// Whether to switch to the alternate screen. Enabled by default.
+    pub alternate_screen: bool,
+    /// Whether to capture mouse events. Disabled by default.
+    pub mouse_capture: bool,
+    /// Whether to install a panic hook that restores the terminal before the panic message is
+    /// printed. Enabled by default.
+    pub panic_hook: bool,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        SessionOptions {
+            terminal: TerminalOptions::default(),
+            alternate_screen: true,
+            mouse_capture: false,
+            panic_hook: true,
+        }
+    }
+}
+
+/// A [`Terminal`] that keeps the terminal in the state a full screen application needs for as
+/// long as it lives.
+///
+/// Creating a session enables raw mode and, depending on its [`SessionOptions`], switches to the
+/// alternate screen and enables mouse capture. Everything is restored when the session is
+/// dropped, including while unwinding from a panic. A panic hook is also installed by default,
+/// which restores the terminal before the panic message is printed so that the message can be
+/// read, even when panics abort. See [`Backend::panic_restorer`] for what each backend restores
+/// from the hook.
+///
+/// The session dereferences to its [`Terminal`], so it can be used in the same way. Only one
+/// session should be active at a time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::io::stdout;
+///
+/// use ratatui::{prelude::*, widgets::Paragraph};
+///
+/// let mut session = TerminalSession::new(CrosstermBackend::new(stdout()))?;
+/// session.draw(|frame| {
+///     frame.render_widget(Paragraph::new("Hello World!"), frame.size());
+/// })?;
+/// // the terminal is restored when the session goes out of scope
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug)]
+pub struct TerminalSession<B>
+where
+    B: Backend,
+{
+    /// The terminal used to draw
+    terminal: Terminal<B>,
+    /// Whether the session registered the function called by the panic hook
+    panic_hook: bool,
+}
+
+/// Called by the panic hook installed by [`TerminalSession`] while a session is active.
+static PANIC_RESTORER: Mutex<Option<PanicRestorer>> = Mutex::new(None);
+
+impl<B> TerminalSession<B>
+where
+    B: Backend,
+{
+    /// Starts a session with the default [`SessionOptions`]: raw mode, the alternate screen, no
+    /// mouse capture and a panic hook.
+    pub fn new(backend: B) -> Result<TerminalSession<B>, B::Error> {
+        TerminalSession::with_options(backend, SessionOptions::default())
+    }
+
+    /// Starts a session with the given [`Backend`] and [`SessionOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::io::stdout;
+    /// # use ratatui::prelude::*;
+    /// let backend = CrosstermBackend::new(stdout());
+    /// let options = SessionOptions {
+    ///     mouse_capture: true,
+    ///     ..Default::default()
+    /// };
+    /// let session = TerminalSession::with_options(backend, options)?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    pub fn with_options(
+        backend: B,
+        options: SessionOptions,
+    ) -> Result<TerminalSession<B>, B::Error> {
+        let mut session = TerminalSession {
+            terminal: Terminal::with_options(backend, options.terminal)?,
+            panic_hook: false,
+        };
+        // if anything fails, dropping the session restores what was already changed
+        if options.panic_hook {
+            match session.terminal.backend().panic_restorer() {
+                Ok(restorer) => {
+                    install_panic_hook();
+                    *PANIC_RESTORER.lock().unwrap_or_else(|e| e.into_inner()) = Some(restorer);
+                    session.panic_hook = true;
+                }
+                // the terminal is only restored when the session is dropped
+                Err(err) if is_unsupported(&err) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        session.terminal.enable_raw_mode()?;
+        if options.alternate_screen {
+            session.terminal.enter_alternate_screen()?;
+        }
+        if options.mouse_capture {
+            session.terminal.enable_mouse_capture()?;
+        }
+        Ok(session)
+    }
+
+    /// Restores the terminal: disables mouse capture and raw mode, leaves the alternate screen and
+    /// shows the cursor.
+    ///
+    /// This is done when the session is dropped, but calling it explicitly allows handling
+    /// errors. Restoring the terminal more than once does nothing.
+    pub fn restore(&mut self) -> Result<(), B::Error> {
+        if self.panic_hook {
+            PANIC_RESTORER
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take();
+            self.panic_hook = false;
+        }
+        if self.terminal.mouse_capture {
+            self.terminal.disable_mouse_capture()?;
+        }
+        if self.terminal.alternate_screen {
+            self.terminal.leave_alternate_screen()?;
+        }
+        if self.terminal.raw_mode {
+            self.terminal.disable_raw_mode()?;
+        }
+        if self.terminal.hidden_cursor {
+            self.terminal.show_cursor()?;
+        }
+        self.terminal.backend_mut().flush()
+    }
+}
+
+impl<B> Deref for TerminalSession<B>
+where
+    B: Backend,
+{
+    type Target = Terminal<B>;
+
+    fn deref(&self) -> &Terminal<B> {
+        &self.terminal
+    }
+}
+
+impl<B> DerefMut for TerminalSession<B>
+where
+    B: Backend,
+{
+    fn deref_mut(&mut self) -> &mut Terminal<B> {
+        &mut self.terminal
+    }
+}
+
+impl<B> Drop for TerminalSession<B>
+where
+    B: Backend,
+{
+    fn drop(&mut self) {
+        if let Err(err) = self.restore() {
+            eprintln!("Failed to restore the terminal: {err}");
+        }
+    }
+}
+
+/// Installs the panic hook calling [`PANIC_RESTORER`] before the previous hook, once.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // the restorer is taken so that nested panics don't restore the terminal again
+            let restorer = PANIC_RESTORER.try_lock().ok().and_then(|mut r| r.take());
+            if let Some(restorer) = restorer {
+                let _ = restorer();
+            }
+            previous(info);
+        }));
+    });
+}
+
 /// A consistent view into the terminal state for rendering a single frame.
 ///
 /// This is obtained via the closure argument of [`Terminal::draw`]. It is used to render widgets
@@ -572,11 +1413,16 @@ pub struct Frame<'a> {
     /// If `None`, the cursor is hidden and its position is controlled by the backend. If `Some((x,
     /// y))`, the cursor is shown and placed at `(x, y)` after the call to `Terminal::draw()`.
     cursor_position: Option<(u16, u16)>,
+    /// The shape of the cursor after drawing this frame
+    cursor_style: CursorStyle,
     /// The area of the viewport
     viewport_area: Rect,
 
     /// The buffer that is used to draw the current frame
     buffer: &'a mut Buffer,
+
+    /// The layers drawn over the buffer once the frame is rendered
+    layers: Vec<Layer>,
 }
 
 impl Frame<'_> {
@@ -644,6 +1490,30 @@ impl Frame<'_> {
         widget.render(area, self.buffer, state);
     }
 
+    /// Draws a [`Layer`] over the content of the frame once the closure passed to
+    /// [`Terminal::draw`] returns.
+    ///
+    /// Layers are drawn by increasing z-index, over the content rendered to the frame's buffer
+    /// which is at z-index 0. Their transparent cells let the content below them show through.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::{backend::TestBackend, buffer::Layer, prelude::*, widgets::*};
+    /// # let backend = TestBackend::new(20, 5);
+    /// # let mut terminal = Terminal::new(backend).unwrap();
+    /// # let mut frame = terminal.get_frame();
+    /// let area = Rect::new(2, 1, 10, 3);
+    /// let mut popup = Layer::new(area).z_index(1);
+    /// Paragraph::new("Popup")
+    ///     .block(Block::default().borders(Borders::ALL))
+    ///     .render(area, popup.buffer_mut());
+    /// frame.render_layer(popup);
+    /// ```
+    pub fn render_layer(&mut self, layer: Layer) {
+        self.layers.push(layer);
+    }
+
     /// After drawing this frame, make the cursor visible and put it at the specified (x, y)
     /// coordinates. If this method is not called, the cursor will be hidden.
     ///
@@ -654,6 +1524,31 @@ impl Frame<'_> {
         self.cursor_position = Some((x, y));
     }
 
+    /// After drawing this frame, give the cursor the specified shape and blinking behavior. If this
+    /// method is not called, the cursor has the default shape configured in the terminal.
+    ///
+    /// The style only changes the appearance of the cursor, use [`Frame::set_cursor`] to make it
+    /// visible. Backends that do not support cursor styles keep the default shape.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use ratatui::{backend::{CursorStyle, TestBackend}, prelude::*};
+    /// # let backend = TestBackend::new(5, 5);
+    /// # let mut terminal = Terminal::new(backend).unwrap();
+    /// # let mut frame = terminal.get_frame();
+    /// # let insert_mode = true;
+    /// frame.set_cursor(2, 0);
+    /// frame.set_cursor_style(if insert_mode {
+    ///     CursorStyle::BlinkingBar
+    /// } else {
+    ///     CursorStyle::SteadyBlock
+    /// });
+    /// ```
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style = style;
+    }
+
     /// Gets the buffer that this `Frame` draws into as a mutable reference.
     pub fn buffer_mut(&mut self) -> &mut Buffer {
         self.buffer
@@ -669,19 +1564,130 @@ pub struct CompletedFrame<'a> {
     pub buffer: &'a Buffer,
     /// The size of the last frame.
     pub area: Rect,
+    /// What drawing the last frame cost.
+    pub stats: FrameStats,
+}
+
+/// Statistics about a frame drawn by [`Terminal::draw`], returned in [`CompletedFrame::stats`].
+///
+/// They can be used to display the frame rate of an application or to find widgets that redraw
+/// more of the screen than needed. The terminal can also keep the statistics of the most recent
+/// frames, see [`Terminal::frame_stats`].
+///
+/// # Example
+///
+/// ```rust
+/// # use ratatui::{prelude::*, backend::TestBackend, widgets::Paragraph};
+/// let mut terminal = Terminal::new(TestBackend::new(10, 1))?;
+/// let frame = terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("Hello"), frame.size());
+/// })?;
+/// assert_eq!(frame.stats.cells_changed, 5);
+/// // the test backend doesn't count the bytes it receives
+/// assert_eq!(frame.stats.bytes_written, None);
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FrameStats {
+    /// Number of cells that differed from the previous frame and were drawn
+    pub cells_changed: usize,
+    /// Number of bytes written to the backend, or `None` if the backend doesn't count them (see
+    /// [`Backend::bytes_written`])
+    pub bytes_written: Option<u64>,
+    /// Time spent in the closure passed to [`Terminal::draw`]
+    pub render_time: Duration,
+    /// Time spent computing the difference between the previous and the current buffer
+    pub diff_time: Duration,
+    /// Time spent writing the frame to the backend and flushing it, not including `diff_time`
+    pub flush_time: Duration,
+}
+
+impl FrameStats {
+    /// Returns the total time spent drawing the frame.
+    pub fn total_time(&self) -> Duration {
+        self.render_time + self.diff_time + self.flush_time
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn vertical_shift_of_scrolled_rows() {
+        let previous = Buffer::with_lines(vec!["a", "b", "c", "d", "e"]);
+        let current = Buffer::with_lines(vec!["a", "c", "d", "e", "f"]);
+        assert_eq!(
+            find_vertical_shift(&previous, &current),
+            Some(VerticalShift::Up(1..5, 1))
+        );
+        let current = Buffer::with_lines(vec!["x", "a", "b", "c", "d"]);
+        assert_eq!(
+            find_vertical_shift(&previous, &current),
+            Some(VerticalShift::Down(0..5, 1))
+        );
+    }
+
+    #[test]
+    fn no_vertical_shift_when_only_the_top_and_bottom_rows_changed() {
+        let previous = Buffer::with_lines(vec!["a", "b", "c", "d", "e", "f", "g", "h"]);
+        let current = Buffer::with_lines(vec!["x", "b", "c", "d", "e", "f", "g", "y"]);
+        assert_eq!(find_vertical_shift(&previous, &current), None);
+
+        // a shift that saves one row but breaks the six unchanged rows isn't worth it
+        let current = Buffer::with_lines(vec!["d", "b", "c", "d", "e", "f", "g", "y"]);
+        assert_eq!(find_vertical_shift(&previous, &current), None);
+    }
+
     #[test]
     fn viewport_to_string() {
         assert_eq!(Viewport::Fullscreen.to_string(), "Fullscreen");
         assert_eq!(Viewport::Inline(5).to_string(), "Inline(5)");
+        assert_eq!(
+            Viewport::InlineAuto { min: 1, max: 5 }.to_string(),
+            "InlineAuto(1..=5)"
+        );
         assert_eq!(
             Viewport::Fixed(Rect::new(0, 0, 5, 5)).to_string(),
             "Fixed(5x5+0+0)"
         );
     }
+
+    #[cfg(all(
+        unix,
+        any(feature = "crossterm", feature = "termion", feature = "termwiz")
+    ))]
+    #[test]
+    fn suspend_disables_and_enables_the_tracked_modes() {
+        use crate::{backend::TestBackend, widgets::Paragraph};
+
+        let mut terminal = Terminal::new(TestBackend::new(10, 2)).unwrap();
+        terminal.enable_raw_mode().unwrap();
+        terminal.enable_mouse_capture().unwrap();
+        let hello = |f: &mut Frame| f.render_widget(Paragraph::new("Hello"), f.size());
+        terminal.draw(hello).unwrap();
+
+        let mut stopped = false;
+        terminal
+            .suspend_with(|backend| {
+                assert!(!backend.raw_mode_enabled());
+                assert!(!backend.mouse_capture_enabled());
+                stopped = true;
+                Ok(())
+            })
+            .unwrap();
+        assert!(stopped);
+        assert!(terminal.backend().raw_mode_enabled());
+        assert!(terminal.backend().mouse_capture_enabled());
+        // the alternate screen wasn't entered before suspending
+        assert!(!terminal.backend().alternate_screen_active());
+
+        // the terminal was cleared, so the unchanged frame is drawn again
+        terminal.set_frame_stats_history(1);
+        terminal.draw(hello).unwrap();
+        assert_eq!(terminal.frame_stats()[0].cells_changed, 5);
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(vec!["Hello     ", "          "]));
+    }
 }
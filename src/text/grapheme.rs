@@ -9,11 +9,23 @@ use crate::style::{Style, Styled};
 pub struct StyledGrapheme<'a> {
     pub symbol: &'a str,
     pub style: Style,
+    /// The target of the hyperlink the grapheme is part of, if any.
+    pub hyperlink: Option<&'a str>,
 }
 
 impl<'a> StyledGrapheme<'a> {
     pub fn new(symbol: &'a str, style: Style) -> StyledGrapheme<'a> {
-        StyledGrapheme { symbol, style }
+        StyledGrapheme {
+            symbol,
+            style,
+            hyperlink: None,
+        }
+    }
+
+    /// Sets the target of the hyperlink the grapheme is part of.
+    pub fn hyperlink(mut self, target: &'a str) -> StyledGrapheme<'a> {
+        self.hyperlink = Some(target);
+        self
     }
 }
 
@@ -41,6 +53,13 @@ mod tests {
         let sg = StyledGrapheme::new("a", style);
         assert_eq!(sg.symbol, "a");
         assert_eq!(sg.style, style);
+        assert_eq!(sg.hyperlink, None);
+    }
+
+    #[test]
+    fn hyperlink() {
+        let sg = StyledGrapheme::new("a", Style::new()).hyperlink("https://ratatui.rs");
+        assert_eq!(sg.hyperlink, Some("https://ratatui.rs"));
     }
 
     #[test]
@@ -80,6 +80,8 @@ pub struct Span<'a> {
     pub content: Cow<'a, str>,
     /// The style of the span.
     pub style: Style,
+    /// The target of the hyperlink the span is rendered as, if any.
+    pub hyperlink: Option<Cow<'a, str>>,
 }
 
 impl<'a> Span<'a> {
@@ -99,6 +101,7 @@ impl<'a> Span<'a> {
         Span {
             content: content.into(),
             style: Style::default(),
+            hyperlink: None,
         }
     }
 
@@ -119,6 +122,7 @@ impl<'a> Span<'a> {
         Span {
             content: content.into(),
             style,
+            hyperlink: None,
         }
     }
 
@@ -168,6 +172,31 @@ impl<'a> Span<'a> {
         self
     }
 
+    /// Sets the target of the hyperlink the span is rendered as.
+    ///
+    /// This is a fluent setter method which must be chained or used as it consumes self
+    ///
+    /// The target is usually a URL. It is stored in each [`Cell`] the span is rendered to and
+    /// backends emit it as an `OSC 8` hyperlink, which terminals that support it make clickable
+    /// as a whole, even when the span is split across several lines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ratatui::prelude::*;
+    /// let span = Span::raw("ratatui").hyperlink("https://ratatui.rs");
+    /// ```
+    ///
+    /// [`Cell`]: crate::buffer::Cell
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn hyperlink<T>(mut self, target: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.hyperlink = Some(target.into());
+        self
+    }
+
     /// Patches the style of the Span, adding modifiers from the given style.
     ///
     /// # Example
@@ -237,6 +266,7 @@ impl<'a> Span<'a> {
             .map(move |g| StyledGrapheme {
                 symbol: g,
                 style: base_style.patch(self.style),
+                hyperlink: self.hyperlink.as_deref(),
             })
     }
 }
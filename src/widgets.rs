@@ -11,6 +11,7 @@
 //! - [`Chart`]: displays multiple datasets as a lines or scatter graph.
 //! - [`Clear`]: clears the area it occupies. Useful to render over previously drawn widgets.
 //! - [`Gauge`]: displays progress percentage using block characters.
+//! - [`Image`]: displays an image using a terminal graphics protocol or half block characters.
 //! - [`LineGauge`]: display progress as a line.
 //! - [`List`]: displays a list of items and allows selection.
 //! - [`Paragraph`]: displays a paragraph of optionally styled and wrapped text.
@@ -28,6 +29,7 @@ pub mod canvas;
 mod chart;
 mod clear;
 mod gauge;
+mod image;
 mod list;
 mod paragraph;
 mod reflow;
@@ -46,6 +48,7 @@ pub use self::{
     chart::{Axis, Chart, Dataset, GraphType},
     clear::Clear,
     gauge::{Gauge, LineGauge},
+    image::{Image, ImageProtocol},
     list::{List, ListItem, ListState},
     paragraph::{Paragraph, Wrap},
     scrollbar::{ScrollDirection, Scrollbar, ScrollbarOrientation, ScrollbarState},
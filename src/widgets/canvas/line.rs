@@ -127,7 +127,7 @@ mod tests {
         canvas.render(buffer.area, &mut buffer);
 
         let mut expected = Buffer::with_lines(expected_lines);
-        for cell in expected.content.iter_mut() {
+        for cell in expected.content_mut().iter_mut() {
             if cell.symbol() == "•" {
                 cell.set_style(Style::new().red());
             }
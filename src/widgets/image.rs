@@ -0,0 +1,412 @@
+#![warn(missing_docs)]
+use std::env;
+
+use crate::{
+    backend::{base64, WindowSize},
+    buffer::Buffer,
+    layout::Rect,
+    style::Color,
+    symbols,
+    widgets::{Block, Widget},
+};
+
+mod iterm2;
+mod kitty;
+mod sixel;
+
+/// The protocol used by the [`Image`] widget to display pixel data.
+///
+/// Use [`ImageProtocol::detect`] to pick the protocol supported by the current terminal.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ImageProtocol {
+    /// The [Kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/)
+    Kitty,
+    /// [Sixel](https://vt100.net/docs/vt3xx-gp/chapter14.html) graphics
+    Sixel,
+    /// The [iTerm2 inline images protocol](https://iterm2.com/documentation-images.html)
+    ITerm2,
+    /// Draws two pixels per cell using the upper half block character (`▀`)
+    ///
+    /// This works on any terminal supporting true colors and is used as a fallback when no
+    /// graphics protocol is available. Transparent pixels show the terminal background, cells with
+    /// two transparent pixels are left as they are.
+    #[default]
+    HalfBlocks,
+}
+
+impl ImageProtocol {
+    /// Detects the graphics protocol supported by the terminal from the `TERM`, `TERM_PROGRAM`
+    /// and `KITTY_WINDOW_ID` environment variables.
+    ///
+    /// Falls back to [`ImageProtocol::HalfBlocks`] when no graphics protocol is known to be
+    /// available.
+    pub fn detect() -> ImageProtocol {
+        ImageProtocol::from_env(
+            env::var("TERM").ok().as_deref(),
+            env::var("TERM_PROGRAM").ok().as_deref(),
+            env::var_os("KITTY_WINDOW_ID").is_some(),
+        )
+    }
+
+    fn from_env(term: Option<&str>, term_program: Option<&str>, kitty: bool) -> ImageProtocol {
+        let term = term.unwrap_or_default();
+        if kitty || term == "xterm-kitty" {
+            ImageProtocol::Kitty
+        } else if matches!(term_program, Some("iTerm.app" | "WezTerm")) {
+            ImageProtocol::ITerm2
+        } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+            ImageProtocol::Sixel
+        } else {
+            ImageProtocol::HalfBlocks
+        }
+    }
+}
+
+/// A widget to display an image from raw RGBA pixel data.
+///
+/// The image is scaled down to fit the area while preserving its aspect ratio, and is never
+/// scaled up.
+///
+/// When using a graphics protocol ([`ImageProtocol::Kitty`], [`ImageProtocol::Sixel`] or
+/// [`ImageProtocol::ITerm2`]), the encoded image is stored in the top-left cell of the covered
+/// area and every other covered cell is marked as skipped (see [`Cell::set_skip`]) so that the
+/// backend doesn't draw over the image. Mapping pixels to cells requires the size of the window in
+/// pixels, which can be given with [`Image::window_size`] (see [`Backend::window_size`]). When the
+/// pixel size is unknown, the widget falls back to [`ImageProtocol::HalfBlocks`].
+///
+/// Images drawn with the Kitty protocol live on a separate layer and are only removed when the
+/// screen is cleared (e.g. with [`Terminal::clear`]).
+///
+/// [`Cell::set_skip`]: crate::buffer::Cell::set_skip
+/// [`Backend::window_size`]: crate::backend::Backend::window_size
+/// [`Terminal::clear`]: crate::Terminal::clear
+///
+/// # Example
+///
+/// ```
+/// use ratatui::{prelude::*, widgets::*};
+///
+/// let pixels = [255, 0, 0, 255, 0, 0, 255, 255];
+/// Image::new(&pixels, 2, 1)
+///     .protocol(ImageProtocol::detect())
+///     .block(Block::default().title("Image").borders(Borders::ALL));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Image<'a> {
+    /// A block to wrap the widget in
+    block: Option<Block<'a>>,
+    /// The pixels of the image, 4 bytes (red, green, blue, alpha) per pixel, row by row
+    rgba: &'a [u8],
+    /// Width of the image in pixels
+    width: u32,
+    /// Height of the image in pixels
+    height: u32,
+    /// The size of the terminal window, used to compute the size of a cell in pixels
+    window_size: Option<WindowSize>,
+    /// The protocol used to display the image
+    protocol: ImageProtocol,
+}
+
+impl<'a> Image<'a> {
+    /// Creates a new image from RGBA pixel data.
+    ///
+    /// `rgba` holds 4 bytes per pixel, row by row. Missing pixels are treated as transparent.
+    pub fn new(rgba: &'a [u8], width: u32, height: u32) -> Image<'a> {
+        Image {
+            block: None,
+            rgba,
+            width,
+            height,
+            window_size: None,
+            protocol: ImageProtocol::default(),
+        }
+    }
+
+    /// Wraps the image with the given `block`.
+    pub fn block(mut self, block: Block<'a>) -> Image<'a> {
+        self.block = Some(block);
+        self
+    }
+
+    /// Sets the size of the terminal window, as returned by
+    /// [`Backend::window_size`](crate::backend::Backend::window_size).
+    ///
+    /// This is required by every protocol except [`ImageProtocol::HalfBlocks`].
+    pub fn window_size(mut self, window_size: WindowSize) -> Image<'a> {
+        self.window_size = Some(window_size);
+        self
+    }
+
+    /// Sets the protocol used to display the image.
+    pub fn protocol(mut self, protocol: ImageProtocol) -> Image<'a> {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Returns the size of a cell in pixels, if known.
+    fn cell_size(&self) -> Option<(u32, u32)> {
+        let size = self.window_size?;
+        if size.columns_rows.width == 0 || size.columns_rows.height == 0 {
+            return None;
+        }
+        let width = u32::from(size.pixels.width / size.columns_rows.width);
+        let height = u32::from(size.pixels.height / size.columns_rows.height);
+        (width > 0 && height > 0).then_some((width, height))
+    }
+
+    /// Returns the size of the image once scaled down to fit in `width` x `height` pixels.
+    fn fit(&self, width: u32, height: u32) -> (u32, u32) {
+        if self.width <= width && self.height <= height {
+            return (self.width, self.height);
+        }
+        let (w, h) = (u64::from(self.width), u64::from(self.height));
+        if w * u64::from(height) > h * u64::from(width) {
+            (width, ((h * u64::from(width)) / w).max(1) as u32)
+        } else {
+            (((w * u64::from(height)) / h).max(1) as u32, height)
+        }
+    }
+
+    /// Returns the pixel at (`x`, `y`) of the image once resized to `width` x `height` pixels.
+    fn pixel(&self, x: u32, y: u32, width: u32, height: u32) -> [u8; 4] {
+        let x = u64::from(x) * u64::from(self.width) / u64::from(width);
+        let y = u64::from(y) * u64::from(self.height) / u64::from(height);
+        let i = ((y * u64::from(self.width) + x) * 4) as usize;
+        self.rgba
+            .get(i..i + 4)
+            .map_or([0; 4], |p| [p[0], p[1], p[2], p[3]])
+    }
+
+    /// Resizes the image to `width` x `height` pixels using nearest neighbor sampling.
+    fn resize(&self, width: u32, height: u32) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).flat_map(move |x| self.pixel(x, y, width, height)))
+            .collect()
+    }
+
+    fn render_graphics(&self, area: Rect, buf: &mut Buffer, cell: (u32, u32)) {
+        let (cell_width, cell_height) = cell;
+        let (width, height) = self.fit(
+            u32::from(area.width) * cell_width,
+            u32::from(area.height) * cell_height,
+        );
+        let rgba = self.resize(width, height);
+        let sequence = match self.protocol {
+            ImageProtocol::Kitty => kitty::encode(&rgba, width, height),
+            ImageProtocol::Sixel => sixel::encode(&rgba, width, height),
+            ImageProtocol::ITerm2 => iterm2::encode(&rgba, width, height),
+            ImageProtocol::HalfBlocks => unreachable!(),
+        };
+        let columns = ((width + cell_width - 1) / cell_width) as u16;
+        let rows = ((height + cell_height - 1) / cell_height) as u16;
+        for y in area.top()..area.top() + rows {
+            for x in area.left()..area.left() + columns {
+                let cell = buf.get_mut(x, y);
+                cell.reset();
+                cell.set_skip(true);
+            }
+        }
+        // Save the cursor, draw the image and restore the cursor, leaving it one cell to the
+        // right like any other symbol would.
+        buf.get_mut(area.left(), area.top())
+            .set_symbol(&format!("\x1b7{sequence}\x1b8\x1b[C"))
+            .set_skip(false);
+    }
+
+    fn render_half_blocks(&self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = self.fit(u32::from(area.width), u32::from(area.height) * 2);
+        // transparent pixels have no color
+        let color = |x, y| {
+            if y >= height {
+                return None;
+            }
+            match self.pixel(x, y, width, height) {
+                [.., a] if a < 128 => None,
+                [r, g, b, _] => Some(Color::Rgb(r, g, b)),
+            }
+        };
+        for y in 0..(height + 1) / 2 {
+            for x in 0..width {
+                let cell = buf.get_mut(area.left() + x as u16, area.top() + y as u16);
+                match (color(x, y * 2), color(x, y * 2 + 1)) {
+                    (Some(top), bottom) => {
+                        cell.set_char(symbols::half_block::UPPER)
+                            .set_fg(top)
+                            .set_bg(bottom.unwrap_or(Color::Reset));
+                    }
+                    // the upper half is left to the terminal background
+                    (None, Some(bottom)) => {
+                        cell.set_char(symbols::half_block::LOWER)
+                            .set_fg(bottom)
+                            .set_bg(Color::Reset);
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Widget for Image<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block.take() {
+            Some(b) => {
+                let inner_area = b.inner(area);
+                b.render(area, buf);
+                inner_area
+            }
+            None => area,
+        };
+        if area.is_empty() || self.width == 0 || self.height == 0 {
+            return;
+        }
+        match (self.protocol, self.cell_size()) {
+            (ImageProtocol::HalfBlocks, _) | (_, None) => self.render_half_blocks(area, buf),
+            (_, Some(cell)) => self.render_graphics(area, buf, cell),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_buffer_eq, layout::Size};
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    fn window_size() -> WindowSize {
+        WindowSize {
+            columns_rows: Size {
+                width: 80,
+                height: 24,
+            },
+            pixels: Size {
+                width: 800,
+                height: 480,
+            },
+        }
+    }
+
+    #[test]
+    fn protocol_from_env() {
+        let detect = ImageProtocol::from_env;
+        assert_eq!(
+            detect(Some("xterm-kitty"), None, false),
+            ImageProtocol::Kitty
+        );
+        assert_eq!(detect(Some("xterm"), None, true), ImageProtocol::Kitty);
+        assert_eq!(
+            detect(Some("xterm-256color"), Some("iTerm.app"), false),
+            ImageProtocol::ITerm2
+        );
+        assert_eq!(detect(Some("foot"), None, false), ImageProtocol::Sixel);
+        assert_eq!(
+            detect(Some("xterm"), None, false),
+            ImageProtocol::HalfBlocks
+        );
+        assert_eq!(detect(None, None, false), ImageProtocol::HalfBlocks);
+    }
+
+    #[test]
+    fn fit_preserves_aspect_ratio() {
+        let image = Image::new(&[], 100, 50);
+        assert_eq!(image.fit(200, 200), (100, 50));
+        assert_eq!(image.fit(50, 50), (50, 25));
+        assert_eq!(image.fit(100, 10), (20, 10));
+    }
+
+    #[test]
+    fn render_half_blocks() {
+        // 2x3 image: red / blue / transparent rows
+        let rgba = [RED, RED, BLUE, BLUE, [0; 4], [0; 4]].concat();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 3));
+        Image::new(&rgba, 2, 3).render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec!["▀▀ ", "   ", "   "]);
+        for x in 0..2 {
+            expected
+                .get_mut(x, 0)
+                .set_fg(Color::Rgb(255, 0, 0))
+                .set_bg(Color::Rgb(0, 0, 255));
+        }
+        assert_buffer_eq!(buf, expected);
+    }
+
+    #[test]
+    fn render_half_blocks_with_transparent_top_pixels() {
+        // 2x4 image: transparent over red in the first column, red over transparent in the
+        // second, then transparent over transparent and blue over transparent
+        let clear = [0; 4];
+        let rgba = [clear, RED, RED, clear, clear, BLUE, clear, clear].concat();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 2));
+        Image::new(&rgba, 2, 4).render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(vec!["▄▀", " ▀"]);
+        expected
+            .get_mut(0, 0)
+            .set_fg(Color::Rgb(255, 0, 0))
+            .set_bg(Color::Reset);
+        expected
+            .get_mut(1, 0)
+            .set_fg(Color::Rgb(255, 0, 0))
+            .set_bg(Color::Reset);
+        expected
+            .get_mut(1, 1)
+            .set_fg(Color::Rgb(0, 0, 255))
+            .set_bg(Color::Reset);
+        assert_buffer_eq!(buf, expected);
+    }
+
+    #[test]
+    fn render_without_pixel_size_falls_back_to_half_blocks() {
+        let rgba = RED;
+        let mut buf = Buffer::empty(Rect::new(0, 0, 1, 1));
+        Image::new(&rgba, 1, 1)
+            .protocol(ImageProtocol::Kitty)
+            .render(buf.area, &mut buf);
+        assert_eq!(buf.get(0, 0).symbol(), "▀");
+        assert_eq!(buf.get(0, 0).fg, Color::Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn render_graphics_skips_covered_cells() {
+        // 25x15 pixels with 10x20 pixel cells covers 3 columns and 1 row
+        let rgba = RED.repeat(25 * 15);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 2));
+        Image::new(&rgba, 25, 15)
+            .protocol(ImageProtocol::Kitty)
+            .window_size(window_size())
+            .render(buf.area, &mut buf);
+
+        let symbol = buf.get(0, 0).symbol();
+        assert!(symbol.starts_with("\x1b7\x1b_Ga=T,f=32,s=25,v=15,"));
+        assert!(symbol.ends_with("\x1b\\\x1b8\x1b[C"));
+        assert!(!buf.get(0, 0).skip);
+        for x in 1..3 {
+            assert!(buf.get(x, 0).skip);
+        }
+        assert!(!buf.get(3, 0).skip);
+        assert!(!buf.get(0, 1).skip);
+
+        // the covered cells are not part of the diff and the cell after the image is drawn
+        let mut next = buf.clone();
+        next.get_mut(3, 0).set_symbol("x");
+        let diff = buf.diff(&next);
+        assert_eq!(diff.len(), 1);
+        assert_eq!((diff[0].0, diff[0].1), (3, 0));
+    }
+
+    #[test]
+    fn render_graphics_in_block() {
+        let rgba = RED.repeat(4);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 3));
+        Image::new(&rgba, 2, 2)
+            .protocol(ImageProtocol::Sixel)
+            .window_size(window_size())
+            .block(Block::default().borders(crate::widgets::Borders::ALL))
+            .render(buf.area, &mut buf);
+        assert!(buf.get(1, 1).symbol().starts_with("\x1b7\x1bP"));
+        assert!(!buf.get(2, 1).skip);
+    }
+}
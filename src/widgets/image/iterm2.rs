@@ -0,0 +1,149 @@
+//! Encoder for the [iTerm2 inline images protocol](https://iterm2.com/documentation-images.html).
+
+use super::base64;
+
+/// Encodes RGBA pixels as an iTerm2 inline image escape sequence.
+///
+/// The protocol expects an image file, so the pixels are first stored in an uncompressed PNG.
+pub(super) fn encode(rgba: &[u8], width: u32, height: u32) -> String {
+    let png = png(rgba, width, height);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={width}px;height={height}px;preserveAspectRatio=0:{}\x07",
+        png.len(),
+        base64(&png)
+    )
+}
+
+/// Encodes RGBA pixels as a PNG file using uncompressed (stored) deflate blocks.
+fn png(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGBA, default compression, filtering and no interlacing
+    header.extend([8, 6, 0, 0, 0]);
+
+    // every scanline starts with its filter type (0: none)
+    let stride = width as usize * 4;
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for y in 0..height as usize {
+        raw.push(0);
+        let start = (y * stride).min(rgba.len());
+        let end = (start + stride).min(rgba.len());
+        raw.extend(&rgba[start..end]);
+        raw.resize(raw.len() + stride - (end - start), 0);
+    }
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    push_chunk(&mut png, b"IHDR", &header);
+    push_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut output = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        output.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        output.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        output.extend(len.to_le_bytes());
+        output.extend((!len).to_le_bytes());
+        output.extend(block);
+    }
+    output.extend(adler32(data).to_be_bytes());
+    output
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), byte| {
+        let a = (a + u32::from(*byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encode_png() {
+        let png = png(&[255, 0, 0, 255], 1, 1);
+        assert_eq!(
+            png,
+            [
+                b"\x89PNG\r\n\x1a\n".as_slice(),
+                // IHDR
+                &[0, 0, 0, 13],
+                b"IHDR",
+                &[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0],
+                &crc32(b"IHDR\0\0\0\x01\0\0\0\x01\x08\x06\0\0\0").to_be_bytes(),
+                // IDAT: zlib header, a single stored block and the adler32 checksum
+                &[0, 0, 0, 16],
+                b"IDAT",
+                &[0x78, 0x01, 1, 5, 0, 0xfa, 0xff, 0, 255, 0, 0, 255],
+                &adler32(&[0, 255, 0, 0, 255]).to_be_bytes(),
+                &crc32(
+                    &[
+                        b"IDAT".as_slice(),
+                        &[0x78, 0x01, 1, 5, 0, 0xfa, 0xff, 0, 255, 0, 0, 255],
+                        &adler32(&[0, 255, 0, 0, 255]).to_be_bytes(),
+                    ]
+                    .concat()
+                )
+                .to_be_bytes(),
+                // IEND
+                &[0, 0, 0, 0],
+                b"IEND",
+                &[0xae, 0x42, 0x60, 0x82],
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn encode_sequence() {
+        let output = encode(&[255, 0, 0, 255], 1, 1);
+        let png = png(&[255, 0, 0, 255], 1, 1);
+        assert_eq!(
+            output,
+            format!(
+                "\x1b]1337;File=inline=1;size={};width=1px;height=1px;preserveAspectRatio=0:{}\x07",
+                png.len(),
+                base64(&png)
+            )
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Encoder for the [Kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/).
+
+use super::base64;
+
+/// Maximum size of the base64 payload of a single escape sequence.
+const CHUNK_SIZE: usize = 4096;
+
+/// Encodes RGBA pixels as a Kitty graphics escape sequence that displays the image at the cursor
+/// position without moving the cursor.
+///
+/// The payload is split into chunks of at most 4096 bytes as required by the protocol.
+pub(super) fn encode(rgba: &[u8], width: u32, height: u32) -> String {
+    let payload = base64(rgba);
+    let chunks = payload.as_bytes().chunks(CHUNK_SIZE);
+    let last = chunks.len().saturating_sub(1);
+    let mut output = String::with_capacity(payload.len() + chunks.len() * 16 + 32);
+    for (i, chunk) in chunks.enumerate() {
+        let more = u8::from(i != last);
+        // the payload is base64 and therefore always valid UTF-8
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            output.push_str(&format!(
+                "\x1b_Ga=T,f=32,s={width},v={height},C=1,q=2,m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            output.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_single_chunk() {
+        assert_eq!(
+            encode(&[255, 0, 0, 255], 1, 1),
+            "\x1b_Ga=T,f=32,s=1,v=1,C=1,q=2,m=0;/wAA/w==\x1b\\"
+        );
+    }
+
+    #[test]
+    fn encode_multiple_chunks() {
+        // 3072 bytes encode to exactly one chunk of 4096 bytes
+        let rgba = vec![0; 3072 + 3];
+        let output = encode(&rgba, 769, 1);
+        let first = format!(
+            "\x1b_Ga=T,f=32,s=769,v=1,C=1,q=2,m=1;{}\x1b\\",
+            "A".repeat(4096)
+        );
+        assert_eq!(output, format!("{first}\x1b_Gm=0;AAAA\x1b\\"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Encoder for [Sixel](https://vt100.net/docs/vt3xx-gp/chapter14.html) graphics.
+
+use std::fmt::Write;
+
+/// Number of levels per channel of the color cube used as the sixel palette.
+const LEVELS: u8 = 6;
+
+/// Returns the index of the color closest to `pixel` in a 6x6x6 color cube, or `None` for
+/// transparent pixels.
+fn palette_index(pixel: &[u8]) -> Option<u8> {
+    if pixel[3] < 128 {
+        return None;
+    }
+    let level = |c: u8| ((u16::from(c) * u16::from(LEVELS - 1) + 127) / 255) as u8;
+    Some(level(pixel[0]) * LEVELS * LEVELS + level(pixel[1]) * LEVELS + level(pixel[2]))
+}
+
+/// Encodes RGBA pixels as a sixel escape sequence.
+///
+/// Colors are quantized to a 6x6x6 color cube, and transparent pixels are left untouched.
+pub(super) fn encode(rgba: &[u8], width: u32, height: u32) -> String {
+    let width = width as usize;
+    let height = height as usize;
+    let indices: Vec<Option<u8>> = rgba.chunks_exact(4).map(palette_index).collect();
+
+    let mut output = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+
+    let mut used = [false; 216];
+    for index in indices.iter().flatten() {
+        used[*index as usize] = true;
+    }
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let percent = |level: usize| level * 100 / (LEVELS as usize - 1);
+        let (r, g, b) = (index / 36, index / 6 % 6, index % 6);
+        let _ = write!(
+            output,
+            "#{index};2;{};{};{}",
+            percent(r),
+            percent(g),
+            percent(b)
+        );
+    }
+
+    for band in 0..(height + 5) / 6 {
+        if band > 0 {
+            output.push('-');
+        }
+        let rows = band * 6..((band + 1) * 6).min(height);
+        let mut colors: Vec<u8> = rows
+            .clone()
+            .flat_map(|y| {
+                indices[y * width..(y + 1) * width]
+                    .iter()
+                    .flatten()
+                    .copied()
+            })
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for (i, color) in colors.iter().enumerate() {
+            if i > 0 {
+                output.push('$');
+            }
+            let _ = write!(output, "#{color}");
+            let sixels = (0..width).map(|x| {
+                rows.clone().fold(0u8, |bits, y| {
+                    if indices[y * width + x] == Some(*color) {
+                        bits | 1 << (y - band * 6)
+                    } else {
+                        bits
+                    }
+                })
+            });
+            push_run_length_encoded(&mut output, sixels);
+        }
+    }
+
+    output.push_str("\x1b\\");
+    output
+}
+
+/// Pushes the sixel characters for `sixels`, compressing runs of 4 or more identical characters.
+fn push_run_length_encoded(output: &mut String, sixels: impl Iterator<Item = u8>) {
+    let mut run: Option<(u8, usize)> = None;
+    let flush = |output: &mut String, (sixel, count): (u8, usize)| {
+        let c = char::from(63 + sixel);
+        if count >= 4 {
+            let _ = write!(output, "!{count}{c}");
+        } else {
+            output.extend(std::iter::repeat(c).take(count));
+        }
+    };
+    for sixel in sixels {
+        run = match run {
+            Some((previous, count)) if previous == sixel => Some((sixel, count + 1)),
+            Some(previous) => {
+                flush(output, previous);
+                Some((sixel, 1))
+            }
+            None => Some((sixel, 1)),
+        };
+    }
+    if let Some(run) = run {
+        flush(output, run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    #[test]
+    fn encode_single_pixel() {
+        assert_eq!(
+            encode(&RED, 1, 1),
+            "\x1bP0;1;0q\"1;1;1;1#180;2;100;0;0#180@\x1b\\"
+        );
+    }
+
+    #[test]
+    fn encode_colors_and_bands() {
+        // 2x7 image: red on the left, blue on the right, the last row transparent
+        let mut rgba = [RED, BLUE].concat().repeat(6);
+        rgba.extend([0; 8]);
+        assert_eq!(
+            encode(&rgba, 2, 7),
+            concat!(
+                "\x1bP0;1;0q\"1;1;2;7",
+                "#5;2;0;0;100#180;2;100;0;0",
+                "#5?~$#180~?",
+                "-",
+                "\x1b\\"
+            )
+        );
+    }
+
+    #[test]
+    fn encode_run_length() {
+        let rgba = RED.repeat(5);
+        assert_eq!(
+            encode(&rgba, 5, 1),
+            "\x1bP0;1;0q\"1;1;5;1#180;2;100;0;0#180!5@\x1b\\"
+        );
+    }
+}
@@ -254,7 +254,12 @@ impl<'a> Paragraph<'a> {
         {
             if y >= self.scroll.0 {
                 let mut x = get_line_offset(current_line_width, area.width, current_line_alignment);
-                for StyledGrapheme { symbol, style } in current_line {
+                for StyledGrapheme {
+                    symbol,
+                    style,
+                    hyperlink,
+                } in current_line
+                {
                     let width = symbol.width();
                     if width == 0 {
                         continue;
@@ -264,7 +269,8 @@ impl<'a> Paragraph<'a> {
                     let symbol = if symbol.is_empty() { " " } else { symbol };
                     buf.get_mut(area.left() + x, area.top() + y - self.scroll.0)
                         .set_symbol(symbol)
-                        .set_style(*style);
+                        .set_style(*style)
+                        .set_hyperlink(*hyperlink);
                     x += width as u16;
                 }
             }
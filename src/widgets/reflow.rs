@@ -93,7 +93,8 @@ where
                         (VecDeque::<StyledGrapheme>::new(), 0);
 
                     let mut has_seen_non_whitespace = false;
-                    for StyledGrapheme { symbol, style } in line_symbols {
+                    for grapheme in line_symbols {
+                        let symbol = grapheme.symbol;
                         let symbol_whitespace =
                             symbol.chars().all(&char::is_whitespace) && symbol != NBSP;
                         let symbol_width = symbol.width() as u16;
@@ -164,10 +165,10 @@ where
                         // Append symbol to unfinished, partially processed word
                         if symbol_whitespace {
                             whitespace_width += symbol_width;
-                            unfinished_whitespaces.push_back(StyledGrapheme { symbol, style });
+                            unfinished_whitespaces.push_back(grapheme);
                         } else {
                             word_width += symbol_width;
-                            unfinished_word.push(StyledGrapheme { symbol, style });
+                            unfinished_word.push(grapheme);
                         }
 
                         has_seen_non_whitespace = !symbol_whitespace;
@@ -264,7 +265,12 @@ where
             lines_exhausted = false;
             current_alignment = *alignment;
 
-            for StyledGrapheme { symbol, style } in current_line {
+            for StyledGrapheme {
+                symbol,
+                style,
+                hyperlink,
+            } in current_line
+            {
                 // Ignore characters wider that the total max width.
                 if symbol.width() as u16 > self.max_line_width {
                     continue;
@@ -289,7 +295,11 @@ where
                     }
                 };
                 current_line_width += symbol.width() as u16;
-                self.current_line.push(StyledGrapheme { symbol, style });
+                self.current_line.push(StyledGrapheme {
+                    symbol,
+                    style,
+                    hyperlink,
+                });
             }
         }
 
@@ -0,0 +1,130 @@
+use std::{error::Error, fmt, io};
+
+use ratatui::{
+    backend::{Backend, CursorStyle, TestBackend, WindowSize},
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    widgets::Paragraph,
+    Terminal,
+};
+
+/// The error of a backend that doesn't write to a terminal.
+#[derive(Debug)]
+enum CanvasError {
+    Io(io::Error),
+    Disconnected,
+}
+
+impl fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanvasError::Io(_) => write!(f, "unsupported operation"),
+            CanvasError::Disconnected => write!(f, "the canvas is disconnected"),
+        }
+    }
+}
+
+impl Error for CanvasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CanvasError::Io(err) => Some(err),
+            CanvasError::Disconnected => None,
+        }
+    }
+}
+
+impl From<io::Error> for CanvasError {
+    fn from(err: io::Error) -> Self {
+        CanvasError::Io(err)
+    }
+}
+
+/// A backend drawing to a canvas that can be disconnected, and which doesn't support optional
+/// features like cursor styles.
+struct CanvasBackend {
+    inner: TestBackend,
+    connected: bool,
+}
+
+impl Backend for CanvasBackend {
+    type Error = CanvasError;
+
+    fn draw<'a, I>(&mut self, content: I) -> Result<(), CanvasError>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if !self.connected {
+            return Err(CanvasError::Disconnected);
+        }
+        Ok(self.inner.draw(content)?)
+    }
+
+    fn hide_cursor(&mut self) -> Result<(), CanvasError> {
+        Ok(self.inner.hide_cursor()?)
+    }
+
+    fn show_cursor(&mut self) -> Result<(), CanvasError> {
+        Ok(self.inner.show_cursor()?)
+    }
+
+    fn get_cursor(&mut self) -> Result<(u16, u16), CanvasError> {
+        Ok(self.inner.get_cursor()?)
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), CanvasError> {
+        Ok(self.inner.set_cursor(x, y)?)
+    }
+
+    fn clear(&mut self) -> Result<(), CanvasError> {
+        Ok(self.inner.clear()?)
+    }
+
+    fn size(&self) -> Result<Rect, CanvasError> {
+        Ok(self.inner.size()?)
+    }
+
+    fn window_size(&mut self) -> Result<WindowSize, CanvasError> {
+        Ok(self.inner.window_size()?)
+    }
+
+    fn flush(&mut self) -> Result<(), CanvasError> {
+        Ok(self.inner.flush()?)
+    }
+}
+
+fn canvas(connected: bool) -> CanvasBackend {
+    CanvasBackend {
+        inner: TestBackend::new(10, 4),
+        connected,
+    }
+}
+
+#[test]
+fn backend_error_is_returned_by_draw() -> Result<(), CanvasError> {
+    let mut terminal = Terminal::new(canvas(false))?;
+    let result = terminal.draw(|f| f.render_widget(Paragraph::new("Hello"), f.size()));
+    assert!(matches!(result, Err(CanvasError::Disconnected)));
+    Ok(())
+}
+
+#[test]
+fn unsupported_features_are_recognized_through_the_error_source() -> Result<(), CanvasError> {
+    let mut terminal = Terminal::new(canvas(true))?;
+    // the cursor style is not supported, which is not an error when drawing
+    terminal.draw(|f| {
+        f.render_widget(Paragraph::new("Hello\nWorld\n!"), f.size());
+        f.set_cursor_style(CursorStyle::SteadyBar);
+    })?;
+    // scroll regions are not supported either, so the rows that moved are redrawn
+    terminal.draw(|f| f.render_widget(Paragraph::new("\nHello\nWorld\n!"), f.size()))?;
+    terminal
+        .backend()
+        .inner
+        .assert_buffer(&Buffer::with_lines(vec![
+            "          ",
+            "Hello     ",
+            "World     ",
+            "!         ",
+        ]));
+    Ok(())
+}
@@ -16,6 +16,7 @@ fn backend_termion_should_only_write_diffs() -> Result<(), Box<dyn std::error::E
             backend,
             TerminalOptions {
                 viewport: Viewport::Fixed(area),
+                ..Default::default()
             },
         )?;
         terminal.draw(|f| {
@@ -61,3 +62,43 @@ fn backend_termion_should_only_write_diffs() -> Result<(), Box<dyn std::error::E
 
     Ok(())
 }
+
+#[cfg(feature = "termion")]
+#[test]
+fn backend_termion_output_replays_to_drawn_buffer() -> Result<(), Box<dyn std::error::Error>> {
+    use ratatui::{
+        assert_buffer_eq,
+        backend::{AnsiParser, TermionBackend},
+        prelude::*,
+        widgets::{Block, Borders, Paragraph},
+    };
+
+    let area = Rect::new(0, 0, 12, 4);
+    let mut bytes = Vec::new();
+    let expected = {
+        let backend = TermionBackend::new(&mut bytes);
+        let mut terminal = Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Fixed(area),
+                ..Default::default()
+            },
+        )?;
+        let frame = terminal.draw(|f| {
+            let paragraph = Paragraph::new(vec![
+                Line::from(vec!["Hello ".red().bold(), "称号".on_blue()]),
+                Line::from("World".italic().underlined().fg(Color::Rgb(1, 2, 3))),
+            ])
+            .block(Block::default().borders(Borders::LEFT | Borders::TOP));
+            f.render_widget(paragraph, area);
+            let link = Span::raw("link").hyperlink("https://ratatui.rs");
+            f.buffer_mut().set_span(1, 3, &link, 4);
+        })?;
+        frame.buffer.clone()
+    };
+
+    let mut parser = AnsiParser::new(area);
+    parser.process(&bytes);
+    assert_buffer_eq!(*parser.buffer(), expected);
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use ratatui::{
+    backend::{assert_buffer_snapshot, TestBackend},
+    buffer::Buffer,
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+    Terminal,
+};
+
+#[test]
+fn snapshot_styled_paragraph() {
+    let backend = TestBackend::new(20, 4);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal
+        .draw(|f| {
+            let text = vec![
+                Line::from(vec!["Hello ".bold(), "World".red().on_black()]),
+                Line::from("ratatui".italic().underlined()),
+            ];
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::new().light_blue())
+                .title("Snapshot".yellow());
+            f.render_widget(Paragraph::new(text).block(block), f.size());
+        })
+        .unwrap();
+    terminal
+        .backend()
+        .assert_snapshot("tests/snapshots/styled_paragraph.snap");
+}
+
+#[test]
+fn snapshot_buffer() {
+    let mut buffer = Buffer::empty(Rect::new(2, 1, 6, 2));
+    buffer.set_string(2, 1, "plain", Style::default());
+    buffer.set_string(2, 2, "styled", Style::new().green().on_blue());
+    assert_buffer_snapshot(&buffer, "tests/snapshots/buffer.snap");
+}
@@ -1,12 +1,16 @@
-use std::error::Error;
+use std::{error::Error, io, time::Duration};
 
 use ratatui::{
     assert_buffer_eq,
-    backend::{Backend, TestBackend},
+    backend::{AnsiBackend, AnsiParser, Backend, CursorStyle, DefaultColor, TestBackend},
+    buffer::Layer,
+    event::{Event, KeyCode, KeyEvent, KeyModifiers},
     layout::Rect,
     prelude::Buffer,
+    style::{Color, ColorDepth, Style},
+    text::Line,
     widgets::{Paragraph, Widget},
-    Terminal, TerminalOptions, Viewport,
+    SessionOptions, Terminal, TerminalOptions, TerminalSession, Viewport,
 };
 
 #[test]
@@ -61,6 +65,7 @@ fn terminal_insert_before_moves_viewport() -> Result<(), Box<dyn Error>> {
         backend,
         TerminalOptions {
             viewport: Viewport::Inline(1),
+            ..Default::default()
         },
     )?;
 
@@ -106,6 +111,7 @@ fn terminal_insert_before_scrolls_on_large_input() -> Result<(), Box<dyn Error>>
         backend,
         TerminalOptions {
             viewport: Viewport::Inline(1),
+            ..Default::default()
         },
     )?;
 
@@ -152,6 +158,7 @@ fn terminal_insert_before_scrolls_on_many_inserts() -> Result<(), Box<dyn Error>
         backend,
         TerminalOptions {
             viewport: Viewport::Inline(1),
+            ..Default::default()
         },
     )?;
 
@@ -193,3 +200,488 @@ fn terminal_insert_before_scrolls_on_many_inserts() -> Result<(), Box<dyn Error>
 
     Ok(())
 }
+
+#[test]
+fn terminal_downsamples_colors_to_color_depth() -> Result<(), Box<dyn Error>> {
+    let backend = TestBackend::new(2, 1);
+    let mut terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            color_depth: ColorDepth::Palette256,
+            ..Default::default()
+        },
+    )?;
+    let style = Style::new()
+        .fg(Color::Rgb(255, 0, 0))
+        .bg(Color::Rgb(0, 0, 0));
+    terminal.draw(|f| {
+        f.buffer_mut().set_string(0, 0, "ab", style);
+    })?;
+
+    let mut expected = Buffer::with_lines(vec!["ab"]);
+    expected.set_style(
+        expected.area,
+        Style::new().fg(Color::Indexed(196)).bg(Color::Indexed(16)),
+    );
+    assert_buffer_eq!(terminal.backend().buffer().clone(), expected);
+
+    // changing the color depth applies to the following draws
+    terminal.set_color_depth(ColorDepth::Ansi16);
+    terminal.draw(|f| {
+        f.buffer_mut().set_string(0, 0, "cd", style);
+    })?;
+    let mut expected = Buffer::with_lines(vec!["cd"]);
+    expected.set_style(
+        expected.area,
+        Style::new().fg(Color::LightRed).bg(Color::Black),
+    );
+    assert_buffer_eq!(terminal.backend().buffer().clone(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn terminal_draw_with_synchronized_output() -> Result<(), Box<dyn Error>> {
+    let backend = AnsiBackend::new(Vec::new(), 5, 1);
+    let mut terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            synchronized_output: true,
+            ..Default::default()
+        },
+    )?;
+    terminal.draw(|f| {
+        f.render_widget(Paragraph::new("Hello"), f.size());
+    })?;
+
+    let output = String::from_utf8(terminal.backend().writer().clone())?;
+    assert!(output.starts_with("\x1b[?2026h"), "{output:?}");
+    assert!(output.ends_with("\x1b[?2026l"), "{output:?}");
+    assert!(output.contains("Hello"), "{output:?}");
+    Ok(())
+}
+
+/// A writer that fails to write `!`, recording everything else.
+#[derive(Debug, Default)]
+struct FailingWriter(Vec<u8>);
+
+impl io::Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf == b"!" {
+            return Err(io::Error::new(io::ErrorKind::Other, "write failed"));
+        }
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn terminal_draw_ends_synchronized_output_when_writing_fails() -> Result<(), Box<dyn Error>> {
+    let backend = AnsiBackend::new(FailingWriter::default(), 5, 1);
+    let mut terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            synchronized_output: true,
+            ..Default::default()
+        },
+    )?;
+    let result = terminal.draw(|f| {
+        f.render_widget(Paragraph::new("Hey!"), f.size());
+    });
+
+    assert_eq!(
+        result.err().map(|err| err.to_string()),
+        Some("write failed".into())
+    );
+    let output = String::from_utf8(terminal.backend().writer().0.clone())?;
+    assert!(output.starts_with("\x1b[?2026h"), "{output:?}");
+    assert!(output.ends_with("\x1b[?2026l"), "{output:?}");
+    Ok(())
+}
+
+#[test]
+fn terminal_scrolls_shifted_rows() -> Result<(), Box<dyn Error>> {
+    let lines: Vec<String> = (0..20).map(|i| format!("line {i:02}   ")).collect();
+    let backend = AnsiBackend::new(Vec::new(), 10, 5);
+    let mut terminal = Terminal::new(backend)?;
+    let mut parser = AnsiParser::new(Rect::new(0, 0, 10, 5));
+    for offset in [0, 1, 3, 2] {
+        terminal.backend_mut().writer_mut().clear();
+        terminal.draw(|f| {
+            let text = lines[offset..offset + 5].join("\n");
+            f.render_widget(Paragraph::new(text), f.size());
+        })?;
+        let output = terminal.backend().writer().clone();
+        parser.process(&output);
+
+        let expected = Buffer::with_lines(
+            lines[offset..offset + 5]
+                .iter()
+                .map(String::as_str)
+                .collect(),
+        );
+        assert_buffer_eq!(parser.buffer().clone(), expected);
+        if offset != 0 {
+            // only the rows that scrolled into view are drawn
+            let output = String::from_utf8(output)?;
+            assert!(output.contains("\x1b[1;5r"), "{output:?}");
+            assert_eq!(
+                output.matches("line").count(),
+                if offset == 3 { 2 } else { 1 }
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn terminal_poll_and_read_events() -> Result<(), Box<dyn Error>> {
+    let mut backend = TestBackend::new(10, 2);
+    let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    backend.push_event(Event::Resize(20, 4));
+    backend.push_event(Event::Key(ctrl_c));
+    let mut terminal = Terminal::new(backend)?;
+
+    assert!(terminal.poll_event(Duration::ZERO)?);
+    // polling again doesn't consume the pending event
+    assert!(terminal.poll_event(Duration::ZERO)?);
+    assert_eq!(terminal.read_event()?, Event::Resize(20, 4));
+    assert_eq!(terminal.read_event()?, Event::Key(ctrl_c));
+    assert!(!terminal.poll_event(Duration::from_millis(10))?);
+    Ok(())
+}
+
+#[test]
+fn terminal_read_event_unsupported() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(AnsiBackend::new(Vec::new(), 10, 2))?;
+    let error = terminal.poll_event(Duration::ZERO).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::Unsupported);
+    Ok(())
+}
+
+#[test]
+fn terminal_draw_sets_cursor_style() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(TestBackend::new(10, 2))?;
+    terminal.draw(|f| {
+        f.set_cursor(1, 0);
+        f.set_cursor_style(CursorStyle::BlinkingBar);
+    })?;
+    assert_eq!(terminal.backend().cursor_style(), CursorStyle::BlinkingBar);
+
+    terminal.draw(|f| f.set_cursor(1, 0))?;
+    assert_eq!(
+        terminal.backend().cursor_style(),
+        CursorStyle::DefaultUserShape
+    );
+    Ok(())
+}
+
+#[test]
+fn terminal_cursor_style_is_written_on_change_and_restored_on_drop() -> Result<(), Box<dyn Error>> {
+    let mut output = Vec::new();
+    let mut terminal = Terminal::new(AnsiBackend::new(&mut output, 10, 2))?;
+    for _ in 0..2 {
+        terminal.draw(|f| f.set_cursor_style(CursorStyle::SteadyUnderline))?;
+    }
+    drop(terminal);
+    let output = String::from_utf8(output)?;
+    assert_eq!(output.matches("\x1b[4 q").count(), 1);
+    assert!(output.ends_with("\x1b[0 q"));
+    Ok(())
+}
+
+fn draw_lines<B: Backend>(terminal: &mut Terminal<B>, count: usize) -> Result<(), B::Error> {
+    terminal.draw(|f| {
+        let lines: Vec<_> = (1..=count).map(|i| format!("line {i}").into()).collect();
+        f.render_widget(Paragraph::new(lines), f.size());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn terminal_inline_auto_viewport_grows_and_shrinks() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::with_options(
+        TestBackend::new(10, 6),
+        TerminalOptions {
+            viewport: Viewport::InlineAuto { min: 1, max: 4 },
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 0, 10, 1));
+
+    draw_lines(&mut terminal, 3)?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 0, 10, 3));
+    assert_buffer_eq!(
+        terminal.backend().buffer().clone(),
+        Buffer::with_lines(vec![
+            "line 1    ",
+            "line 2    ",
+            "line 3    ",
+            "          ",
+            "          ",
+            "          "
+        ])
+    );
+
+    // content beyond the maximum height is cut
+    draw_lines(&mut terminal, 5)?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 0, 10, 4));
+
+    draw_lines(&mut terminal, 1)?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 0, 10, 1));
+    assert_buffer_eq!(
+        terminal.backend().buffer().clone(),
+        Buffer::with_lines(vec![
+            "line 1    ",
+            "          ",
+            "          ",
+            "          ",
+            "          ",
+            "          "
+        ])
+    );
+
+    // the viewport never gets smaller than the minimum height
+    draw_lines(&mut terminal, 0)?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 0, 10, 1));
+    Ok(())
+}
+
+#[test]
+fn terminal_inline_auto_viewport_scrolls_when_growing_at_the_bottom() -> Result<(), Box<dyn Error>>
+{
+    let mut backend = TestBackend::new(10, 4);
+    let history = Buffer::with_lines(vec!["history 1 ", "history 2 ", "history 3 "]);
+    backend.draw(history.content().iter().enumerate().map(|(i, cell)| {
+        let (x, y) = history.pos_of(i);
+        (x, y, cell)
+    }))?;
+    backend.set_cursor(0, 3)?;
+    let mut terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: Viewport::InlineAuto { min: 1, max: 3 },
+            ..Default::default()
+        },
+    )?;
+
+    terminal.draw(|f| {
+        let lines = vec!["line 1".into(), "line 2".into(), "line 3".into()];
+        f.render_widget(Paragraph::new(lines), f.size());
+        f.set_cursor(6, f.size().y + 2);
+    })?;
+    assert_eq!(terminal.get_frame().size(), Rect::new(0, 1, 10, 3));
+    assert_eq!(terminal.get_cursor()?, (6, 3));
+    assert_buffer_eq!(
+        terminal.backend().buffer().clone(),
+        Buffer::with_lines(vec!["history 3 ", "line 1    ", "line 2    ", "line 3    "])
+    );
+
+    // the viewport is at the bottom, so inserting a line scrolls the history up
+    terminal.insert_before(1, |buf| {
+        Paragraph::new("inserted").render(buf.area, buf);
+    })?;
+    draw_lines(&mut terminal, 2)?;
+    assert_buffer_eq!(
+        terminal.backend().buffer().clone(),
+        Buffer::with_lines(vec!["inserted  ", "line 1    ", "line 2    ", "          "])
+    );
+    Ok(())
+}
+
+#[test]
+fn terminal_draw_reports_frame_stats() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(AnsiBackend::new(Vec::new(), 10, 2))?;
+
+    let stats = terminal
+        .draw(|f| f.render_widget(Paragraph::new("Hello"), f.size()))?
+        .stats;
+    assert_eq!(stats.cells_changed, 5);
+    let written = terminal.backend().writer().len() as u64;
+    assert_eq!(stats.bytes_written, Some(written));
+
+    // nothing changed, only the cursor is hidden again
+    let stats = terminal
+        .draw(|f| f.render_widget(Paragraph::new("Hello"), f.size()))?
+        .stats;
+    assert_eq!(stats.cells_changed, 0);
+    let total = terminal.backend().writer().len() as u64;
+    assert_eq!(stats.bytes_written, Some(total - written));
+    assert_eq!(
+        stats.total_time(),
+        stats.render_time + stats.diff_time + stats.flush_time
+    );
+
+    let mut terminal = Terminal::new(TestBackend::new(10, 2))?;
+    let stats = terminal
+        .draw(|f| f.render_widget(Paragraph::new("Hi"), f.size()))?
+        .stats;
+    assert_eq!(stats.cells_changed, 2);
+    assert_eq!(stats.bytes_written, None);
+    Ok(())
+}
+
+#[test]
+fn terminal_keeps_frame_stats_history() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(TestBackend::new(10, 1))?;
+    terminal.draw(|_| {})?;
+    assert!(terminal.frame_stats().is_empty());
+
+    terminal.set_frame_stats_history(3);
+    for text in ["a", "ab", "abc", "abcd"] {
+        terminal.draw(|f| f.render_widget(Paragraph::new(text), f.size()))?;
+    }
+    let cells: Vec<usize> = terminal
+        .frame_stats()
+        .iter()
+        .map(|stats| stats.cells_changed)
+        .collect();
+    assert_eq!(cells, vec![1, 1, 1]);
+
+    terminal.draw(|_| {})?;
+    terminal.set_frame_stats_history(2);
+    let cells: Vec<usize> = terminal
+        .frame_stats()
+        .iter()
+        .map(|stats| stats.cells_changed)
+        .collect();
+    assert_eq!(cells, vec![1, 4]);
+    Ok(())
+}
+
+#[test]
+fn terminal_session_enables_and_restores_modes() -> Result<(), Box<dyn Error>> {
+    let options = SessionOptions {
+        mouse_capture: true,
+        ..Default::default()
+    };
+    let mut session = TerminalSession::with_options(TestBackend::new(10, 2), options)?;
+    assert!(session.backend().raw_mode_enabled());
+    assert!(session.backend().alternate_screen_active());
+    assert!(session.backend().mouse_capture_enabled());
+
+    session.draw(|f| f.render_widget(Paragraph::new("Hello"), f.size()))?;
+    session
+        .backend()
+        .assert_buffer(&Buffer::with_lines(vec!["Hello     ", "          "]));
+
+    session.restore()?;
+    assert!(!session.backend().raw_mode_enabled());
+    assert!(!session.backend().alternate_screen_active());
+    assert!(!session.backend().mouse_capture_enabled());
+    Ok(())
+}
+
+#[test]
+fn terminal_session_options() -> Result<(), Box<dyn Error>> {
+    let session = TerminalSession::new(TestBackend::new(10, 2))?;
+    assert!(session.backend().raw_mode_enabled());
+    assert!(session.backend().alternate_screen_active());
+    assert!(!session.backend().mouse_capture_enabled());
+
+    let options = SessionOptions {
+        alternate_screen: false,
+        panic_hook: false,
+        ..Default::default()
+    };
+    let session = TerminalSession::with_options(TestBackend::new(10, 2), options)?;
+    assert!(session.backend().raw_mode_enabled());
+    assert!(!session.backend().alternate_screen_active());
+    Ok(())
+}
+
+#[test]
+fn terminal_session_fails_with_unsupported_backend() {
+    // the ANSI backend leaves raw mode to whatever is on the other end of the writer
+    let result = TerminalSession::new(AnsiBackend::new(Vec::new(), 10, 2));
+    assert_eq!(
+        result.err().map(|err| err.kind()),
+        Some(std::io::ErrorKind::Unsupported)
+    );
+}
+
+#[test]
+fn terminal_session_restores_modes_enabled_on_the_terminal() -> Result<(), Box<dyn Error>> {
+    let mut session = TerminalSession::new(TestBackend::new(10, 2))?;
+    session.enable_mouse_capture()?;
+    session.leave_alternate_screen()?;
+    assert!(session.backend().mouse_capture_enabled());
+    assert!(!session.backend().alternate_screen_active());
+
+    session.restore()?;
+    assert!(!session.backend().raw_mode_enabled());
+    assert!(!session.backend().mouse_capture_enabled());
+    Ok(())
+}
+
+#[test]
+fn terminal_title_clipboard_and_bell() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(TestBackend::new(10, 2))?;
+    terminal.set_title("ratatui")?;
+    terminal.set_clipboard("copied text")?;
+    terminal.bell()?;
+    assert_eq!(terminal.backend().title(), Some("ratatui"));
+    assert_eq!(terminal.backend().clipboard(), Some("copied text"));
+    assert_eq!(terminal.backend().bell_count(), 1);
+    Ok(())
+}
+
+#[test]
+fn terminal_query_default_color() -> Result<(), Box<dyn Error>> {
+    let mut backend = TestBackend::new(10, 2);
+    backend.set_default_color(DefaultColor::Foreground, Some(Color::Rgb(205, 214, 244)));
+    let mut terminal = Terminal::new(backend)?;
+    let timeout = Duration::from_millis(100);
+    assert_eq!(
+        terminal.query_default_color(DefaultColor::Foreground, timeout)?,
+        Some(Color::Rgb(205, 214, 244))
+    );
+    assert_eq!(
+        terminal.query_default_color(DefaultColor::Background, timeout)?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn terminal_draw_composites_layers() -> Result<(), Box<dyn Error>> {
+    let mut terminal = Terminal::new(TestBackend::new(10, 3))?;
+    terminal.draw(|frame| {
+        let text = Paragraph::new(vec!["abcdefghij".into(); 3]);
+        frame.render_widget(text, frame.size());
+        let area = Rect::new(2, 0, 5, 3);
+        let mut popup = Layer::new(area).z_index(1);
+        Paragraph::new(vec!["".into(), " xyz".into()]).render(area, popup.buffer_mut());
+        frame.render_layer(popup.offset(1, 0));
+    })?;
+    terminal.backend().assert_buffer(&Buffer::with_lines(vec![
+        "abcdefghij",
+        "abcdxyzhij",
+        "abcdefghij",
+    ]));
+    Ok(())
+}
+
+#[test]
+fn terminal_draw_copies_offscreen_buffer() -> Result<(), Box<dyn Error>> {
+    let content_area = Rect::new(0, 0, 6, 10);
+    let mut content = Buffer::empty(content_area);
+    let lines: Vec<Line> = (0..10).map(|i| Line::from(format!("line {i}"))).collect();
+    Paragraph::new(lines).render(content_area, &mut content);
+
+    let mut terminal = Terminal::new(TestBackend::new(8, 3))?;
+    for scroll in [0, 4] {
+        terminal.draw(|frame| {
+            let area = Rect::new(0, scroll, content_area.width, 2);
+            frame.buffer_mut().copy_from(&content, area, 1, 1);
+        })?;
+    }
+    terminal.backend().assert_buffer(&Buffer::with_lines(vec![
+        "        ", " line 4 ", " line 5 ",
+    ]));
+    Ok(())
+}
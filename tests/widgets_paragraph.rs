@@ -364,3 +364,31 @@ fn widgets_paragraph_can_align_spans() {
         ]),
     );
 }
+
+#[test]
+fn widgets_paragraph_wraps_hyperlinks() {
+    let line = Line::from(vec![
+        Span::raw("see "),
+        Span::raw("the docs").hyperlink("https://ratatui.rs"),
+        Span::raw(" now"),
+    ]);
+    let paragraph = Paragraph::new(line).wrap(Wrap { trim: true });
+
+    let mut expected = Buffer::with_lines(vec!["see the ", "docs now", "        "]);
+    expected.set_span(4, 0, &Span::raw("the").hyperlink("https://ratatui.rs"), 3);
+    expected.set_span(0, 1, &Span::raw("docs").hyperlink("https://ratatui.rs"), 4);
+    test_case(paragraph, expected);
+}
+
+#[test]
+fn widgets_paragraph_truncates_hyperlinks() {
+    let line = Line::from(vec![
+        Span::raw("ab"),
+        Span::raw("cdefgh").hyperlink("https://ratatui.rs"),
+    ]);
+    let paragraph = Paragraph::new(line).scroll((0, 1));
+
+    let mut expected = Buffer::with_lines(vec!["bcdefg"]);
+    expected.set_span(1, 0, &Span::raw("cdefg").hyperlink("https://ratatui.rs"), 5);
+    test_case(paragraph, expected);
+}